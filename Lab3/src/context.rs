@@ -1,14 +1,24 @@
+use crate::graphics::fill::Fill;
 use crate::graphics::grid::Grid;
+use crate::graphics::reference_image::ReferenceImage;
+use crate::models::analysis::Analysis;
 use crate::models::model::Model;
 use crate::operations::animation::AnimationSettings;
+use crate::operations::history::History;
 use crate::operations::offset::Offset;
 use crate::operations::rotation::Rotation;
+use crate::operations::selection::Selection;
 
 #[derive(Default)]
 pub struct Context {
+    pub analysis: Analysis,
     pub animation_settings: AnimationSettings,
+    pub fill: Fill,
     pub grid: Grid,
+    pub history: History,
+    pub reference_image: ReferenceImage,
     pub model: Model,
     pub euclidean_offset: Offset,
     pub euclidean_rotation: Rotation,
+    pub selection: Selection,
 }
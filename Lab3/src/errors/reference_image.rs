@@ -0,0 +1,23 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum ReferenceImageError {
+    #[error("Cannot open file using specified path.")]
+    CantOpenFile(String),
+
+    #[error("Failed to decode image.")]
+    DecodingFailed(String),
+
+    #[error("Image has zero width or height.")]
+    EmptyImage,
+}
+
+impl ReferenceImageError {
+    pub fn get_additional_info(&self) -> Option<&String> {
+        // Match only variants with String fields dynamically
+        match self {
+            Self::CantOpenFile(s) | Self::DecodingFailed(s) => Some(s),
+            _ => None,
+        }
+    }
+}
@@ -21,9 +21,10 @@ impl MoveablePoint {
         Id::new(Uuid::new_v4())
     }
 
+    // Returns offset (in cm) applied by dragging.
     pub fn update_self(
         &mut self, radius: f32, screen_params: ScreenParams, ui: &egui::Ui, response: &Response,
-    ) {
+    ) -> Vec2 {
         let size = Vec2::splat(2.0 * radius);
 
         let area = Rect::from_center_size(self.to_screen(screen_params).to_pos2(), size);
@@ -37,6 +38,8 @@ impl MoveablePoint {
         if offset.x != 0.0 || offset.y != 0.0 {
             ui.ctx().request_repaint();
         }
+
+        offset
     }
 
     pub fn show_tooltip(
@@ -0,0 +1,126 @@
+use crate::geometry::line::Line;
+use crate::geometry::point::Point;
+use crate::graphics::screen::ScreenParams;
+use crate::traits::positionable::Positionable;
+use crate::ui::styles::colors;
+use eframe::epaint::{Color32, Mesh, Shape};
+use egui::{Pos2, Rect};
+
+// Height of one scan-line in pixels.
+const SCANLINE_HEIGHT_PX: f32 = 1.0;
+
+pub struct Fill {
+    pub is_enabled: bool,
+
+    pub rule: FillRule,
+    pub color: Color32,
+    pub opacity: f32,
+}
+
+impl Default for Fill {
+    fn default() -> Self {
+        Self {
+            is_enabled: false,
+
+            rule: FillRule::EvenOdd,
+            color: colors::BLUE,
+            opacity: 0.5,
+        }
+    }
+}
+
+impl Fill {
+    // Scan-line rasterization of the flattened outline (in world coordinates).
+    // Outline is closed implicitly by edge from the last point to the first one.
+    pub fn shape(&self, outline: &[Line<Point>], screen_params: ScreenParams, clip: Rect) -> Shape {
+        let polygon: Vec<Pos2> = Self::polygon(outline)
+            .iter()
+            .map(|point| point.to_screen(screen_params).to_pos2())
+            .collect();
+
+        let mut mesh = Mesh::default();
+        if polygon.len() < 3 {
+            return Shape::mesh(mesh);
+        }
+
+        let color = Color32::from_rgba_unmultiplied(
+            self.color.r(),
+            self.color.g(),
+            self.color.b(),
+            (self.opacity.clamp(0.0, 1.0) * 255.0) as u8,
+        );
+
+        let (min_y, max_y) = polygon
+            .iter()
+            .fold((f32::MAX, f32::MIN), |(min, max), point| {
+                (f32::min(min, point.y), f32::max(max, point.y))
+            });
+        let min_y = f32::max(min_y, clip.min.y).floor();
+        let max_y = f32::min(max_y, clip.max.y).ceil();
+
+        let mut crossings: Vec<(f32, i32)> = vec![];
+        let mut y = min_y;
+        while y < max_y {
+            let scan_y = y + SCANLINE_HEIGHT_PX / 2.0;
+
+            crossings.clear();
+            for i in 0..polygon.len() {
+                let start = polygon[i];
+                let end = polygon[(i + 1) % polygon.len()];
+
+                let direction = if start.y <= scan_y && scan_y < end.y {
+                    1
+                } else if end.y <= scan_y && scan_y < start.y {
+                    -1
+                } else {
+                    continue;
+                };
+
+                let x = start.x + (scan_y - start.y) / (end.y - start.y) * (end.x - start.x);
+                crossings.push((x, direction));
+            }
+            crossings.sort_by(|first, second| first.0.total_cmp(&second.0));
+
+            let mut winding = 0;
+            for pair in crossings.windows(2) {
+                winding += pair[0].1;
+
+                let is_inside = match self.rule {
+                    FillRule::EvenOdd => winding % 2 != 0,
+                    FillRule::NonZero => winding != 0,
+                };
+
+                let (left, right) = (
+                    f32::max(pair[0].0, clip.min.x),
+                    f32::min(pair[1].0, clip.max.x),
+                );
+                if is_inside && left < right {
+                    let span = Rect::from_min_max(
+                        Pos2::new(left, y),
+                        Pos2::new(right, y + SCANLINE_HEIGHT_PX),
+                    );
+                    mesh.add_colored_rect(span, color);
+                }
+            }
+
+            y += SCANLINE_HEIGHT_PX;
+        }
+
+        Shape::mesh(mesh)
+    }
+
+    fn polygon(outline: &[Line<Point>]) -> Vec<Point> {
+        let mut points: Vec<Point> = outline.iter().map(|line| line.start).collect();
+        if let Some(last) = outline.last() {
+            points.push(last.end);
+        }
+
+        points
+    }
+}
+
+#[derive(PartialEq)]
+pub enum FillRule {
+    EvenOdd,
+    NonZero,
+}
@@ -0,0 +1,157 @@
+use crate::errors::reference_image::ReferenceImageError;
+use crate::geometry::moveable_point::MoveablePoint;
+use crate::geometry::point::Point;
+use crate::graphics::screen::ScreenParams;
+use crate::math::angle::Angle;
+use crate::traits::positionable::Positionable;
+use crate::ui::styles::colors;
+use eframe::epaint::{Color32, Mesh, Shape, Vertex};
+use egui::{ColorImage, Pos2, TextureHandle, TextureOptions};
+use image::ImageError;
+use std::path::PathBuf;
+
+// Length of the longer image side (in cm) when scale is 1.0.
+pub const BASE_SIZE_CM: f32 = 10.0;
+
+pub struct ReferenceImage {
+    pub is_enabled: bool,
+    pub is_anchor_enabled: bool,
+
+    pub anchor: MoveablePoint,
+    pub scale: f32,
+    pub angle: f32,
+    pub opacity: f32,
+
+    pub anchor_color: Color32,
+
+    texture: Option<TextureHandle>,
+    size: (f32, f32),
+}
+
+impl Default for ReferenceImage {
+    fn default() -> Self {
+        Self {
+            is_enabled: false,
+            is_anchor_enabled: false,
+
+            anchor: MoveablePoint::new(0.0, 0.0),
+            scale: 1.0,
+            angle: 0.0,
+            opacity: 0.5,
+
+            anchor_color: colors::PURPLE,
+
+            texture: None,
+            size: (0.0, 0.0),
+        }
+    }
+}
+
+impl ReferenceImage {
+    pub fn load(&mut self, path: PathBuf, ctx: &egui::Context) -> Result<(), ReferenceImageError> {
+        let image = image::open(&path).map_err(|err| match err {
+            ImageError::IoError(err) => ReferenceImageError::CantOpenFile(err.to_string()),
+            err => ReferenceImageError::DecodingFailed(err.to_string()),
+        })?;
+
+        let image = image.to_rgba8();
+        let (width, height) = image.dimensions();
+        if width == 0 || height == 0 {
+            return Err(ReferenceImageError::EmptyImage);
+        }
+
+        let color_image = ColorImage::from_rgba_unmultiplied(
+            [width as usize, height as usize],
+            image.as_flat_samples().as_slice(),
+        );
+
+        let name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or(String::from("Reference Image"));
+
+        self.texture = Some(ctx.load_texture(name, color_image, TextureOptions::LINEAR));
+        self.size = (width as f32, height as f32);
+        self.is_enabled = true;
+
+        Ok(())
+    }
+
+    pub fn unload(&mut self) {
+        self.texture = None;
+        self.size = (0.0, 0.0);
+        self.is_enabled = false;
+    }
+
+    pub fn ensure_image_loaded(&mut self) {
+        if !self.is_loaded() {
+            self.is_enabled = false;
+        }
+    }
+
+    pub fn is_loaded(&self) -> bool {
+        self.texture.is_some()
+    }
+
+    pub fn reset_transform(&mut self) {
+        self.anchor = MoveablePoint::new(0.0, 0.0);
+        self.scale = 1.0;
+        self.angle = 0.0;
+    }
+
+    // Size of the image in world coordinates (cm).
+    pub fn world_size(&self) -> (f32, f32) {
+        let longer_side = f32::max(self.size.0, self.size.1);
+        if longer_side == 0.0 {
+            return (0.0, 0.0);
+        }
+
+        let cm_per_px = BASE_SIZE_CM / longer_side * self.scale;
+
+        (self.size.0 * cm_per_px, self.size.1 * cm_per_px)
+    }
+
+    pub fn shape(&self, screen_params: ScreenParams) -> Option<Shape> {
+        let texture = self.texture.as_ref()?;
+
+        let (width, height) = self.world_size();
+        let (half_width, half_height) = (width / 2.0, height / 2.0);
+
+        // Corners in image order: top-left, top-right, bottom-right, bottom-left.
+        let corners = [
+            (-half_width, half_height),
+            (half_width, half_height),
+            (half_width, -half_height),
+            (-half_width, -half_height),
+        ];
+        let uvs = [
+            Pos2::new(0.0, 0.0),
+            Pos2::new(1.0, 0.0),
+            Pos2::new(1.0, 1.0),
+            Pos2::new(0.0, 1.0),
+        ];
+
+        let angle = Angle::from_degree(self.angle).radian();
+        let (sin, cos) = angle.sin_cos();
+
+        let opacity = (self.opacity.clamp(0.0, 1.0) * 255.0) as u8;
+        let tint = Color32::from_white_alpha(opacity);
+
+        let mut mesh = Mesh::with_texture(texture.id());
+        for (corner, uv) in corners.iter().zip(uvs) {
+            let x = self.anchor.x + corner.0 * cos - corner.1 * sin;
+            let y = self.anchor.y + corner.0 * sin + corner.1 * cos;
+
+            let pos = Point::new(x, y).to_screen(screen_params).to_pos2();
+            mesh.vertices.push(Vertex {
+                pos,
+                uv,
+                color: tint,
+            });
+        }
+        mesh.add_triangle(0, 1, 2);
+        mesh.add_triangle(0, 2, 3);
+
+        Some(Shape::mesh(mesh))
+    }
+}
@@ -29,6 +29,7 @@ pub mod context;
 pub mod errors {
     pub mod env;
     pub mod log;
+    pub mod reference_image;
 }
 pub mod geometry {
     pub mod line;
@@ -36,7 +37,9 @@ pub mod geometry {
     pub mod point;
 }
 pub mod graphics {
+    pub mod fill;
     pub mod grid;
+    pub mod reference_image;
     pub mod screen;
 }
 pub mod logger;
@@ -44,14 +47,19 @@ pub mod math {
     pub mod angle;
 }
 pub mod models {
+    pub mod analysis;
     pub mod bezier_curve;
     pub mod bezier_point;
     pub mod model;
+    pub mod segment;
 }
 pub mod operations {
     pub mod animation;
+    pub mod history;
     pub mod offset;
     pub mod rotation;
+    pub mod scaling;
+    pub mod selection;
 }
 pub mod traits {
     pub mod positionable;
@@ -61,6 +69,7 @@ pub mod ui {
     pub mod core;
     pub mod components {
         pub mod canvas;
+        pub mod message_box;
         pub mod settings;
     }
     pub mod styles {
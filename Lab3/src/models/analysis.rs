@@ -0,0 +1,307 @@
+use crate::geometry::line::Line;
+use crate::geometry::point::Point;
+use crate::graphics::screen::ScreenParams;
+use crate::models::model::Model;
+use crate::models::segment::{lerp, BoundingBox, Segment};
+use crate::traits::positionable::Positionable;
+use crate::ui::styles::{colors, strokes};
+use egui::{Color32, Stroke};
+
+// Gauss-Legendre nodes and weights on [-1; 1].
+const GAUSS_NODES: [f32; 5] = [0.0, -0.538_469_3, 0.538_469_3, -0.906_179_8, 0.906_179_8];
+const GAUSS_WEIGHTS: [f32; 5] = [
+    0.568_888_9,
+    0.478_628_67,
+    0.478_628_67,
+    0.236_926_88,
+    0.236_926_88,
+];
+// Number of sub-intervals of composite quadrature for arc length.
+const LENGTH_INTERVALS: usize = 8;
+
+// Ends closer than this are considered the same point.
+const CLOSURE_EPSILON: f32 = 1e-3;
+
+// Self-intersection search parameters.
+const INTERSECTION_FLATNESS: f32 = 1e-4;
+const INTERSECTION_MAX_DEPTH: u32 = 24;
+const INTERSECTION_MERGE_DISTANCE: f32 = 1e-3;
+const PARAMETER_EPSILON: f32 = 1e-3;
+
+pub struct Analysis {
+    pub is_enabled: bool,
+
+    pub is_bounding_box_enabled: bool,
+    pub is_centroid_enabled: bool,
+    pub are_intersections_enabled: bool,
+
+    pub bounding_box_stroke: Stroke,
+    pub centroid_color: Color32,
+    pub intersection_color: Color32,
+
+    pub segment_lengths: Vec<f32>,
+    pub total_length: f32,
+    pub signed_area: f32,
+    pub centroid: Option<Point>,
+    pub bounding_box: Option<BoundingBox>,
+    pub intersections: Vec<Point>,
+    pub is_closed: bool,
+}
+
+impl Default for Analysis {
+    fn default() -> Self {
+        Self {
+            is_enabled: false,
+
+            is_bounding_box_enabled: true,
+            is_centroid_enabled: true,
+            are_intersections_enabled: true,
+
+            bounding_box_stroke: strokes::bounding_box_blue(0.03),
+            centroid_color: colors::ORANGE,
+            intersection_color: colors::PURPLE,
+
+            segment_lengths: vec![],
+            total_length: 0.0,
+            signed_area: 0.0,
+            centroid: None,
+            bounding_box: None,
+            intersections: vec![],
+            is_closed: false,
+        }
+    }
+}
+
+impl Analysis {
+    pub fn update(&mut self, model: &Model) {
+        let segments = model.segments();
+
+        self.segment_lengths = segments.iter().map(arc_length).collect();
+        self.total_length = self.segment_lengths.iter().sum();
+
+        self.bounding_box = segments
+            .iter()
+            .map(|segment| segment.bounding_box())
+            .reduce(|first, second| first.union(&second));
+
+        // Open contour is closed by chord from the last point to the first one.
+        let mut contour = segments.clone();
+        self.is_closed = true;
+        if let (Some(first), Some(last)) = (segments.first(), segments.last()) {
+            if distance(last.end, first.start) > CLOSURE_EPSILON {
+                self.is_closed = false;
+                contour.push(Segment {
+                    start: last.end,
+                    middle: lerp(last.end, first.start, 0.5),
+                    end: first.start,
+                });
+            }
+        }
+
+        self.signed_area = contour.iter().map(signed_area).sum();
+        self.centroid = centroid(&contour, self.signed_area);
+        self.intersections = self_intersections(&segments, self.is_closed);
+    }
+
+    pub fn bounding_box_lines(&self, screen_params: ScreenParams) -> Vec<Line<Point>> {
+        let Some(bounding_box) = self.bounding_box else {
+            return vec![];
+        };
+
+        let mut stroke = self.bounding_box_stroke;
+        stroke.width = screen_params.value_cm_to_px(self.bounding_box_stroke.width);
+
+        let corners = [
+            Point::new(bounding_box.min.x, bounding_box.min.y),
+            Point::new(bounding_box.max.x, bounding_box.min.y),
+            Point::new(bounding_box.max.x, bounding_box.max.y),
+            Point::new(bounding_box.min.x, bounding_box.max.y),
+        ];
+
+        (0..corners.len())
+            .map(|i| Line::new(corners[i], corners[(i + 1) % corners.len()], stroke))
+            .collect()
+    }
+}
+
+fn distance(first: Point, second: Point) -> f32 {
+    f32::sqrt((second.x - first.x).powf(2.0) + (second.y - first.y).powf(2.0))
+}
+
+fn cross(first: Point, second: Point) -> f32 {
+    first.x * second.y - first.y * second.x
+}
+
+// Composite Gauss-Legendre quadrature of |B'(t)|.
+fn arc_length(segment: &Segment) -> f32 {
+    let interval = 1.0 / LENGTH_INTERVALS as f32;
+
+    (0..LENGTH_INTERVALS)
+        .map(|i| {
+            let center = (i as f32 + 0.5) * interval;
+
+            GAUSS_NODES
+                .iter()
+                .zip(GAUSS_WEIGHTS)
+                .map(|(node, weight)| {
+                    let (dx, dy) = segment.derivative(center + node * interval / 2.0);
+                    weight * f32::sqrt(dx * dx + dy * dy)
+                })
+                .sum::<f32>()
+                * interval
+                / 2.0
+        })
+        .sum()
+}
+
+// Closed form of 1/2 * integral(x dy - y dx) over quadratic segment.
+fn signed_area(segment: &Segment) -> f32 {
+    cross(segment.start, segment.middle) / 3.0
+        + cross(segment.start, segment.end) / 6.0
+        + cross(segment.middle, segment.end) / 3.0
+}
+
+// Green's theorem: Cx = 1/(2A) * integral(x^2 dy), Cy = -1/(2A) * integral(y^2 dx).
+// Integrands are polynomials of degree 5, so 3 Gauss nodes give exact result.
+fn centroid(contour: &[Segment], area: f32) -> Option<Point> {
+    if area.abs() < f32::EPSILON {
+        return None;
+    }
+
+    let nodes = [0.0, -0.774_596_7, 0.774_596_7];
+    let weights = [0.888_888_9, 0.555_555_6, 0.555_555_6];
+
+    let (mut moment_x, mut moment_y) = (0.0, 0.0);
+    for segment in contour {
+        for (node, weight) in nodes.iter().zip(weights) {
+            let t = 0.5 + node / 2.0;
+            let point = segment.point(t);
+            let (dx, dy) = segment.derivative(t);
+
+            moment_x += weight / 2.0 * point.x * point.x * dy;
+            moment_y -= weight / 2.0 * point.y * point.y * dx;
+        }
+    }
+
+    Some(Point::new(moment_x / (2.0 * area), moment_y / (2.0 * area)))
+}
+
+// Recursive bounding-box subdivision of every pair of segments.
+fn self_intersections(segments: &[Segment], is_closed: bool) -> Vec<Point> {
+    let mut points: Vec<Point> = vec![];
+
+    for i in 0..segments.len() {
+        for j in (i + 1)..segments.len() {
+            let is_adjacent = j == i + 1;
+            let is_wrapped = is_closed && i == 0 && j == segments.len() - 1;
+
+            let mut hits: Vec<(Point, f32, f32)> = vec![];
+            intersect(
+                &segments[i],
+                (0.0, 1.0),
+                &segments[j],
+                (0.0, 1.0),
+                0,
+                &mut hits,
+            );
+
+            for (point, t_first, t_second) in hits {
+                // Shared endpoints of neighbouring segments are not intersections.
+                if is_adjacent && t_first > 1.0 - PARAMETER_EPSILON && t_second < PARAMETER_EPSILON
+                {
+                    continue;
+                }
+                if is_wrapped && t_first < PARAMETER_EPSILON && t_second > 1.0 - PARAMETER_EPSILON {
+                    continue;
+                }
+
+                if points
+                    .iter()
+                    .all(|known| distance(*known, point) > INTERSECTION_MERGE_DISTANCE)
+                {
+                    points.push(point);
+                }
+            }
+        }
+    }
+
+    points
+}
+
+fn intersect(
+    first: &Segment, first_range: (f32, f32), second: &Segment, second_range: (f32, f32),
+    depth: u32, hits: &mut Vec<(Point, f32, f32)>,
+) {
+    if !first.control_box().overlaps(&second.control_box()) {
+        return;
+    }
+
+    let is_flat =
+        first.deviation() <= INTERSECTION_FLATNESS && second.deviation() <= INTERSECTION_FLATNESS;
+    if is_flat || depth >= INTERSECTION_MAX_DEPTH {
+        if let Some((point, t_first, t_second)) =
+            chord_intersection(first.start, first.end, second.start, second.end)
+        {
+            hits.push((
+                point,
+                first_range.0 + (first_range.1 - first_range.0) * t_first,
+                second_range.0 + (second_range.1 - second_range.0) * t_second,
+            ));
+        }
+        return;
+    }
+
+    let (first_left, first_right) = first.split(0.5);
+    let (second_left, second_right) = second.split(0.5);
+    let first_middle = (first_range.0 + first_range.1) / 2.0;
+    let second_middle = (second_range.0 + second_range.1) / 2.0;
+
+    let halves_first = [
+        (first_left, (first_range.0, first_middle)),
+        (first_right, (first_middle, first_range.1)),
+    ];
+    let halves_second = [
+        (second_left, (second_range.0, second_middle)),
+        (second_right, (second_middle, second_range.1)),
+    ];
+
+    for (segment_first, range_first) in &halves_first {
+        for (segment_second, range_second) in &halves_second {
+            intersect(
+                segment_first,
+                *range_first,
+                segment_second,
+                *range_second,
+                depth + 1,
+                hits,
+            );
+        }
+    }
+}
+
+// Intersection of two line segments with local parameters of both.
+fn chord_intersection(
+    first_start: Point, first_end: Point, second_start: Point, second_end: Point,
+) -> Option<(Point, f32, f32)> {
+    let first = Point::new(first_end.x - first_start.x, first_end.y - first_start.y);
+    let second = Point::new(second_end.x - second_start.x, second_end.y - second_start.y);
+    let between = Point::new(
+        second_start.x - first_start.x,
+        second_start.y - first_start.y,
+    );
+
+    let denominator = cross(first, second);
+    if denominator.abs() < f32::EPSILON {
+        return None;
+    }
+
+    let t_first = cross(between, second) / denominator;
+    let t_second = cross(between, first) / denominator;
+
+    let range = 0.0..=1.0;
+    if !range.contains(&t_first) || !range.contains(&t_second) {
+        return None;
+    }
+
+    Some((lerp(first_start, first_end, t_first), t_first, t_second))
+}
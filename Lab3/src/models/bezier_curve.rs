@@ -1,5 +1,7 @@
 use crate::geometry::line::Line;
 use crate::geometry::point::Point;
+use crate::graphics::screen::ScreenParams;
+use crate::models::segment::Segment;
 use crate::traits::positionable::Positionable;
 use egui::Stroke;
 
@@ -25,6 +27,41 @@ pub fn bezier_curve<T: Positionable>(
     });
 }
 
+// Recursion limit for adaptive flattening, gives at most 2^16 lines per segment.
+const MAX_SUBDIVISION_DEPTH: u32 = 16;
+
+pub fn bezier_curve_adaptive<T: Positionable>(
+    lines: &mut Vec<Line<Point>>, stroke: &Stroke, tolerance_px: f32, screen_params: ScreenParams,
+    control_first: &T, control_second: &T, defining: &T,
+) {
+    debug_assert!(tolerance_px > 0.0);
+
+    // Flatness is measured on screen, so the tolerance is converted into world units.
+    let tolerance = screen_params.value_px_to_cm(tolerance_px);
+
+    let segment = Segment::new(control_first, defining, control_second);
+
+    let mut points: Vec<Point> = vec![segment.start];
+    subdivide(&mut points, tolerance, segment, 0);
+
+    points.windows(2).for_each(|pair| {
+        let line = Line::new(pair[0], pair[1], *stroke);
+        lines.push(line);
+    });
+}
+
+fn subdivide(points: &mut Vec<Point>, tolerance: f32, segment: Segment, depth: u32) {
+    if segment.deviation() <= tolerance || depth >= MAX_SUBDIVISION_DEPTH {
+        points.push(segment.end);
+        return;
+    }
+
+    let (left, right) = segment.split(0.5);
+
+    subdivide(points, tolerance, left, depth + 1);
+    subdivide(points, tolerance, right, depth + 1);
+}
+
 fn formula<T: Positionable>(control_first: &T, control_second: &T, defining: &T, u: f32) -> Point {
     debug_assert!((0.0..=1.0001).contains(&u));
 
@@ -23,7 +23,7 @@ impl BezierPoint {
     }
 }
 
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq)]
 pub enum BezierPointType {
     Control,
     Defining,
@@ -1,8 +1,9 @@
 use crate::geometry::line::Line;
 use crate::geometry::point::Point;
 use crate::graphics::screen::ScreenParams;
-use crate::models::bezier_curve::bezier_curve;
-use crate::models::bezier_point::BezierPoint;
+use crate::models::bezier_curve::{bezier_curve, bezier_curve_adaptive};
+use crate::models::bezier_point::{BezierPoint, BezierPointType};
+use crate::models::segment::Segment;
 use crate::traits::positionable::Positionable;
 use crate::ui::styles::{colors, strokes};
 use egui::{Color32, Stroke};
@@ -17,6 +18,8 @@ pub struct Model {
     pub model_stroke: Stroke,
 
     pub bezier_step: f32,
+    pub flattening: Flattening,
+    pub flatness_tolerance: f32,
 
     pub are_tooltips_enabled: bool,
     pub is_skeleton_enabled: bool,
@@ -36,6 +39,8 @@ impl Default for Model {
 
             model_stroke: strokes::model_black(0.1),
             bezier_step: 0.1,
+            flattening: Flattening::Uniform,
+            flatness_tolerance: 0.25,
 
             are_tooltips_enabled: false,
             is_skeleton_enabled: false,
@@ -77,19 +82,93 @@ impl Model {
             let defining = &self.points[i + 1];
             let control_second = &self.points[i + 2];
 
-            bezier_curve(
-                &mut vec,
-                &stroke,
-                self.bezier_step,
-                &control_first.point,
-                &control_second.point,
-                &defining.point,
-            )
+            match self.flattening {
+                Flattening::Uniform => bezier_curve(
+                    &mut vec,
+                    &stroke,
+                    self.bezier_step,
+                    &control_first.point,
+                    &control_second.point,
+                    &defining.point,
+                ),
+                Flattening::Adaptive => bezier_curve_adaptive(
+                    &mut vec,
+                    &stroke,
+                    self.flatness_tolerance,
+                    screen_params,
+                    &control_first.point,
+                    &control_second.point,
+                    &defining.point,
+                ),
+            }
         }
 
         vec
     }
 
+    pub fn segments(&self) -> Vec<Segment> {
+        let step = 2;
+        let chunk_size = 3;
+
+        (0..self.points.len().saturating_sub(chunk_size - 1))
+            .step_by(step)
+            .map(|i| {
+                Segment::new(
+                    &self.points[i].point,
+                    &self.points[i + 1].point,
+                    &self.points[i + 2].point,
+                )
+            })
+            .collect()
+    }
+
+    // Index of the segment, that contains point with specified index.
+    pub fn segment_index(&self, index: usize) -> usize {
+        let segments = (self.points.len() - 1) / 2;
+
+        usize::min(index.saturating_sub(1) / 2, segments.saturating_sub(1))
+    }
+
+    // Splits segment with specified point in the middle, so the shape stays the same.
+    pub fn insert_point(&mut self, index: usize) {
+        if self.points.len() < 3 || index >= self.points.len() {
+            return;
+        }
+
+        let start = self.segment_index(index) * 2;
+        let segment = Segment::new(
+            &self.points[start].point,
+            &self.points[start + 1].point,
+            &self.points[start + 2].point,
+        );
+        let (left, right) = segment.split(0.5);
+
+        self.points[start + 1] = BezierPoint::defining(left.middle.x, left.middle.y);
+        self.points.splice(
+            (start + 2)..(start + 2),
+            [
+                BezierPoint::control(left.end.x, left.end.y),
+                BezierPoint::defining(right.middle.x, right.middle.y),
+            ],
+        );
+    }
+
+    // Only inner control points can be removed, neighbouring segments are merged.
+    pub fn can_remove_point(&self, index: usize) -> bool {
+        self.points.len() > 3
+            && index > 0
+            && index < self.points.len() - 1
+            && self.points[index].kind == BezierPointType::Control
+    }
+
+    pub fn remove_point(&mut self, index: usize) {
+        if !self.can_remove_point(index) {
+            return;
+        }
+
+        self.points.drain(index..=(index + 1));
+    }
+
     pub fn default_points() -> Vec<BezierPoint> {
         vec![
             BezierPoint::control(-5.691, -0.951),  // 1
@@ -216,3 +295,9 @@ impl Model {
         ]
     }
 }
+
+#[derive(PartialEq)]
+pub enum Flattening {
+    Uniform,
+    Adaptive,
+}
@@ -0,0 +1,159 @@
+use crate::geometry::point::Point;
+use crate::traits::positionable::Positionable;
+
+// Quadratic Bezier segment: `start` and `end` lie on the curve, `middle` defines its shape.
+#[derive(Debug, Clone, Copy)]
+pub struct Segment {
+    pub start: Point,
+    pub middle: Point,
+    pub end: Point,
+}
+
+impl Segment {
+    pub fn new<T: Positionable>(control_first: &T, defining: &T, control_second: &T) -> Self {
+        Self {
+            start: Point::new(control_first.x(), control_first.y()),
+            middle: Point::new(defining.x(), defining.y()),
+            end: Point::new(control_second.x(), control_second.y()),
+        }
+    }
+
+    pub fn point(&self, t: f32) -> Point {
+        let x = self.start.x * (1.0 - t).powf(2.0)
+            + 2.0 * self.middle.x * t * (1.0 - t)
+            + self.end.x * t.powf(2.0);
+        let y = self.start.y * (1.0 - t).powf(2.0)
+            + 2.0 * self.middle.y * t * (1.0 - t)
+            + self.end.y * t.powf(2.0);
+
+        Point::new(x, y)
+    }
+
+    pub fn derivative(&self, t: f32) -> (f32, f32) {
+        let x = 2.0 * (1.0 - t) * (self.middle.x - self.start.x)
+            + 2.0 * t * (self.end.x - self.middle.x);
+        let y = 2.0 * (1.0 - t) * (self.middle.y - self.start.y)
+            + 2.0 * t * (self.end.y - self.middle.y);
+
+        (x, y)
+    }
+
+    // Maximum distance between segment and its chord: |P0 - 2P1 + P2| / 4.
+    pub fn deviation(&self) -> f32 {
+        0.25 * f32::sqrt(
+            (self.start.x - 2.0 * self.middle.x + self.end.x).powf(2.0)
+                + (self.start.y - 2.0 * self.middle.y + self.end.y).powf(2.0),
+        )
+    }
+
+    // De Casteljau split at parameter t.
+    pub fn split(&self, t: f32) -> (Self, Self) {
+        let left = lerp(self.start, self.middle, t);
+        let right = lerp(self.middle, self.end, t);
+        let center = lerp(left, right, t);
+
+        (
+            Self {
+                start: self.start,
+                middle: left,
+                end: center,
+            },
+            Self {
+                start: center,
+                middle: right,
+                end: self.end,
+            },
+        )
+    }
+
+    // Box around all three points. Always contains the segment.
+    pub fn control_box(&self) -> BoundingBox {
+        BoundingBox::from_points(&[self.start, self.middle, self.end])
+    }
+
+    // Tight box: endpoints plus extrema, where derivative of a coordinate is zero.
+    pub fn bounding_box(&self) -> BoundingBox {
+        let mut points = vec![self.start, self.end];
+
+        let denominator = (
+            self.start.x - 2.0 * self.middle.x + self.end.x,
+            self.start.y - 2.0 * self.middle.y + self.end.y,
+        );
+        let roots = [
+            (self.start.x - self.middle.x) / denominator.0,
+            (self.start.y - self.middle.y) / denominator.1,
+        ];
+
+        for t in roots {
+            if t.is_finite() && (0.0..=1.0).contains(&t) {
+                points.push(self.point(t));
+            }
+        }
+
+        BoundingBox::from_points(&points)
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct BoundingBox {
+    pub min: Point,
+    pub max: Point,
+}
+
+impl BoundingBox {
+    pub fn from_points(points: &[Point]) -> Self {
+        let mut min = Point::new(f32::MAX, f32::MAX);
+        let mut max = Point::new(f32::MIN, f32::MIN);
+
+        for point in points {
+            min.x = f32::min(min.x, point.x);
+            min.y = f32::min(min.y, point.y);
+            max.x = f32::max(max.x, point.x);
+            max.y = f32::max(max.y, point.y);
+        }
+
+        Self { min, max }
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        Self {
+            min: Point::new(
+                f32::min(self.min.x, other.min.x),
+                f32::min(self.min.y, other.min.y),
+            ),
+            max: Point::new(
+                f32::max(self.max.x, other.max.x),
+                f32::max(self.max.y, other.max.y),
+            ),
+        }
+    }
+
+    pub fn overlaps(&self, other: &Self) -> bool {
+        self.min.x <= other.max.x
+            && other.min.x <= self.max.x
+            && self.min.y <= other.max.y
+            && other.min.y <= self.max.y
+    }
+
+    pub fn width(&self) -> f32 {
+        self.max.x - self.min.x
+    }
+
+    pub fn height(&self) -> f32 {
+        self.max.y - self.min.y
+    }
+
+    pub fn center(&self) -> Point {
+        Point::new(
+            (self.min.x + self.max.x) / 2.0,
+            (self.min.y + self.max.y) / 2.0,
+        )
+    }
+}
+
+pub fn lerp(first: Point, second: Point, t: f32) -> Point {
+    Point::new(
+        first.x + (second.x - first.x) * t,
+        first.y + (second.y - first.y) * t,
+    )
+}
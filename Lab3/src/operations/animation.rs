@@ -28,9 +28,15 @@ impl Default for AnimationSettings {
 
 impl AnimationSettings {
     pub fn process_animation(&mut self, model: &mut Model) {
-        assert_eq!(model.points.len(), self.figure_state.len());
-        assert_eq!(model.points.len(), self.model_state.len());
-        assert_eq!(model.points.len(), 121);
+        // Points were added or removed while running, the states no longer match.
+        if model.points.len() != self.figure_state.len()
+            || model.points.len() != self.model_state.len()
+        {
+            log::warn!("Animation stopped: number of points has changed.");
+            self.is_running = false;
+            self.model_state = vec![];
+            return;
+        }
 
         let length = model.points.len();
         for i in 0..length {
@@ -44,7 +50,7 @@ impl AnimationSettings {
             model.points[i].point.x = x;
             model.points[i].point.y = y;
         }
-        model.points[120] = model.points[0];
+        model.points[length - 1] = model.points[0];
 
         self.step += self.direction;
 
@@ -84,6 +90,16 @@ impl AnimationSettings {
     }
 
     pub fn checkout_status(&mut self, model: &mut Model) {
+        // Animation is defined only for the default number of points.
+        if !self.is_running && model.points.len() != self.figure_state.len() {
+            log::warn!(
+                "Animation requires {} points, model has {}.",
+                self.figure_state.len(),
+                model.points.len()
+            );
+            return;
+        }
+
         self.is_running = !self.is_running;
 
         if self.is_running {
@@ -0,0 +1,140 @@
+use crate::models::bezier_point::BezierPoint;
+use crate::models::model::Model;
+use egui::{Key, KeyboardShortcut, Modifiers};
+
+// Oldest entries are dropped after this limit.
+pub const HISTORY_LIMIT: usize = 100;
+
+pub struct History {
+    entries: Vec<HistoryEntry>,
+    current: usize,
+
+    // Description of the change that is in progress.
+    label: Option<String>,
+}
+
+pub struct HistoryEntry {
+    pub label: String,
+    points: Vec<BezierPoint>,
+}
+
+impl Default for History {
+    fn default() -> Self {
+        Self {
+            entries: vec![HistoryEntry {
+                label: String::from("Initial"),
+                points: Model::default_points(),
+            }],
+            current: 0,
+
+            label: None,
+        }
+    }
+}
+
+impl History {
+    pub fn entries(&self) -> &[HistoryEntry] {
+        &self.entries
+    }
+
+    pub fn current(&self) -> usize {
+        self.current
+    }
+
+    pub fn can_undo(&self) -> bool {
+        self.current > 0
+    }
+
+    pub fn can_redo(&self) -> bool {
+        self.current + 1 < self.entries.len()
+    }
+
+    // Keeps only the current state of the model.
+    pub fn clear(&mut self, model: &Model) {
+        self.entries = vec![HistoryEntry {
+            label: String::from("Initial"),
+            points: model.points.clone(),
+        }];
+        self.current = 0;
+        self.label = None;
+    }
+
+    // Label for the next recorded entry. Last label wins.
+    pub fn set_label(&mut self, label: impl Into<String>) {
+        self.label = Some(label.into());
+    }
+
+    pub fn record(&mut self, label: impl Into<String>, model: &Model) {
+        self.label = None;
+        self.entries.truncate(self.current + 1);
+        self.entries.push(HistoryEntry {
+            label: label.into(),
+            points: model.points.clone(),
+        });
+
+        if self.entries.len() > HISTORY_LIMIT {
+            self.entries.remove(0);
+        }
+        self.current = self.entries.len() - 1;
+    }
+
+    // Records changes of the model as one entry, when user releases all buttons.
+    // So every drag gesture becomes a single entry.
+    pub fn update(&mut self, model: &Model, is_animation_running: bool, ui: &egui::Ui) {
+        if is_animation_running || ui.input(|i| i.pointer.any_down()) {
+            return;
+        }
+
+        if self.is_changed(model) {
+            let label = self.label.take().unwrap_or(String::from("Edit"));
+            self.record(label, model);
+        }
+    }
+
+    pub fn handle_shortcuts(&mut self, model: &mut Model, ui: &egui::Ui) {
+        let redo = KeyboardShortcut::new(Modifiers::COMMAND | Modifiers::SHIFT, Key::Z);
+        let undo = KeyboardShortcut::new(Modifiers::COMMAND, Key::Z);
+
+        // Redo is checked first, because undo shortcut matches it too.
+        if ui.input_mut(|i| i.consume_shortcut(&redo)) {
+            self.redo(model);
+        } else if ui.input_mut(|i| i.consume_shortcut(&undo)) {
+            self.undo(model);
+        }
+    }
+
+    pub fn undo(&mut self, model: &mut Model) {
+        // Unsaved changes are saved first, so undo returns to the last recorded state.
+        if self.is_changed(model) {
+            let label = self.label.take().unwrap_or(String::from("Edit"));
+            self.record(label, model);
+        }
+
+        if self.can_undo() {
+            self.jump(self.current - 1, model);
+        }
+    }
+
+    pub fn redo(&mut self, model: &mut Model) {
+        if self.can_redo() {
+            self.jump(self.current + 1, model);
+        }
+    }
+
+    pub fn jump(&mut self, index: usize, model: &mut Model) {
+        if let Some(entry) = self.entries.get(index) {
+            model.points = entry.points.clone();
+            self.current = index;
+            self.label = None;
+        }
+    }
+
+    fn is_changed(&self, model: &Model) -> bool {
+        let recorded = &self.entries[self.current].points;
+
+        recorded.len() != model.points.len()
+            || recorded.iter().zip(&model.points).any(|(old, new)| {
+                old.kind != new.kind || old.point.x != new.point.x || old.point.y != new.point.y
+            })
+    }
+}
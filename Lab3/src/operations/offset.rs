@@ -26,16 +26,23 @@ impl Default for Offset {
 }
 
 impl Offset {
-    pub fn process(&mut self, model: &mut Model) {
+    // Returns true, if model was changed.
+    pub fn process(&mut self, model: &mut Model) -> bool {
         if self.dot.x == 0.0 && self.dot.y == 0.0 {
-            return;
+            return false;
         };
 
         let delta = (self.dot.x - self.old.0, self.dot.y - self.old.1);
+        if delta == (0.0, 0.0) {
+            return false;
+        }
+
         model.points.iter_mut().for_each(|bezier| {
             self.update_point(&mut bezier.point, delta);
         });
         self.old = (self.dot.x, self.dot.y);
+
+        true
     }
 
     pub fn update_point(&mut self, point: &mut MoveablePoint, delta: (f32, f32)) {
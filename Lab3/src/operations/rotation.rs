@@ -33,12 +33,13 @@ impl Default for Rotation {
 }
 
 impl Rotation {
-    pub fn process(&mut self, model: &mut Model) {
+    // Returns true, if model was changed.
+    pub fn process(&mut self, model: &mut Model) -> bool {
         if self.angle == 0.0 {
-            return;
+            return false;
         };
         if self.dot.x == self.old.0 && self.dot.y == self.old.1 && self.angle == self.old.2 {
-            return;
+            return false;
         }
 
         let delta = self.angle - self.old.2;
@@ -47,6 +48,8 @@ impl Rotation {
         });
 
         self.old = (self.dot.x, self.dot.y, self.angle);
+
+        true
     }
 
     pub fn update_point(&self, point: &mut MoveablePoint, angle: f32) {
@@ -0,0 +1,42 @@
+use crate::geometry::moveable_point::MoveablePoint;
+use crate::traits::positionable::Positionable;
+use nalgebra::Matrix3;
+
+pub struct Scaling {
+    pub dot: MoveablePoint,
+
+    pub factor_x: f32,
+    pub factor_y: f32,
+}
+
+impl Default for Scaling {
+    fn default() -> Self {
+        Self {
+            dot: MoveablePoint::new(0.0, 0.0),
+
+            factor_x: 1.0,
+            factor_y: 1.0,
+        }
+    }
+}
+
+impl Scaling {
+    pub fn update_point(&self, point: &mut MoveablePoint) {
+        let point_vector = point.to_vector();
+        let matrix = self.matrix();
+
+        let answer = point_vector * matrix;
+
+        point.x = answer.x;
+        point.y = answer.y;
+    }
+
+    fn matrix(&self) -> Matrix3<f32> {
+        let m11 = self.factor_x;
+        let m22 = self.factor_y;
+        let m31 = self.dot.x * (1.0 - self.factor_x);
+        let m32 = self.dot.y * (1.0 - self.factor_y);
+
+        Matrix3::new(m11, 0.0, 0.0, 0.0, m22, 0.0, m31, m32, 1.0)
+    }
+}
@@ -0,0 +1,276 @@
+use crate::geometry::moveable_point::MoveablePoint;
+use crate::graphics::screen::ScreenParams;
+use crate::models::model::Model;
+use crate::operations::offset::Offset;
+use crate::operations::rotation::Rotation;
+use crate::operations::scaling::Scaling;
+use crate::traits::positionable::Positionable;
+use crate::ui::styles::{colors, strokes};
+use eframe::epaint::{Color32, Shape};
+use egui::{Pos2, Rect, Response, Vec2};
+use std::collections::BTreeSet;
+
+pub struct Selection {
+    pub is_enabled: bool,
+
+    pub pivot_mode: PivotMode,
+    pub pivot: MoveablePoint,
+
+    pub offset: (f32, f32),
+    pub angle: f32,
+    pub scale: f32,
+
+    pub color: Color32,
+    pub pivot_color: Color32,
+
+    indices: BTreeSet<usize>,
+    rubber_band: Option<(Pos2, Pos2)>,
+}
+
+impl Default for Selection {
+    fn default() -> Self {
+        Self {
+            is_enabled: false,
+
+            pivot_mode: PivotMode::Centroid,
+            pivot: MoveablePoint::new(0.0, 0.0),
+
+            offset: (0.0, 0.0),
+            angle: 0.0,
+            scale: 1.0,
+
+            color: colors::BLUE,
+            pivot_color: colors::ORANGE,
+
+            indices: BTreeSet::new(),
+            rubber_band: None,
+        }
+    }
+}
+
+impl Selection {
+    pub fn contains(&self, index: usize) -> bool {
+        self.indices.contains(&index)
+    }
+
+    pub fn len(&self) -> usize {
+        self.indices.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.indices.is_empty()
+    }
+
+    pub fn indices(&self) -> &BTreeSet<usize> {
+        &self.indices
+    }
+
+    pub fn clear(&mut self) {
+        self.indices.clear();
+    }
+
+    pub fn select_all(&mut self, model: &Model) {
+        self.indices = (0..model.points.len()).collect();
+    }
+
+    pub fn invert(&mut self, model: &Model) {
+        self.indices = (0..model.points.len())
+            .filter(|index| !self.indices.contains(index))
+            .collect();
+    }
+
+    pub fn toggle(&mut self, index: usize) {
+        if !self.indices.remove(&index) {
+            self.indices.insert(index);
+        }
+    }
+
+    pub fn select_only(&mut self, index: usize) {
+        self.indices.clear();
+        self.indices.insert(index);
+    }
+
+    // Drops indices that no longer exist in the model.
+    pub fn sync_with(&mut self, model: &Model) {
+        let length = model.points.len();
+        self.indices.retain(|index| *index < length);
+    }
+
+    pub fn update_on_click(
+        &mut self, model: &Model, radius: f32, screen_params: ScreenParams, ui: &egui::Ui,
+        response: &Response,
+    ) {
+        if !response.clicked() {
+            return;
+        }
+        let Some(pointer) = response.interact_pointer_pos() else {
+            return;
+        };
+        let is_additive = ui.input(|i| i.modifiers.shift);
+
+        let hit = model.points.iter().position(|bezier| {
+            let center = bezier.point.to_screen(screen_params).to_pos2();
+            center.distance(pointer) <= radius
+        });
+
+        match (hit, is_additive) {
+            (Some(index), true) => self.toggle(index),
+            (Some(index), false) => self.select_only(index),
+            (None, true) => {},
+            (None, false) => self.clear(),
+        }
+    }
+
+    pub fn update_rubber_band(
+        &mut self, model: &Model, screen_params: ScreenParams, ui: &egui::Ui, response: &Response,
+    ) {
+        if response.drag_started() {
+            if let Some(pointer) = response.interact_pointer_pos() {
+                self.rubber_band = Some((pointer, pointer));
+            }
+        }
+
+        if response.dragged() {
+            if let (Some((start, _)), Some(pointer)) =
+                (self.rubber_band, response.interact_pointer_pos())
+            {
+                self.rubber_band = Some((start, pointer));
+            }
+        }
+
+        if response.drag_stopped() {
+            if let Some((start, end)) = self.rubber_band.take() {
+                let rect = Rect::from_two_pos(start, end);
+
+                if !ui.input(|i| i.modifiers.shift) {
+                    self.indices.clear();
+                }
+
+                model
+                    .points
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, bezier)| {
+                        rect.contains(bezier.point.to_screen(screen_params).to_pos2())
+                    })
+                    .for_each(|(index, _)| {
+                        self.indices.insert(index);
+                    });
+            }
+        }
+    }
+
+    pub fn rubber_band_shape(&self) -> Option<Shape> {
+        let (start, end) = self.rubber_band?;
+
+        Some(Shape::rect_stroke(
+            Rect::from_two_pos(start, end),
+            0.0,
+            strokes::selection_frame(self.color),
+        ))
+    }
+
+    pub fn centroid(&self, model: &Model) -> Option<MoveablePoint> {
+        let points: Vec<&MoveablePoint> = self
+            .indices
+            .iter()
+            .filter_map(|index| model.points.get(*index))
+            .map(|bezier| &bezier.point)
+            .collect();
+
+        if points.is_empty() {
+            return None;
+        }
+
+        let count = points.len() as f32;
+        let x = points.iter().map(|point| point.x).sum::<f32>() / count;
+        let y = points.iter().map(|point| point.y).sum::<f32>() / count;
+
+        Some(MoveablePoint::new(x, y))
+    }
+
+    pub fn pivot_point(&self, model: &Model) -> Option<MoveablePoint> {
+        match self.pivot_mode {
+            PivotMode::Centroid => self.centroid(model),
+            PivotMode::Custom => Some(self.pivot),
+        }
+    }
+
+    // Moves every selected point except the one that was dragged by user.
+    pub fn move_points(&self, model: &mut Model, delta: Vec2, except: Option<usize>) {
+        let mut offset = Offset::default();
+
+        self.indices
+            .iter()
+            .filter(|index| Some(**index) != except)
+            .for_each(|index| {
+                if let Some(bezier) = model.points.get_mut(*index) {
+                    offset.update_point(&mut bezier.point, (delta.x, delta.y));
+                }
+            });
+    }
+
+    pub fn apply_offset(&self, model: &mut Model) {
+        let delta = Vec2::new(self.offset.0, self.offset.1);
+        self.move_points(model, delta, None);
+    }
+
+    pub fn apply_rotation(&self, model: &mut Model) {
+        let Some(pivot) = self.pivot_point(model) else {
+            return;
+        };
+
+        let rotation = Rotation {
+            dot: pivot,
+            ..Default::default()
+        };
+
+        self.for_each_selected(model, |point| rotation.update_point(point, self.angle));
+    }
+
+    pub fn apply_scale(&self, model: &mut Model) {
+        self.apply_scaling(model, self.scale, self.scale);
+    }
+
+    pub fn mirror(&self, model: &mut Model, axis: MirrorAxis) {
+        match axis {
+            MirrorAxis::Horizontal => self.apply_scaling(model, 1.0, -1.0),
+            MirrorAxis::Vertical => self.apply_scaling(model, -1.0, 1.0),
+        }
+    }
+
+    fn apply_scaling(&self, model: &mut Model, factor_x: f32, factor_y: f32) {
+        let Some(pivot) = self.pivot_point(model) else {
+            return;
+        };
+
+        let scaling = Scaling {
+            dot: pivot,
+            factor_x,
+            factor_y,
+        };
+
+        self.for_each_selected(model, |point| scaling.update_point(point));
+    }
+
+    fn for_each_selected(&self, model: &mut Model, mut operation: impl FnMut(&mut MoveablePoint)) {
+        self.indices.iter().for_each(|index| {
+            if let Some(bezier) = model.points.get_mut(*index) {
+                operation(&mut bezier.point);
+            }
+        });
+    }
+}
+
+#[derive(PartialEq)]
+pub enum PivotMode {
+    Centroid,
+    Custom,
+}
+
+pub enum MirrorAxis {
+    // Mirroring across horizontal line through pivot.
+    Horizontal,
+    // Mirroring across vertical line through pivot.
+    Vertical,
+}
@@ -3,10 +3,11 @@ use crate::geometry::line::Line;
 use crate::geometry::point::Point;
 use crate::graphics::screen::{Resolution, ScreenParams};
 use crate::models::bezier_point::BezierPointType;
+use crate::operations::selection::PivotMode;
 use crate::traits::positionable::Positionable;
 use crate::ui::styles::{colors, strokes};
 use eframe::epaint::Shape;
-use egui::{Frame, Response, Sense};
+use egui::{Frame, Response, Sense, Vec2};
 
 #[derive(Default)]
 pub struct Canvas {
@@ -15,23 +16,38 @@ pub struct Canvas {
     pub grid_lines: Vec<Line<Point>>,
     pub model_lines: Vec<Line<Point>>,
     pub skeleton_lines: Vec<Line<Point>>,
+    pub bounding_box_lines: Vec<Line<Point>>,
+
+    context_target: Option<usize>,
 }
 
 impl Canvas {
     pub fn process(&mut self, ui: &mut egui::Ui, context: &mut Context) {
+        // History:
+        if !context.animation_settings.is_running {
+            context.history.handle_shortcuts(&mut context.model, ui);
+        }
+        context
+            .history
+            .update(&context.model, context.animation_settings.is_running, ui);
+
         // Creating grid:
         if context.grid.is_enabled {
             self.grid_lines = context.grid.lines(self.screen_params)
         }
 
         // Euclidean Offset
-        if context.euclidean_offset.is_enabled {
-            context.euclidean_offset.process(&mut context.model);
+        if context.euclidean_offset.is_enabled
+            && context.euclidean_offset.process(&mut context.model)
+        {
+            context.history.set_label("Offset");
         }
 
         // Euclidean Rotation
-        if context.euclidean_rotation.is_enabled {
-            context.euclidean_rotation.process(&mut context.model);
+        if context.euclidean_rotation.is_enabled
+            && context.euclidean_rotation.process(&mut context.model)
+        {
+            context.history.set_label("Rotation");
         }
 
         // Animation:
@@ -52,6 +68,12 @@ impl Canvas {
 
         // Creating model:
         self.model_lines = context.model.lines(self.screen_params);
+
+        // Analysis:
+        if context.analysis.is_enabled {
+            context.analysis.update(&context.model);
+            self.bounding_box_lines = context.analysis.bounding_box_lines(self.screen_params);
+        }
     }
 
     pub fn draw(&mut self, ui: &mut egui::Ui, context: &mut Context) -> Response {
@@ -60,6 +82,13 @@ impl Canvas {
         self.screen_params.canvas_center = Point::from_pos2(response.rect.center());
         self.screen_params.resolution = Resolution::from(response.rect.max.x, response.rect.max.y);
 
+        // Draw reference image:
+        if context.reference_image.is_enabled {
+            if let Some(shape) = context.reference_image.shape(self.screen_params) {
+                painter.add(shape);
+            }
+        }
+
         // Draw grid:
         if context.grid.is_enabled {
             let grid_shapes: Vec<Shape> = self
@@ -70,6 +99,15 @@ impl Canvas {
             painter.extend(grid_shapes);
         }
 
+        // Draw fill:
+        if context.fill.is_enabled {
+            painter.add(
+                context
+                    .fill
+                    .shape(&self.model_lines, self.screen_params, response.rect),
+            );
+        }
+
         // Draw model lines
         let model_lines: Vec<Shape> = self
             .model_lines
@@ -85,6 +123,44 @@ impl Canvas {
         let outline_width = self.screen_params.value_cm_to_px(0.02);
         let outline_stroke = strokes::bezier_outline(outline_width);
 
+        // Draw analysis:
+        if context.analysis.is_enabled {
+            if context.analysis.is_bounding_box_enabled {
+                let bounding_box: Vec<Shape> = self
+                    .bounding_box_lines
+                    .iter()
+                    .map(|line| line.to_screen(self.screen_params).to_shape())
+                    .collect();
+                painter.extend(bounding_box);
+            }
+
+            if context.analysis.is_centroid_enabled {
+                if let Some(centroid) = context.analysis.centroid {
+                    painter.add(centroid.to_screen(self.screen_params).to_dot(
+                        dot_radius,
+                        context.analysis.centroid_color,
+                        outline_stroke,
+                    ));
+                }
+            }
+
+            if context.analysis.are_intersections_enabled {
+                let intersections: Vec<Shape> = context
+                    .analysis
+                    .intersections
+                    .iter()
+                    .map(|point| {
+                        point.to_screen(self.screen_params).to_dot(
+                            dot_radius * 1.5,
+                            context.analysis.intersection_color,
+                            outline_stroke,
+                        )
+                    })
+                    .collect();
+                painter.extend(intersections);
+            }
+        }
+
         if context.model.is_skeleton_enabled {
             // Draw skeleton lines
             let skeleton_lines: Vec<Shape> = self
@@ -114,16 +190,54 @@ impl Canvas {
                 .collect();
             painter.extend(point_shapes);
 
+            // Draw selection:
+            if context.selection.is_enabled {
+                let selection_stroke = strokes::selection_frame(context.selection.color);
+                let selection_shapes: Vec<Shape> = context
+                    .selection
+                    .indices()
+                    .iter()
+                    .filter_map(|index| context.model.points.get(*index))
+                    .map(|bezier| {
+                        Shape::circle_stroke(
+                            bezier.point.to_screen(self.screen_params).to_pos2(),
+                            dot_radius * 2.0,
+                            selection_stroke,
+                        )
+                    })
+                    .collect();
+                painter.extend(selection_shapes);
+
+                if let Some(shape) = context.selection.rubber_band_shape() {
+                    painter.add(shape);
+                }
+            }
+
             // Updating model
+            let mut group_delta: Option<(usize, Vec2)> = None;
             context
                 .model
                 .points
                 .iter_mut()
                 .enumerate()
                 .for_each(|(index, bezier)| {
-                    bezier
-                        .point
-                        .update_self(dot_radius, self.screen_params, ui, &response);
+                    let delta =
+                        bezier
+                            .point
+                            .update_self(dot_radius, self.screen_params, ui, &response);
+
+                    if delta != Vec2::ZERO {
+                        context
+                            .history
+                            .set_label(format!("Move point #{}", index + 1));
+                    }
+
+                    if context.selection.is_enabled
+                        && delta != Vec2::ZERO
+                        && context.selection.contains(index)
+                    {
+                        group_delta = Some((index, delta));
+                    }
 
                     if context.model.are_tooltips_enabled {
                         bezier.point.show_tooltip(
@@ -135,6 +249,76 @@ impl Canvas {
                         );
                     }
                 });
+
+            // Moving selection as a group
+            if let Some((dragged, delta)) = group_delta {
+                context
+                    .selection
+                    .move_points(&mut context.model, delta, Some(dragged));
+                context.history.set_label("Move selection");
+            }
+
+            // Context menu: inserting and deleting points
+            if !context.animation_settings.is_running {
+                self.update_context_menu(context, dot_radius, &response);
+            }
+
+            // Updating selection
+            if context.selection.is_enabled {
+                context.selection.sync_with(&context.model);
+                context.selection.update_on_click(
+                    &context.model,
+                    dot_radius,
+                    self.screen_params,
+                    ui,
+                    &response,
+                );
+                context.selection.update_rubber_band(
+                    &context.model,
+                    self.screen_params,
+                    ui,
+                    &response,
+                );
+            }
+        }
+
+        // Selection Pivot Dot
+        if context.model.is_skeleton_enabled
+            && context.selection.is_enabled
+            && context.selection.pivot_mode == PivotMode::Custom
+        {
+            let dot = context
+                .selection
+                .pivot
+                .to_screen(self.screen_params)
+                .to_dot(dot_radius, context.selection.pivot_color, outline_stroke);
+            painter.add(dot);
+
+            context
+                .selection
+                .pivot
+                .update_self(dot_radius, self.screen_params, ui, &response);
+        }
+
+        // Reference Image Anchor Dot
+        if context.reference_image.is_enabled && context.reference_image.is_anchor_enabled {
+            let dot = context
+                .reference_image
+                .anchor
+                .to_screen(self.screen_params)
+                .to_dot(
+                    dot_radius,
+                    context.reference_image.anchor_color,
+                    outline_stroke,
+                );
+            painter.add(dot);
+
+            context.reference_image.anchor.update_self(
+                dot_radius,
+                self.screen_params,
+                ui,
+                &response,
+            );
         }
 
         // Offset Dot
@@ -169,12 +353,66 @@ impl Canvas {
             );
         }
 
-        // Check for dragging
-        self.screen_params.update_self_offset(ui, &response);
+        // Check for dragging (in selection mode dragging draws rubber band)
+        if !(context.model.is_skeleton_enabled && context.selection.is_enabled) {
+            self.screen_params.update_self_offset(ui, &response);
+        }
 
         response
     }
 
+    fn update_context_menu(&mut self, context: &mut Context, radius: f32, response: &Response) {
+        if response.secondary_clicked() {
+            self.context_target = response.interact_pointer_pos().and_then(|pointer| {
+                context.model.points.iter().position(|bezier| {
+                    let center = bezier.point.to_screen(self.screen_params).to_pos2();
+                    center.distance(pointer) <= radius
+                })
+            });
+        }
+
+        let Some(index) = self.context_target else {
+            return;
+        };
+        if index >= context.model.points.len() {
+            self.context_target = None;
+            return;
+        }
+
+        response.context_menu(|ui| {
+            ui.label(format!("Point #{}", index + 1));
+            ui.separator();
+
+            if ui
+                .button("Split Segment")
+                .on_hover_text("Inserts new point in the middle of the segment.")
+                .clicked()
+            {
+                context.model.insert_point(index);
+                context
+                    .history
+                    .set_label(format!("Split segment at #{}", index + 1));
+                ui.close_menu();
+            }
+
+            if ui
+                .add_enabled(
+                    context.model.can_remove_point(index),
+                    egui::Button::new("Delete Point"),
+                )
+                .on_disabled_hover_text("Only inner control points can be deleted.")
+                .clicked()
+            {
+                context.model.remove_point(index);
+                context
+                    .history
+                    .set_label(format!("Delete point #{}", index + 1));
+                self.context_target = None;
+                ui.close_menu();
+            }
+        });
+    }
+
     pub fn show_content(&mut self, context: &mut Context, ui: &mut egui::Ui) {
         Frame::canvas(ui.style())
             .fill(colors::WHITE)
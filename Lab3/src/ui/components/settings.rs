@@ -1,285 +1,876 @@
 use crate::context::Context;
+use crate::graphics::fill::FillRule;
 use crate::graphics::screen::{MAX_PX_PER_CM, MIN_PX_PER_CM};
+use crate::models::model::Flattening;
+use crate::operations::selection::{MirrorAxis, PivotMode};
 use crate::ui::components::canvas::Canvas;
+use crate::ui::components::message_box::MessageBox;
 use crate::ui::styles::colors;
 use egui::{DragValue, Grid, RichText};
 
 pub const SETTINGS_PANEL_WIDTH: f32 = 250.0;
 
-pub fn show_panel(context: &mut Context, canvas: &mut Canvas, ui: &mut egui::Ui) {
-    egui::ScrollArea::vertical().show(ui, |ui| {
-        ui.vertical_centered(|ui| {
-            ui.heading("Settings");
-        });
-
-        ui.add_space(10.0);
-
-        ui.horizontal(|ui| {
-            ui.label("Pixels per Centimeter:");
-            ui.add(
-                DragValue::new(&mut canvas.screen_params.px_per_cm)
-                    .speed(1)
-                    .range(MIN_PX_PER_CM..=MAX_PX_PER_CM),
-            );
-        });
-
-        ui.add_space(10.0);
-
-        ui.vertical_centered(|ui| {
-            if ui.button("Reset to Default Settings").clicked() {
-                reset_to_defaults(context, canvas);
-            }
-        });
-
-        ui.add_space(10.0);
+#[derive(Default)]
+pub struct Settings {
+    error_box: Option<MessageBox>,
+}
 
-        ui.collapsing("Grid Settings", |ui| {
-            ui.checkbox(&mut context.grid.is_enabled, "Enable Grid");
-            ui.checkbox(&mut canvas.screen_params.is_dragging_offset_enabled, "Enable Drag & Offset");
+impl Settings {
+    pub fn show_panel(&mut self, context: &mut Context, canvas: &mut Canvas, ui: &mut egui::Ui) {
+        egui::ScrollArea::vertical().show(ui, |ui| {
+            ui.vertical_centered(|ui| {
+                ui.heading("Settings");
+            });
 
-            ui.add_space(5.0);
+            ui.add_space(10.0);
 
             ui.horizontal(|ui| {
-                ui.label("Unit Length:");
+                ui.label("Pixels per Centimeter:");
                 ui.add(
-                    DragValue::new(&mut canvas.screen_params.grid_unit_length)
+                    DragValue::new(&mut canvas.screen_params.px_per_cm)
                         .speed(1)
-                        .range(1.0..=10.0),
+                        .range(MIN_PX_PER_CM..=MAX_PX_PER_CM),
                 );
             });
 
             ui.add_space(10.0);
 
-            ui.label(RichText::new("Strokes:").strong());
-            Grid::new("GridStrokes").num_columns(2).show(ui, |ui| {
-                ui.label("Axis X:");
-                ui.add(&mut context.grid.axis_x_stroke);
+            ui.vertical_centered(|ui| {
+                if ui.button("Reset to Default Settings").clicked() {
+                    self.reset_to_defaults(context, canvas);
+                }
+            });
 
-                ui.end_row();
+            ui.add_space(10.0);
 
-                ui.label("Axis Y:");
-                ui.add(&mut context.grid.axis_y_stroke);
+            ui.collapsing("Grid Settings", |ui| {
+                ui.checkbox(&mut context.grid.is_enabled, "Enable Grid");
+                ui.checkbox(&mut canvas.screen_params.is_dragging_offset_enabled, "Enable Drag & Offset");
 
-                ui.end_row();
+                ui.add_space(5.0);
+
+                ui.horizontal(|ui| {
+                    ui.label("Unit Length:");
+                    ui.add(
+                        DragValue::new(&mut canvas.screen_params.grid_unit_length)
+                            .speed(1)
+                            .range(1.0..=10.0),
+                    );
+                });
+
+                ui.add_space(10.0);
+
+                ui.label(RichText::new("Strokes:").strong());
+                Grid::new("GridStrokes").num_columns(2).show(ui, |ui| {
+                    ui.label("Axis X:");
+                    ui.add(&mut context.grid.axis_x_stroke);
 
-                ui.label("Grid:");
-                ui.add(&mut context.grid.grid_stroke);
+                    ui.end_row();
+
+                    ui.label("Axis Y:");
+                    ui.add(&mut context.grid.axis_y_stroke);
+
+                    ui.end_row();
+
+                    ui.label("Grid:");
+                    ui.add(&mut context.grid.grid_stroke);
+                });
+
+                ui.add_space(10.0);
+
+                ui.vertical_centered(|ui| {
+                    if ui.button("Reset Settings").clicked() {
+                        context.grid = Default::default();
+                        canvas.screen_params.grid_unit_length = 1.0;
+                        canvas.screen_params.offset = (0.0, 0.0);
+                        canvas.screen_params.is_dragging_offset_enabled = true;
+                    }
+                });
             });
 
             ui.add_space(10.0);
 
-            ui.vertical_centered(|ui| {
-                if ui.button("Reset Settings").clicked() {
-                    context.grid = Default::default();
-                    canvas.screen_params.grid_unit_length = 1.0;
-                    canvas.screen_params.offset = (0.0, 0.0);
-                    canvas.screen_params.is_dragging_offset_enabled = true;
-                }
-            });
-        });
+            ui.collapsing("Reference Image", |ui| {
+                Grid::new("ReferenceImageStatus")
+                    .num_columns(2)
+                    .show(ui, |ui| {
+                        ui.checkbox(&mut context.reference_image.is_enabled, "Enable");
+                        ui.end_row();
+
+                        ui.label("Status: ");
+                        if context.reference_image.is_loaded() {
+                            ui.label(RichText::new("Loaded!").color(colors::LIME));
+                        } else {
+                            ui.label(RichText::new("Not Loaded.").color(colors::RED));
+                        }
+                        ui.end_row();
+                    });
+                context.reference_image.ensure_image_loaded();
+
+                ui.add_space(5.0);
+
+                ui.vertical_centered_justified(|ui| {
+                    if ui
+                        .button("Load Image")
+                        .on_hover_text("Supported formats: PNG, JPEG.")
+                        .clicked()
+                    {
+                        if let Some(path) = rfd::FileDialog::new()
+                            .add_filter("Images", &["png", "jpg", "jpeg"])
+                            .pick_file()
+                        {
+                            let loading_result = context.reference_image.load(path, ui.ctx());
+
+                            if let Err(e) = loading_result {
+                                let mut error_message =
+                                    format!("Error occurred while loading image:\n{}\n", e);
+                                if let Some(additional_info) = e.get_additional_info() {
+                                    error_message.push_str(
+                                        format!("\nAdditional Info:\n{}", additional_info).as_str(),
+                                    );
+                                }
+
+                                self.error_box =
+                                    Some(MessageBox::new("Error!".to_string(), error_message));
+                            }
+                        }
+                    }
+                });
 
-        ui.add_space(10.0);
+                ui.vertical_centered_justified(|ui| {
+                    if ui
+                        .add_enabled(
+                            context.reference_image.is_loaded(),
+                            egui::Button::new("Unload Image"),
+                        )
+                        .clicked()
+                    {
+                        context.reference_image.unload();
+                    }
+                });
 
-        ui.collapsing("Bezier Skeleton Settings", |ui| {
-            ui.checkbox(&mut context.model.are_tooltips_enabled, "Tooltips")
-                .on_hover_text("If you hover the cursor over a control or defining point with 'Tooltips' mode enabled, you will be able to see its number and coordinates.");
+                ui.add_space(5.0);
+
+                ui.checkbox(
+                    &mut context.reference_image.is_anchor_enabled,
+                    RichText::new("Show Anchor").color(context.reference_image.anchor_color),
+                )
+                .on_hover_text("Anchor is the center of the image. Drag it to move the image.");
+
+                ui.add_space(5.0);
+
+                Grid::new("ReferenceImageSettings")
+                    .num_columns(2)
+                    .show(ui, |ui| {
+                        ui.label("Position X: ");
+                        ui.add(DragValue::new(&mut context.reference_image.anchor.x).speed(0.1));
+                        ui.end_row();
+
+                        ui.label("Position Y: ");
+                        ui.add(DragValue::new(&mut context.reference_image.anchor.y).speed(0.1));
+                        ui.end_row();
+
+                        ui.label("Scale: ");
+                        ui.add(
+                            DragValue::new(&mut context.reference_image.scale)
+                                .speed(0.01)
+                                .range(0.01..=100.0),
+                        );
+                        ui.end_row();
+
+                        ui.label("Rotation Angle: ");
+                        ui.add(
+                            DragValue::new(&mut context.reference_image.angle)
+                                .speed(1)
+                                .range(-360..=360),
+                        );
+                        ui.end_row();
+
+                        ui.label("Opacity: ");
+                        ui.add(egui::Slider::new(
+                            &mut context.reference_image.opacity,
+                            0.0..=1.0,
+                        ));
+                        ui.end_row();
+
+                        ui.label("Anchor Color: ");
+                        egui::color_picker::color_edit_button_srgba(
+                            ui,
+                            &mut context.reference_image.anchor_color,
+                            egui::color_picker::Alpha::Opaque,
+                        );
+                    });
+
+                ui.add_space(5.0);
+
+                ui.vertical_centered(|ui| {
+                    if ui.button("Reset Position").clicked() {
+                        context.reference_image.reset_transform();
+                    }
+                });
+            });
 
             ui.add_space(10.0);
 
-            Grid::new("PointStrokes").num_columns(2).show(ui, |ui| {
-                ui.label("Skeleton Stroke:");
-                ui.add(&mut context.model.skeleton_stroke);
+            ui.collapsing("Bezier Skeleton Settings", |ui| {
+                ui.checkbox(&mut context.model.are_tooltips_enabled, "Tooltips")
+                    .on_hover_text("If you hover the cursor over a control or defining point with 'Tooltips' mode enabled, you will be able to see its number and coordinates.");
 
-                ui.end_row();
+                ui.add_space(10.0);
 
-                ui.label("Fill - Control Point:");
-                egui::color_picker::color_edit_button_srgba(
-                    ui,
-                    &mut context.model.fill_control,
-                    egui::color_picker::Alpha::Opaque,
-                );
+                Grid::new("PointStrokes").num_columns(2).show(ui, |ui| {
+                    ui.label("Skeleton Stroke:");
+                    ui.add(&mut context.model.skeleton_stroke);
 
-                ui.end_row();
+                    ui.end_row();
 
-                ui.label("Fill - Defining Point:");
-                egui::color_picker::color_edit_button_srgba(
-                    ui,
-                    &mut context.model.fill_defining,
-                    egui::color_picker::Alpha::Opaque,
-                );
+                    ui.label("Fill - Control Point:");
+                    egui::color_picker::color_edit_button_srgba(
+                        ui,
+                        &mut context.model.fill_control,
+                        egui::color_picker::Alpha::Opaque,
+                    );
 
-                ui.end_row();
+                    ui.end_row();
 
-                ui.label("Radius:");
-                ui.add(
-                    DragValue::new(&mut context.model.radius)
-                        .speed(0.1)
-                        .range(0.1..=2.0),
-                );
+                    ui.label("Fill - Defining Point:");
+                    egui::color_picker::color_edit_button_srgba(
+                        ui,
+                        &mut context.model.fill_defining,
+                        egui::color_picker::Alpha::Opaque,
+                    );
+
+                    ui.end_row();
+
+                    ui.label("Radius:");
+                    ui.add(
+                        DragValue::new(&mut context.model.radius)
+                            .speed(0.1)
+                            .range(0.1..=2.0),
+                    );
+                });
+
+                ui.add_space(10.0);
+
+                ui.vertical_centered(|ui| {
+                    if ui.button("Reset Settings").clicked() {
+                        context.model = Default::default();
+                    }
+                });
             });
 
             ui.add_space(10.0);
 
-            ui.vertical_centered(|ui| {
-                if ui.button("Reset Settings").clicked() {
-                    context.model = Default::default();
-                }
+            ui.collapsing("Model Settings", |ui| {
+                Grid::new("PointStrokes").num_columns(2).show(ui, |ui| {
+                    ui.label("Stroke:");
+                    ui.add(&mut context.model.model_stroke);
+
+                    ui.end_row();
+
+                    ui.label("Flattening:");
+                    ui.horizontal(|ui| {
+                        ui.radio_value(
+                            &mut context.model.flattening,
+                            Flattening::Uniform,
+                            "Uniform",
+                        );
+                        ui.radio_value(
+                            &mut context.model.flattening,
+                            Flattening::Adaptive,
+                            "Adaptive",
+                        )
+                        .on_hover_text(
+                            "Segments are subdivided until they deviate from the curve \
+                            by no more than the tolerance (in pixels).",
+                        );
+                    });
+
+                    ui.end_row();
+
+                    match context.model.flattening {
+                        Flattening::Uniform => {
+                            ui.label("Bezier step:");
+                            ui.add(
+                                DragValue::new(&mut context.model.bezier_step)
+                                    .speed(0.01)
+                                    .range(0.01..=0.5),
+                            );
+                        },
+                        Flattening::Adaptive => {
+                            ui.label("Tolerance:");
+                            ui.add(
+                                DragValue::new(&mut context.model.flatness_tolerance)
+                                    .speed(0.01)
+                                    .range(0.01..=10.0)
+                                    .suffix(" px"),
+                            );
+                        },
+                    }
+
+                    ui.end_row();
+
+                    ui.label("Lines:");
+                    ui.label(canvas.model_lines.len().to_string());
+                });
+
+                ui.add_space(10.0);
+
+                ui.vertical_centered(|ui| {
+                    if ui.button("Reset Settings").clicked() {
+                        context.model = Default::default();
+                    }
+                });
             });
-        });
 
-        ui.add_space(10.0);
+            ui.add_space(10.0);
 
-        ui.collapsing("Model Settings", |ui| {
-            Grid::new("PointStrokes").num_columns(2).show(ui, |ui| {
-                ui.label("Stroke:");
-                ui.add(&mut context.model.model_stroke);
+            ui.collapsing("Fill Settings", |ui| {
+                ui.checkbox(&mut context.fill.is_enabled, "Enable Fill");
+
+                ui.add_space(5.0);
+
+                Grid::new("FillSettings").num_columns(2).show(ui, |ui| {
+                    ui.label("Rule:");
+                    ui.horizontal(|ui| {
+                        ui.radio_value(&mut context.fill.rule, FillRule::EvenOdd, "Even-Odd")
+                            .on_hover_text(
+                                "Point is inside if a ray from it crosses the outline \
+                                an odd number of times.",
+                            );
+                        ui.radio_value(&mut context.fill.rule, FillRule::NonZero, "Non-Zero")
+                            .on_hover_text(
+                                "Point is inside if the outline winds around it \
+                                a non-zero number of times.",
+                            );
+                    });
+                    ui.end_row();
+
+                    ui.label("Color:");
+                    egui::color_picker::color_edit_button_srgba(
+                        ui,
+                        &mut context.fill.color,
+                        egui::color_picker::Alpha::Opaque,
+                    );
+                    ui.end_row();
+
+                    ui.label("Opacity:");
+                    ui.add(egui::Slider::new(&mut context.fill.opacity, 0.0..=1.0));
+                    ui.end_row();
+                });
 
-                ui.end_row();
+                ui.add_space(10.0);
 
-                ui.label("Bezier step:");
-                ui.add(DragValue::new(&mut context.model.bezier_step)
-                           .speed(0.01)
-                           .range(0.01..=0.5));
+                ui.vertical_centered(|ui| {
+                    if ui.button("Reset Settings").clicked() {
+                        context.fill = Default::default();
+                    }
+                });
             });
 
             ui.add_space(10.0);
 
-            ui.vertical_centered(|ui| {
-                if ui.button("Reset Settings").clicked() {
-                    context.model = Default::default();
+            ui.collapsing("Analysis", |ui| {
+                ui.checkbox(&mut context.analysis.is_enabled, "Enable Analysis");
+
+                ui.add_space(5.0);
+
+                ui.add_enabled_ui(context.analysis.is_enabled, |ui| {
+                    Grid::new("AnalysisResults").num_columns(2).show(ui, |ui| {
+                        ui.label("Contour:");
+                        if context.analysis.is_closed {
+                            ui.label("Closed");
+                        } else {
+                            ui.label("Open").on_hover_text(
+                                "Area and centroid are calculated \
+                                with a chord from the last point to the first one.",
+                            );
+                        }
+                        ui.end_row();
+
+                        ui.label("Arc Length:");
+                        ui.label(format!("{:.4} cm", context.analysis.total_length));
+                        ui.end_row();
+
+                        ui.label("Signed Area:");
+                        ui.label(format!("{:.4} cm²", context.analysis.signed_area));
+                        ui.end_row();
+
+                        ui.label("Centroid:");
+                        match context.analysis.centroid {
+                            Some(point) => ui.label(format!("({:.3}, {:.3})", point.x, point.y)),
+                            None => ui.label("-"),
+                        };
+                        ui.end_row();
+
+                        ui.label("Bounding Box:");
+                        match context.analysis.bounding_box {
+                            Some(bounding_box) => ui.label(format!(
+                                "({:.3}, {:.3})\n({:.3}, {:.3})\n{:.3} x {:.3} cm",
+                                bounding_box.min.x,
+                                bounding_box.min.y,
+                                bounding_box.max.x,
+                                bounding_box.max.y,
+                                bounding_box.width(),
+                                bounding_box.height(),
+                            )),
+                            None => ui.label("-"),
+                        };
+                        ui.end_row();
+
+                        ui.label("Self-Intersections:");
+                        ui.label(context.analysis.intersections.len().to_string());
+                        ui.end_row();
+                    });
+
+                    ui.add_space(5.0);
+
+                    ui.collapsing("Segment Lengths", |ui| {
+                        Grid::new("AnalysisSegmentLengths")
+                            .num_columns(2)
+                            .striped(true)
+                            .show(ui, |ui| {
+                                context.analysis.segment_lengths.iter().enumerate().for_each(
+                                    |(index, length)| {
+                                        ui.label(format!(
+                                            "#{} ({}-{}):",
+                                            index + 1,
+                                            2 * index + 1,
+                                            2 * index + 3
+                                        ));
+                                        ui.label(format!("{:.4} cm", length));
+                                        ui.end_row();
+                                    },
+                                );
+                            });
+                    });
+
+                    ui.add_space(10.0);
+
+                    ui.checkbox(
+                        &mut context.analysis.is_bounding_box_enabled,
+                        "Display Bounding Box",
+                    );
+                    ui.checkbox(&mut context.analysis.is_centroid_enabled, "Display Centroid");
+                    ui.checkbox(
+                        &mut context.analysis.are_intersections_enabled,
+                        "Display Intersections",
+                    );
+
+                    ui.add_space(5.0);
+
+                    Grid::new("AnalysisStyles").num_columns(2).show(ui, |ui| {
+                        ui.label("Bounding Box:");
+                        ui.add(&mut context.analysis.bounding_box_stroke);
+                        ui.end_row();
+
+                        ui.label("Centroid:");
+                        egui::color_picker::color_edit_button_srgba(
+                            ui,
+                            &mut context.analysis.centroid_color,
+                            egui::color_picker::Alpha::Opaque,
+                        );
+                        ui.end_row();
+
+                        ui.label("Intersections:");
+                        egui::color_picker::color_edit_button_srgba(
+                            ui,
+                            &mut context.analysis.intersection_color,
+                            egui::color_picker::Alpha::Opaque,
+                        );
+                        ui.end_row();
+                    });
+                });
+
+                ui.add_space(10.0);
+
+                ui.vertical_centered(|ui| {
+                    if ui.button("Reset Settings").clicked() {
+                        context.analysis = Default::default();
+                    }
+                });
+            });
+
+            ui.add_space(10.0);
+
+            ui.collapsing("Selection", |ui| {
+                ui.checkbox(&mut context.selection.is_enabled, "Selection Mode")
+                    .on_hover_text(
+                        "Works with displayed skeleton.\n\
+                        - Click: select point.\n\
+                        - Shift + Click: add or remove point.\n\
+                        - Drag: rubber-band selection (hold Shift to add).\n\
+                        - Drag selected point: move the whole selection.",
+                    );
+
+                ui.add_space(5.0);
+
+                ui.horizontal(|ui| {
+                    ui.label("Selected:");
+                    ui.label(format!("{} points", context.selection.len()));
+                });
+
+                ui.add_space(5.0);
+
+                ui.horizontal(|ui| {
+                    if ui.button("All").clicked() {
+                        context.selection.select_all(&context.model);
+                    }
+                    if ui.button("Invert").clicked() {
+                        context.selection.invert(&context.model);
+                    }
+                    if ui.button("Clear").clicked() {
+                        context.selection.clear();
+                    }
+                });
+
+                ui.add_space(10.0);
+
+                ui.label(RichText::new("Pivot:").strong());
+                ui.horizontal(|ui| {
+                    ui.radio_value(
+                        &mut context.selection.pivot_mode,
+                        PivotMode::Centroid,
+                        "Centroid",
+                    );
+                    ui.radio_value(
+                        &mut context.selection.pivot_mode,
+                        PivotMode::Custom,
+                        RichText::new("Custom").color(context.selection.pivot_color),
+                    );
+                });
+
+                if context.selection.pivot_mode == PivotMode::Custom {
+                    Grid::new("SelectionPivot").num_columns(2).show(ui, |ui| {
+                        ui.label("Pivot X: ");
+                        ui.add(DragValue::new(&mut context.selection.pivot.x).speed(0.1));
+                        ui.end_row();
+
+                        ui.label("Pivot Y: ");
+                        ui.add(DragValue::new(&mut context.selection.pivot.y).speed(0.1));
+                        ui.end_row();
+                    });
                 }
+
+                ui.add_space(10.0);
+
+                ui.add_enabled_ui(!context.selection.is_empty(), |ui| {
+                    Grid::new("SelectionTransformations")
+                        .num_columns(3)
+                        .show(ui, |ui| {
+                            ui.label("Offset: ");
+                            ui.horizontal(|ui| {
+                                ui.add(
+                                    DragValue::new(&mut context.selection.offset.0)
+                                        .speed(0.1)
+                                        .prefix("X: "),
+                                );
+                                ui.add(
+                                    DragValue::new(&mut context.selection.offset.1)
+                                        .speed(0.1)
+                                        .prefix("Y: "),
+                                );
+                            });
+                            if ui.button("Apply").clicked() {
+                                context.selection.apply_offset(&mut context.model);
+                                context.history.set_label("Offset selection");
+                            }
+                            ui.end_row();
+
+                            ui.label("Rotation: ");
+                            ui.add(
+                                DragValue::new(&mut context.selection.angle)
+                                    .speed(1)
+                                    .range(-360..=360)
+                                    .suffix("°"),
+                            );
+                            if ui.button("Apply").clicked() {
+                                context.selection.apply_rotation(&mut context.model);
+                                context.history.set_label("Rotate selection");
+                            }
+                            ui.end_row();
+
+                            ui.label("Scale: ");
+                            ui.add(
+                                DragValue::new(&mut context.selection.scale)
+                                    .speed(0.01)
+                                    .range(0.01..=100.0),
+                            );
+                            if ui.button("Apply").clicked() {
+                                context.selection.apply_scale(&mut context.model);
+                                context.history.set_label("Scale selection");
+                            }
+                            ui.end_row();
+                        });
+
+                    ui.add_space(5.0);
+
+                    ui.horizontal(|ui| {
+                        ui.label("Mirror: ");
+                        if ui
+                            .button("Horizontal")
+                            .on_hover_text("Across horizontal line through pivot.")
+                            .clicked()
+                        {
+                            context
+                                .selection
+                                .mirror(&mut context.model, MirrorAxis::Horizontal);
+                            context.history.set_label("Mirror selection horizontally");
+                        }
+                        if ui
+                            .button("Vertical")
+                            .on_hover_text("Across vertical line through pivot.")
+                            .clicked()
+                        {
+                            context
+                                .selection
+                                .mirror(&mut context.model, MirrorAxis::Vertical);
+                            context.history.set_label("Mirror selection vertically");
+                        }
+                    });
+                });
+
+                ui.add_space(10.0);
+
+                Grid::new("SelectionColors").num_columns(2).show(ui, |ui| {
+                    ui.label("Selection Color: ");
+                    egui::color_picker::color_edit_button_srgba(
+                        ui,
+                        &mut context.selection.color,
+                        egui::color_picker::Alpha::Opaque,
+                    );
+                    ui.end_row();
+
+                    ui.label("Pivot Color: ");
+                    egui::color_picker::color_edit_button_srgba(
+                        ui,
+                        &mut context.selection.pivot_color,
+                        egui::color_picker::Alpha::Opaque,
+                    );
+                });
+
+                ui.add_space(10.0);
+
+                ui.vertical_centered(|ui| {
+                    if ui.button("Reset Settings").clicked() {
+                        context.selection = Default::default();
+                    }
+                });
             });
-        });
 
-        ui.add_space(10.0);
+            ui.add_space(10.0);
 
-        ui.checkbox(&mut context.model.is_skeleton_enabled, "Display Skeleton");
+            ui.collapsing("History", |ui| {
+                let is_animated = context.animation_settings.is_running;
+                if is_animated {
+                    ui.label(RichText::new("Stop the animation to use the history.").weak());
+                }
 
-        ui.add_space(10.0);
+                ui.horizontal(|ui| {
+                    if ui
+                        .add_enabled(
+                            !is_animated && context.history.can_undo(),
+                            egui::Button::new("Undo"),
+                        )
+                        .on_hover_text("Ctrl + Z")
+                        .clicked()
+                    {
+                        context.history.undo(&mut context.model);
+                    }
+                    if ui
+                        .add_enabled(
+                            !is_animated && context.history.can_redo(),
+                            egui::Button::new("Redo"),
+                        )
+                        .on_hover_text("Ctrl + Shift + Z")
+                        .clicked()
+                    {
+                        context.history.redo(&mut context.model);
+                    }
+                });
 
-        ui.vertical_centered(|ui| {
-            ui.label(RichText::new("Animation").strong());
-        });
-        ui.add_space(5.0);
-        ui.group(|ui| {
-            ui.horizontal_wrapped(|ui| {
-                ui.label("Status: ");
-                if context.animation_settings.is_running {
-                    ui.label(RichText::new("Running!").color(colors::LIME));
-                } else {
-                    ui.label(RichText::new("Stopped.").color(colors::RED));
-                };
+                ui.add_space(5.0);
+
+                let mut target: Option<usize> = None;
+                egui::ScrollArea::vertical()
+                    .id_salt("HistoryEntries")
+                    .max_height(150.0)
+                    .show(ui, |ui| {
+                        let current = context.history.current();
+                        for (index, entry) in context.history.entries().iter().enumerate() {
+                            let text = if index > current {
+                                RichText::new(&entry.label).weak()
+                            } else {
+                                RichText::new(&entry.label)
+                            };
+
+                            if ui.selectable_label(index == current, text).clicked() {
+                                target = Some(index);
+                            }
+                        }
+                    });
+                if let Some(index) = target.filter(|_| !is_animated) {
+                    context.history.jump(index, &mut context.model);
+                }
+
+                ui.add_space(10.0);
 
                 ui.vertical_centered(|ui| {
-                    if ui.button("Start / Stop").clicked() {
-                        context.animation_settings.checkout_status(&mut context.model);
+                    if ui.button("Clear History").clicked() {
+                        context.history.clear(&context.model);
                     }
                 });
             });
-        });
 
-        ui.add_space(10.0);
+            ui.add_space(10.0);
 
-        ui.vertical_centered(|ui| {
-            ui.label(RichText::new("Euclidean Transformations").strong());
-        });
+            ui.checkbox(&mut context.model.is_skeleton_enabled, "Display Skeleton");
 
-        ui.add_space(5.0);
+            ui.add_space(10.0);
 
-        if ui.checkbox(&mut context.euclidean_offset.is_enabled,
-                    RichText::new("Enable Offset").color(context.euclidean_offset.color)).clicked() {
-            context.euclidean_offset.checkout_status(&mut context.model);
-        };
+            ui.vertical_centered(|ui| {
+                ui.label(RichText::new("Animation").strong());
+            });
+            ui.add_space(5.0);
+            ui.group(|ui| {
+                ui.horizontal_wrapped(|ui| {
+                    ui.label("Status: ");
+                    if context.animation_settings.is_running {
+                        ui.label(RichText::new("Running!").color(colors::LIME));
+                    } else {
+                        ui.label(RichText::new("Stopped.").color(colors::RED));
+                    };
+
+                    ui.vertical_centered(|ui| {
+                        if ui.button("Start / Stop").clicked() {
+                            context.animation_settings.checkout_status(&mut context.model);
+                            context.history.set_label("Animation");
+                        }
+                    });
+                });
+            });
 
-        ui.add_space(5.0);
+            ui.add_space(10.0);
 
-        Grid::new("OffsetGrid").num_columns(2).show(ui, |ui| {
-            ui.label("Offset X: ");
-            ui.add(
-                DragValue::new(&mut context.euclidean_offset.dot.x).speed(0.1)
-            );
+            ui.vertical_centered(|ui| {
+                ui.label(RichText::new("Euclidean Transformations").strong());
+            });
 
-            ui.end_row();
+            ui.add_space(5.0);
 
-            ui.label("Offset Y: ");
-            ui.add(
-                DragValue::new(&mut context.euclidean_offset.dot.y).speed(0.1)
-            );
+            if ui.checkbox(&mut context.euclidean_offset.is_enabled,
+                        RichText::new("Enable Offset").color(context.euclidean_offset.color)).clicked() {
+                context.euclidean_offset.checkout_status(&mut context.model);
+            };
 
-            ui.end_row();
+            ui.add_space(5.0);
 
-            ui.label("Dot Color: ");
-            egui::color_picker::color_edit_button_srgba(
-                ui,
-                &mut context.euclidean_offset.color,
-                egui::color_picker::Alpha::Opaque,
-            );
-        });
+            Grid::new("OffsetGrid").num_columns(2).show(ui, |ui| {
+                ui.label("Offset X: ");
+                ui.add(
+                    DragValue::new(&mut context.euclidean_offset.dot.x).speed(0.1)
+                );
 
-        ui.add_space(5.0);
+                ui.end_row();
 
-        ui.vertical_centered_justified(|ui| {
-            if ui.button("Reset").clicked() {
-                context.euclidean_offset = Default::default();
-            }
-        });
+                ui.label("Offset Y: ");
+                ui.add(
+                    DragValue::new(&mut context.euclidean_offset.dot.y).speed(0.1)
+                );
 
-        ui.add_space(5.0);
-        ui.separator();
-        ui.add_space(5.0);
+                ui.end_row();
 
-        ui.checkbox(&mut context.euclidean_rotation.is_enabled,
-                    RichText::new("Enable Rotation").color(context.euclidean_rotation.color));
+                ui.label("Dot Color: ");
+                egui::color_picker::color_edit_button_srgba(
+                    ui,
+                    &mut context.euclidean_offset.color,
+                    egui::color_picker::Alpha::Opaque,
+                );
+            });
 
-        ui.add_space(5.0);
+            ui.add_space(5.0);
 
-        Grid::new("RotationGrid").num_columns(2).show(ui, |ui| {
-            ui.label("Rotation X: ");
-            ui.add(
-                DragValue::new(&mut context.euclidean_rotation.dot.x).speed(0.1)
-            );
+            ui.vertical_centered_justified(|ui| {
+                if ui.button("Reset").clicked() {
+                    context.euclidean_offset = Default::default();
+                }
+            });
 
-            ui.end_row();
+            ui.add_space(5.0);
+            ui.separator();
+            ui.add_space(5.0);
 
-            ui.label("Rotation Y: ");
-            ui.add(
-                DragValue::new(&mut context.euclidean_rotation.dot.y).speed(0.1)
-            );
+            ui.checkbox(&mut context.euclidean_rotation.is_enabled,
+                        RichText::new("Enable Rotation").color(context.euclidean_rotation.color));
 
-            ui.end_row();
+            ui.add_space(5.0);
 
-            ui.label("Rotation Angle: ");
-            ui.add(
-                DragValue::new(&mut context.euclidean_rotation.angle)
-                    .speed(1)
-                    .range(-360..=360),
-            );
+            Grid::new("RotationGrid").num_columns(2).show(ui, |ui| {
+                ui.label("Rotation X: ");
+                ui.add(
+                    DragValue::new(&mut context.euclidean_rotation.dot.x).speed(0.1)
+                );
 
-            ui.end_row();
+                ui.end_row();
 
-            ui.label("Dot Color: ");
-            egui::color_picker::color_edit_button_srgba(
-                ui,
-                &mut context.euclidean_rotation.color,
-                egui::color_picker::Alpha::Opaque,
-            );
-        });
+                ui.label("Rotation Y: ");
+                ui.add(
+                    DragValue::new(&mut context.euclidean_rotation.dot.y).speed(0.1)
+                );
 
-        ui.add_space(5.0);
+                ui.end_row();
 
-        ui.vertical_centered_justified(|ui| {
-            if ui.button("Reset").clicked() {
-                context.euclidean_rotation = Default::default();
-            }
+                ui.label("Rotation Angle: ");
+                ui.add(
+                    DragValue::new(&mut context.euclidean_rotation.angle)
+                        .speed(1)
+                        .range(-360..=360),
+                );
+
+                ui.end_row();
+
+                ui.label("Dot Color: ");
+                egui::color_picker::color_edit_button_srgba(
+                    ui,
+                    &mut context.euclidean_rotation.color,
+                    egui::color_picker::Alpha::Opaque,
+                );
+            });
+
+            ui.add_space(5.0);
+
+            ui.vertical_centered_justified(|ui| {
+                if ui.button("Reset").clicked() {
+                    context.euclidean_rotation = Default::default();
+                }
+            });
         });
-    });
-}
 
-fn reset_to_defaults(context: &mut Context, canvas: &mut Canvas) {
-    canvas.screen_params = Default::default();
-    context.grid = Default::default();
-    context.model = Default::default();
-    context.animation_settings = Default::default();
-    context.euclidean_offset = Default::default();
-    context.euclidean_rotation = Default::default();
+        self.show_errors_if_some(ui);
+    }
+
+    fn show_errors_if_some(&mut self, ui: &egui::Ui) {
+        if let Some(error) = &mut self.error_box {
+            error.show(ui);
+
+            if error.is_closed() {
+                self.error_box = None;
+            }
+        }
+    }
+
+    fn reset_to_defaults(&self, context: &mut Context, canvas: &mut Canvas) {
+        context.history.set_label("Reset to defaults");
+
+        canvas.screen_params = Default::default();
+        context.grid = Default::default();
+        context.fill = Default::default();
+        context.reference_image = Default::default();
+        context.model = Default::default();
+        context.animation_settings = Default::default();
+        context.euclidean_offset = Default::default();
+        context.euclidean_rotation = Default::default();
+        context.selection = Default::default();
+        context.analysis = Default::default();
+    }
 }
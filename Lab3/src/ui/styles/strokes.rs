@@ -1,8 +1,9 @@
 use crate::ui::styles::colors;
-use eframe::epaint::Stroke;
+use eframe::epaint::{Color32, Stroke};
 
 const AXIS_WIDTH_PX: f32 = 2.0;
 const GRID_WIDTH_PX: f32 = 0.8;
+const SELECTION_WIDTH_PX: f32 = 1.5;
 
 pub fn axis_green() -> Stroke {
     Stroke::new(AXIS_WIDTH_PX, colors::LIME)
@@ -12,6 +13,10 @@ pub fn axis_red() -> Stroke {
     Stroke::new(AXIS_WIDTH_PX, colors::DARK_RED)
 }
 
+pub fn bounding_box_blue(width: f32) -> Stroke {
+    Stroke::new(width, colors::BLUE)
+}
+
 pub fn bezier_outline(width: f32) -> Stroke {
     Stroke::new(width, colors::BLACK)
 }
@@ -24,6 +29,10 @@ pub fn model_black(width: f32) -> Stroke {
     Stroke::new(width, colors::BLACK)
 }
 
+pub fn selection_frame(color: Color32) -> Stroke {
+    Stroke::new(SELECTION_WIDTH_PX, color)
+}
+
 pub fn skeleton_dark_grey(width: f32) -> Stroke {
     Stroke::new(width, colors::DARK_GRAY)
 }
@@ -1,5 +1,10 @@
 use crate::graphics::grid::Grid;
+use crate::models::boolean::BooleanOperation;
+use crate::models::fitting::CurveFitting;
 use crate::models::model::Model;
+use crate::models::nurbs::Nurbs;
+use crate::models::offset_curve::OffsetCurve;
+use crate::models::spline::Spline;
 use crate::operations::animation::AnimationSettings;
 use crate::operations::offset::Offset;
 use crate::operations::rotation::Rotation;
@@ -7,8 +12,13 @@ use crate::operations::rotation::Rotation;
 #[derive(Default)]
 pub struct Context {
     pub animation_settings: AnimationSettings,
+    pub boolean: BooleanOperation,
+    pub fitting: CurveFitting,
     pub grid: Grid,
     pub model: Model,
+    pub nurbs: Nurbs,
+    pub offset_curve: OffsetCurve,
+    pub spline: Spline,
     pub euclidean_offset: Offset,
     pub euclidean_rotation: Rotation,
 }
@@ -0,0 +1,23 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum AnimationError {
+    #[error("Start or end contour is not found.")]
+    ContourNotFound,
+
+    #[error("Contour is not a chain of quadratic segments.")]
+    InvalidContour(String),
+
+    #[error("Contours can not be matched point to point.")]
+    MismatchedContours(String),
+}
+
+impl AnimationError {
+    pub fn get_additional_info(&self) -> Option<&String> {
+        // Match only variants with String fields dynamically
+        match self {
+            Self::InvalidContour(s) | Self::MismatchedContours(s) => Some(s),
+            _ => None,
+        }
+    }
+}
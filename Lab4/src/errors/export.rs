@@ -0,0 +1,23 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum ExportError {
+    #[error("Cannot create file using specified path.")]
+    CantCreateFile(String),
+
+    #[error("Failed to write file.")]
+    WritingFailed(String),
+
+    #[error("Nothing to export.")]
+    EmptyContour,
+}
+
+impl ExportError {
+    pub fn get_additional_info(&self) -> Option<&String> {
+        // Match only variants with String fields dynamically
+        match self {
+            Self::CantCreateFile(s) | Self::WritingFailed(s) => Some(s),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,23 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum ImportError {
+    #[error("Cannot open file using specified path.")]
+    CantOpenFile(String),
+
+    #[error("Failed to parse file.")]
+    ParsingFailed(String),
+
+    #[error("File contains less than two points.")]
+    NotEnoughPoints,
+}
+
+impl ImportError {
+    pub fn get_additional_info(&self) -> Option<&String> {
+        // Match only variants with String fields dynamically
+        match self {
+            Self::CantOpenFile(s) | Self::ParsingFailed(s) => Some(s),
+            _ => None,
+        }
+    }
+}
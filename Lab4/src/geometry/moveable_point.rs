@@ -49,11 +49,16 @@ impl MoveablePoint {
         false
     }
 
-    pub fn show_tooltip(&self, index: usize, response: Response) {
-        let label = format!(
-            "Point #{index}.\nCoordinates:\n- X: {}\n- Y: {}",
-            self.x, self.y
-        );
+    pub fn show_tooltip(&self, index: usize, details: Option<String>, response: Response) {
+        self.show_titled_tooltip(&format!("Point #{index}."), details, response);
+    }
+
+    pub fn show_titled_tooltip(&self, title: &str, details: Option<String>, response: Response) {
+        let mut label = format!("{title}\nCoordinates:\n- X: {}\n- Y: {}", self.x, self.y);
+        if let Some(details) = details {
+            label.push('\n');
+            label.push_str(&details);
+        }
 
         response.on_hover_text(label);
     }
@@ -54,11 +54,10 @@ impl ScreenParams {
     pub fn point_px_to_cm<T: Positionable>(&self, point: T) -> T {
         debug_assert!(point.is_converted_checked());
 
-        let x = (point.x() * self.grid_unit_length / self.px_per_cm) - self.canvas_center.x
-            + self.offset.0;
-        let y = (point.y() * self.grid_unit_length / self.px_per_cm)
-            + self.canvas_center.y
-            + self.offset.1;
+        let x = (point.x() - self.canvas_center.x - self.offset.0) * self.grid_unit_length
+            / self.px_per_cm;
+        let y = -(point.y() - self.canvas_center.y - self.offset.1) * self.grid_unit_length
+            / self.px_per_cm;
 
         T::new(x, y).with_converted_unchecked()
     }
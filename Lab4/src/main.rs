@@ -27,7 +27,10 @@ fn main() {
 pub mod config;
 pub mod context;
 pub mod errors {
+    pub mod animation;
     pub mod env;
+    pub mod export;
+    pub mod import;
     pub mod log;
 }
 pub mod geometry {
@@ -36,6 +39,7 @@ pub mod geometry {
     pub mod point;
 }
 pub mod graphics {
+    pub mod fill;
     pub mod grid;
     pub mod screen;
 }
@@ -47,7 +51,14 @@ pub mod math {
 pub mod models {
     pub mod bezier_curve;
     pub mod bezier_point;
+    pub mod boolean;
+    pub mod fitting;
     pub mod model;
+    pub mod nurbs;
+    pub mod offset_curve;
+    pub mod projection;
+    pub mod segment;
+    pub mod spline;
 }
 pub mod operations {
     pub mod animation;
@@ -62,6 +73,7 @@ pub mod ui {
     pub mod core;
     pub mod components {
         pub mod canvas;
+        pub mod message_box;
         pub mod settings;
     }
     pub mod styles {
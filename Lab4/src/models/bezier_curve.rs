@@ -1,6 +1,7 @@
 use crate::geometry::line::Line;
 use crate::geometry::point::Point;
 use crate::math::vector;
+use crate::models::segment::Segment;
 use crate::traits::positionable::Positionable;
 use egui::Stroke;
 
@@ -67,3 +68,175 @@ pub fn c1_continuity<T: Positionable>(
         defining_second.set_y(y);
     }
 }
+
+// G2 (curvature) continuity at the joint of two quadratic segments:
+// (control_first, defining_first, control) and (control, defining_second, control_second).
+// Tangent is aligned first, then length of the changed side handle is adjusted, so
+// curvatures on both sides are equal: k = d / (2 * a^2), where `a` is handle length
+// and `d` is distance from the tangent line to the far point of the segment.
+pub fn g2_continuity<T: Positionable>(
+    control_first: &T, defining_first: &mut T, control: &mut T, defining_second: &mut T,
+    control_second: &T, change: u32,
+) {
+    c1_continuity(defining_first, control, defining_second, change);
+
+    let length_first = vector::length(T::new(
+        control.x() - defining_first.x(),
+        control.y() - defining_first.y(),
+    ));
+    let length_second = vector::length(T::new(
+        defining_second.x() - control.x(),
+        defining_second.y() - control.y(),
+    ));
+    if length_first < f32::EPSILON || length_second < f32::EPSILON {
+        return;
+    }
+
+    // Unit tangent at the joint
+    let tangent = (
+        (defining_second.x() - control.x()) / length_second,
+        (defining_second.y() - control.y()) / length_second,
+    );
+
+    let distance_first = tangent.0 * (control_first.y() - control.y())
+        - tangent.1 * (control_first.x() - control.x());
+    let distance_second = tangent.0 * (control_second.y() - control.y())
+        - tangent.1 * (control_second.x() - control.x());
+
+    // Curvature changes sign (inflection) or is zero: only G1 is possible.
+    let ratio = distance_second / distance_first;
+    if !ratio.is_finite() || ratio <= 0.0 {
+        return;
+    }
+
+    if change == 1 {
+        let length = length_second / ratio.sqrt();
+        defining_first.set_x(control.x() - tangent.0 * length);
+        defining_first.set_y(control.y() - tangent.1 * length);
+    } else if change == 2 {
+        let length = length_first * ratio.sqrt();
+        defining_second.set_x(control.x() + tangent.0 * length);
+        defining_second.set_y(control.y() + tangent.1 * length);
+    }
+}
+
+// Signed curvature at the end of the first segment and at the start of the second one.
+pub fn joint_curvature<T: Positionable>(
+    control_first: &T, defining_first: &T, control: &T, defining_second: &T, control_second: &T,
+) -> (f32, f32) {
+    (
+        Segment::new(control_first, defining_first, control).curvature(1.0),
+        Segment::new(control, defining_second, control_second).curvature(0.0),
+    )
+}
+
+// Spikes along the normal with length proportional to curvature, plus the line through their ends.
+pub fn curvature_comb<T: Positionable>(
+    lines: &mut Vec<Line<Point>>, stroke: &Stroke, samples: usize, scale: f32, control_first: &T,
+    control_second: &T, defining: &T,
+) {
+    debug_assert!(samples > 0);
+
+    let segment = Segment::new(control_first, defining, control_second);
+    let mut tips: Vec<Point> = vec![];
+
+    for i in 0..=samples {
+        let u = i as f32 / samples as f32;
+
+        let point = formula(control_first, control_second, defining, u);
+        let (dx, dy) = segment.derivative(u);
+        let length = f32::sqrt(dx * dx + dy * dy);
+        if length < f32::EPSILON {
+            continue;
+        }
+
+        // Spike is directed away from the center of curvature.
+        let curvature = segment.curvature(u);
+        let normal = (-dy / length, dx / length);
+        let tip = Point::new(
+            point.x - normal.0 * curvature * scale,
+            point.y - normal.1 * curvature * scale,
+        );
+
+        lines.push(Line::new(point, tip, *stroke));
+        tips.push(tip);
+    }
+
+    tips.windows(2).for_each(|pair| {
+        let line = Line::new(pair[0], pair[1], *stroke);
+        lines.push(line);
+    });
+}
+
+// Measured continuity of the joint between (control_first, defining_first, control) and
+// (control, defining_second, control_second). Tolerance is relative.
+pub fn continuity<T: Positionable>(
+    control_first: &T, defining_first: &T, control: &T, defining_second: &T, control_second: &T,
+    tolerance: f32,
+) -> Continuity {
+    let first = (
+        control.x() - defining_first.x(),
+        control.y() - defining_first.y(),
+    );
+    let second = (
+        defining_second.x() - control.x(),
+        defining_second.y() - control.y(),
+    );
+
+    let length_first = vector::length(T::new(first.0, first.1));
+    let length_second = vector::length(T::new(second.0, second.1));
+    if length_first < f32::EPSILON || length_second < f32::EPSILON {
+        return Continuity::C0;
+    }
+
+    // Sine and cosine of the angle between tangents.
+    let sine = (first.0 * second.1 - first.1 * second.0) / (length_first * length_second);
+    let cosine = (first.0 * second.0 + first.1 * second.1) / (length_first * length_second);
+    if cosine <= 0.0 || sine.abs() > tolerance {
+        return Continuity::C0;
+    }
+
+    let (curvature_first, curvature_second) = joint_curvature(
+        control_first,
+        defining_first,
+        control,
+        defining_second,
+        control_second,
+    );
+    let curvature_max = f32::max(curvature_first.abs(), curvature_second.abs());
+    if curvature_max < f32::EPSILON
+        || (curvature_first - curvature_second).abs() / curvature_max <= tolerance
+    {
+        return Continuity::G2;
+    }
+
+    let length_max = f32::max(length_first, length_second);
+    if (length_first - length_second).abs() / length_max <= tolerance {
+        return Continuity::C1;
+    }
+
+    Continuity::G1
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Continuity {
+    // Only positions are equal.
+    C0,
+    // Tangent directions are equal.
+    G1,
+    // First derivatives are equal.
+    C1,
+    // Tangent directions and curvatures are equal.
+    G2,
+}
+
+impl Continuity {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Continuity::C0 => "C0",
+            Continuity::G1 => "G1",
+            Continuity::C1 => "C1",
+            Continuity::G2 => "G2",
+        }
+    }
+}
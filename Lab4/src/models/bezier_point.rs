@@ -2,7 +2,7 @@ use crate::geometry::moveable_point::MoveablePoint;
 use crate::models::model::Model;
 use crate::traits::positionable::Positionable;
 use eframe::epaint::Color32;
-use egui::Response;
+use egui::{Id, Response};
 
 #[derive(Debug, Copy, Clone)]
 pub struct BezierPoint {
@@ -10,6 +10,7 @@ pub struct BezierPoint {
 
     pub kind: BezierPointType,
     pub smoothness: SmoothnessType,
+    pub constraint: Constraint,
 }
 
 impl BezierPoint {
@@ -19,6 +20,7 @@ impl BezierPoint {
 
             kind: BezierPointType::Defining,
             smoothness: SmoothnessType::None,
+            constraint: Default::default(),
         }
     }
 
@@ -28,37 +30,147 @@ impl BezierPoint {
 
             kind: BezierPointType::Control,
             smoothness: SmoothnessType::Break,
+            constraint: Default::default(),
         }
     }
 
     pub fn color(&self, model: &Model) -> Color32 {
+        if model.mirror_selection.contains(&self.point.id) {
+            return model.fill_selected;
+        }
+
         match self.kind {
             BezierPointType::Control if self.smoothness == SmoothnessType::Smooth => {
                 model.fill_smooth
             },
+            BezierPointType::Control if self.smoothness == SmoothnessType::Curvature => {
+                model.fill_curvature
+            },
             BezierPointType::Control => model.fill_control,
             BezierPointType::Defining => model.fill_defining,
         }
     }
 
-    pub fn update_on_change_smoothness(&mut self, ui: &egui::Ui, response: &Response) {
-        if let BezierPointType::Defining = self.kind {
-            return;
-        }
+    // Right click menu with smoothness and constraints of the point.
+    // `mirror_source` is the point, chosen to be mirrored by the next linked one.
+    // `mirror_selection` are the points, mirrored together by the settings panel.
+    // Returns true, if constraints were changed.
+    pub fn update_on_context_menu(
+        &mut self, index: usize, mirror_source: &mut Option<Id>, mirror_selection: &mut Vec<Id>,
+        response: &Response,
+    ) -> bool {
+        let before = (self.smoothness, self.constraint);
+
+        response.context_menu(|ui| {
+            ui.label(format!("Point #{}", index + 1));
+            ui.separator();
+
+            if let BezierPointType::Control = self.kind {
+                ui.label("Smoothness:");
+                ui.radio_value(&mut self.smoothness, SmoothnessType::Break, "Break");
+                ui.radio_value(&mut self.smoothness, SmoothnessType::Smooth, "Smooth (C1)");
+                ui.radio_value(
+                    &mut self.smoothness,
+                    SmoothnessType::Curvature,
+                    "Curvature (G2)",
+                );
+                ui.separator();
+
+                ui.label("Tangent:");
+                ui.radio_value(&mut self.constraint.tangent, TangentDirection::Free, "Free");
+                ui.radio_value(
+                    &mut self.constraint.tangent,
+                    TangentDirection::Horizontal,
+                    "Horizontal",
+                );
+                ui.radio_value(
+                    &mut self.constraint.tangent,
+                    TangentDirection::Vertical,
+                    "Vertical",
+                );
+                ui.checkbox(&mut self.constraint.is_symmetric, "Symmetric Handles")
+                    .on_hover_text("Both defining points are at the same distance from the point.");
+                ui.separator();
+            }
 
-        if response.secondary_clicked() {
-            self.smoothness = match self.smoothness {
-                SmoothnessType::None => SmoothnessType::None,
-                SmoothnessType::Break => SmoothnessType::Smooth,
-                SmoothnessType::Smooth => SmoothnessType::Break,
-            };
+            ui.checkbox(&mut self.constraint.is_locked, "Locked")
+                .on_hover_text("Point is not moved by drags, constraints and animation.");
+            ui.separator();
 
-            ui.ctx().request_repaint();
+            if self.constraint.mirror_of.is_some() {
+                if ui.button("Unlink Mirror").clicked() {
+                    self.constraint.mirror_of = None;
+                    ui.close_menu();
+                }
+            } else {
+                let source = mirror_source.filter(|source| *source != self.point.id);
+                if ui
+                    .add_enabled(
+                        source.is_some(),
+                        egui::Button::new("Link as Mirror of Source"),
+                    )
+                    .on_hover_text("Point is kept symmetric to the source across the mirror axis.")
+                    .on_disabled_hover_text("Choose another point as mirror source first.")
+                    .clicked()
+                {
+                    self.constraint.mirror_of = source;
+                    *mirror_source = None;
+                    ui.close_menu();
+                }
+
+                if ui.button("Set as Mirror Source").clicked() {
+                    *mirror_source = Some(self.point.id);
+                    ui.close_menu();
+                }
+            }
+
+            if let Some(position) = mirror_selection.iter().position(|id| *id == self.point.id) {
+                if ui.button("Remove from Mirror Selection").clicked() {
+                    mirror_selection.remove(position);
+                    ui.close_menu();
+                }
+            } else if ui
+                .button("Add to Mirror Selection")
+                .on_hover_text(
+                    "Selected part of the chain is mirrored by the button in Constraint Settings.",
+                )
+                .clicked()
+            {
+                mirror_selection.push(self.point.id);
+                ui.close_menu();
+            }
+        });
+
+        before != (self.smoothness, self.constraint)
+    }
+
+    // Constraints as lines of the tooltip.
+    pub fn constraint_details(&self) -> Option<String> {
+        let mut details: Vec<&str> = vec![];
+        if self.constraint.is_locked {
+            details.push("- Locked");
+        }
+        match self.constraint.tangent {
+            TangentDirection::Free => {},
+            TangentDirection::Horizontal => details.push("- Horizontal Tangent"),
+            TangentDirection::Vertical => details.push("- Vertical Tangent"),
+        }
+        if self.constraint.is_symmetric {
+            details.push("- Symmetric Handles");
+        }
+        if self.constraint.mirror_of.is_some() {
+            details.push("- Mirrored");
+        }
+
+        if details.is_empty() {
+            None
+        } else {
+            Some(format!("Constraints:\n{}", details.join("\n")))
         }
     }
 }
 
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq)]
 pub enum BezierPointType {
     Control,
     Defining,
@@ -71,4 +183,26 @@ pub enum SmoothnessType {
 
     Break,
     Smooth,
+    // G2: tangents and curvatures are equal on both sides.
+    Curvature,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Default)]
+pub struct Constraint {
+    pub is_locked: bool,
+
+    // Only for control points.
+    pub tangent: TangentDirection,
+    pub is_symmetric: bool,
+
+    // Point, which position is mirrored across the mirror axis of the model.
+    pub mirror_of: Option<Id>,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Default)]
+pub enum TangentDirection {
+    #[default]
+    Free,
+    Horizontal,
+    Vertical,
 }
@@ -0,0 +1,448 @@
+use crate::errors::export::ExportError;
+use crate::geometry::line::Line;
+use crate::geometry::point::Point;
+use crate::graphics::fill::Fill;
+use crate::graphics::screen::ScreenParams;
+use crate::models::bezier_curve::bezier_curve;
+use crate::models::bezier_point::BezierPoint;
+use crate::models::segment::{cross, distance, lerp, Segment};
+use crate::traits::positionable::Positionable;
+use crate::ui::styles::{colors, strokes};
+use egui::{Color32, Stroke};
+use std::fs;
+use std::path::PathBuf;
+
+// Ends closer than this are considered the same point.
+const CLOSURE_EPSILON: f32 = 1e-3;
+// Intersections closer than this are merged.
+const MERGE_DISTANCE: f32 = 1e-3;
+// Intersections closer to the end of segment are placed at the end.
+const PARAMETER_EPSILON: f32 = 1e-3;
+// Points of segment in polygon, used for inside test and fill.
+const FLATTENING_SAMPLES: usize = 32;
+
+// Union, intersection or difference of two closed contours.
+pub struct BooleanOperation {
+    pub is_enabled: bool,
+
+    pub operation: Operation,
+    // Operands: `None` is the model, otherwise index of the saved contour.
+    pub first: Option<usize>,
+    pub second: Option<usize>,
+
+    // Every contour of the result is the chain: control, defining, control, ...
+    pub result: Vec<Vec<BezierPoint>>,
+    // Chains of the result, which did not return to their start and were dropped.
+    pub open_chains: usize,
+
+    pub fill: Fill,
+    pub stroke: Stroke,
+    pub point_color: Color32,
+}
+
+impl Default for BooleanOperation {
+    fn default() -> Self {
+        Self {
+            is_enabled: false,
+
+            operation: Operation::Union,
+            first: None,
+            second: Some(1),
+
+            result: vec![],
+            open_chains: 0,
+
+            fill: Default::default(),
+            stroke: strokes::boolean_lime(0.06),
+            point_color: colors::LIME,
+        }
+    }
+}
+
+impl BooleanOperation {
+    pub fn compute(&mut self, first: &[BezierPoint], second: &[BezierPoint]) {
+        self.result.clear();
+        self.open_chains = 0;
+
+        let first = contour(first);
+        let second = contour(second);
+        if first.is_empty() || second.is_empty() {
+            return;
+        }
+
+        let (first_cuts, second_cuts) = intersections(&first, &second);
+        let first_pieces = split(&first, &first_cuts);
+        let second_pieces = split(&second, &second_cuts);
+
+        let first_polygon = flatten(&first);
+        let second_polygon = flatten(&second);
+
+        let is_inside =
+            |piece: &Segment, polygon: &[Point]| winding(polygon, piece.point(0.5)) != 0;
+
+        let mut selected: Vec<Segment> = vec![];
+        for piece in &first_pieces {
+            let inside = is_inside(piece, &second_polygon);
+            let keep = match self.operation {
+                Operation::Union | Operation::Difference => !inside,
+                Operation::Intersection => inside,
+            };
+
+            if keep {
+                selected.push(*piece);
+            }
+        }
+        for piece in &second_pieces {
+            let inside = is_inside(piece, &first_polygon);
+            match self.operation {
+                Operation::Union if !inside => selected.push(*piece),
+                Operation::Intersection if inside => selected.push(*piece),
+                // Part of the second contour inside the first one becomes the boundary of a hole.
+                Operation::Difference if inside => selected.push(reversed(piece)),
+                _ => {},
+            }
+        }
+
+        let (loops, open_chains) = chain(selected);
+        if open_chains > 0 {
+            log::warn!("Boolean operation dropped {} open chains.", open_chains);
+        }
+        self.open_chains = open_chains;
+
+        self.result = loops
+            .iter()
+            .map(|segments| {
+                let mut points = vec![BezierPoint::control(
+                    segments[0].start.x,
+                    segments[0].start.y,
+                )];
+                for segment in segments {
+                    points.push(BezierPoint::defining(segment.middle.x, segment.middle.y));
+                    points.push(BezierPoint::control(segment.end.x, segment.end.y));
+                }
+
+                points
+            })
+            .collect();
+    }
+
+    pub fn lines(&self, screen_params: ScreenParams) -> Vec<Line<Point>> {
+        let mut stroke = self.stroke;
+        stroke.width = screen_params.value_cm_to_px(self.stroke.width);
+
+        let mut vec: Vec<Line<Point>> = vec![];
+
+        for points in &self.result {
+            for i in (0..points.len().saturating_sub(2)).step_by(2) {
+                bezier_curve(
+                    &mut vec,
+                    &stroke,
+                    0.1,
+                    &points[i].point,
+                    &points[i + 2].point,
+                    &points[i + 1].point,
+                );
+            }
+        }
+
+        vec
+    }
+
+    // Flattened contours of the result for the fill.
+    pub fn polygons(&self) -> Vec<Vec<Point>> {
+        self.result
+            .iter()
+            .map(|points| flatten(&segments(points)))
+            .collect()
+    }
+
+    pub fn save_dialog(&self) -> Result<(), ExportError> {
+        if self.result.is_empty() {
+            return Err(ExportError::EmptyContour);
+        }
+
+        let file_dialog = rfd::FileDialog::new()
+            .add_filter("SVG", &["svg"])
+            .set_file_name("contour.svg");
+
+        if let Some(path) = file_dialog.save_file() {
+            self.save_in(path)
+        } else {
+            Ok(())
+        }
+    }
+
+    // Result is saved as SVG path of quadratic segments. Y axis of SVG is directed down.
+    pub fn save_in(&self, path: PathBuf) -> Result<(), ExportError> {
+        let points: Vec<Point> = self
+            .result
+            .iter()
+            .flatten()
+            .map(|bezier| Point::new(bezier.point.x, -bezier.point.y))
+            .collect();
+
+        let margin = 1.0;
+        let (min, max) = points.iter().fold(
+            (
+                Point::new(f32::MAX, f32::MAX),
+                Point::new(f32::MIN, f32::MIN),
+            ),
+            |(min, max), point| {
+                (
+                    Point::new(f32::min(min.x, point.x), f32::min(min.y, point.y)),
+                    Point::new(f32::max(max.x, point.x), f32::max(max.y, point.y)),
+                )
+            },
+        );
+
+        let mut path_data = String::new();
+        for contour in &self.result {
+            let Some(first) = contour.first() else {
+                continue;
+            };
+
+            path_data.push_str(&format!("M {} {} ", first.point.x, -first.point.y));
+            for pair in contour[1..].chunks(2) {
+                if let [defining, control] = pair {
+                    path_data.push_str(&format!(
+                        "Q {} {} {} {} ",
+                        defining.point.x, -defining.point.y, control.point.x, -control.point.y
+                    ));
+                }
+            }
+            path_data.push_str("Z ");
+        }
+
+        let fill = self.fill.color;
+        let svg = format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"{} {} {} {}\">\n  \
+             <path d=\"{}\" fill=\"rgb({}, {}, {})\" fill-opacity=\"{}\" fill-rule=\"evenodd\" \
+             stroke=\"black\" stroke-width=\"{}\"/>\n</svg>\n",
+            min.x - margin,
+            min.y - margin,
+            max.x - min.x + 2.0 * margin,
+            max.y - min.y + 2.0 * margin,
+            path_data.trim_end(),
+            fill.r(),
+            fill.g(),
+            fill.b(),
+            self.fill.opacity,
+            self.stroke.width,
+        );
+
+        fs::write(&path, svg).map_err(|error| {
+            let info = format!("{}: {}", path.display(), error);
+            match error.kind() {
+                std::io::ErrorKind::NotFound | std::io::ErrorKind::PermissionDenied => {
+                    ExportError::CantCreateFile(info)
+                },
+                _ => ExportError::WritingFailed(info),
+            }
+        })
+    }
+}
+
+#[derive(PartialEq)]
+pub enum Operation {
+    Union,
+    Intersection,
+    // First contour without the second one.
+    Difference,
+}
+
+fn segments(points: &[BezierPoint]) -> Vec<Segment> {
+    (0..points.len().saturating_sub(2))
+        .step_by(2)
+        .map(|i| Segment::new(&points[i].point, &points[i + 1].point, &points[i + 2].point))
+        .collect()
+}
+
+// Closed counter-clockwise contour. Open one is closed by chord from the last point to the first.
+fn contour(points: &[BezierPoint]) -> Vec<Segment> {
+    let mut segments = segments(points);
+
+    if let (Some(first), Some(last)) = (segments.first().copied(), segments.last().copied()) {
+        if distance(last.end, first.start) > CLOSURE_EPSILON {
+            segments.push(Segment {
+                start: last.end,
+                middle: lerp(last.end, first.start, 0.5),
+                end: first.start,
+            });
+        }
+    }
+
+    // Closed form of 1/2 * integral(x dy - y dx) over quadratic segments.
+    let area: f32 = segments
+        .iter()
+        .map(|segment| {
+            cross(segment.start, segment.middle) / 3.0
+                + cross(segment.start, segment.end) / 6.0
+                + cross(segment.middle, segment.end) / 3.0
+        })
+        .sum();
+
+    if area < 0.0 {
+        segments = segments.iter().rev().map(reversed).collect();
+    }
+
+    segments
+}
+
+fn reversed(segment: &Segment) -> Segment {
+    Segment {
+        start: segment.end,
+        middle: segment.middle,
+        end: segment.start,
+    }
+}
+
+fn flatten(segments: &[Segment]) -> Vec<Point> {
+    segments
+        .iter()
+        .flat_map(|segment| {
+            (0..FLATTENING_SAMPLES).map(|i| segment.point(i as f32 / FLATTENING_SAMPLES as f32))
+        })
+        .collect()
+}
+
+// Winding number of the closed polygon around the point.
+fn winding(polygon: &[Point], point: Point) -> i32 {
+    let mut winding = 0;
+
+    for i in 0..polygon.len() {
+        let start = polygon[i];
+        let end = polygon[(i + 1) % polygon.len()];
+
+        let side = cross(
+            Point::new(end.x - start.x, end.y - start.y),
+            Point::new(point.x - start.x, point.y - start.y),
+        );
+
+        if start.y <= point.y && point.y < end.y && side > 0.0 {
+            winding += 1;
+        } else if end.y <= point.y && point.y < start.y && side < 0.0 {
+            winding -= 1;
+        }
+    }
+
+    winding
+}
+
+// Cuts of every segment of both contours: parameter and the exact intersection point.
+type Cuts = Vec<Vec<(f32, Point)>>;
+
+fn intersections(first: &[Segment], second: &[Segment]) -> (Cuts, Cuts) {
+    let mut first_cuts: Cuts = vec![vec![]; first.len()];
+    let mut second_cuts: Cuts = vec![vec![]; second.len()];
+    let mut points: Vec<Point> = vec![];
+
+    for (i, first_segment) in first.iter().enumerate() {
+        for (j, second_segment) in second.iter().enumerate() {
+            for (t_first, t_second) in first_segment.intersections(second_segment) {
+                let point = lerp(
+                    first_segment.point(t_first),
+                    second_segment.point(t_second),
+                    0.5,
+                );
+
+                if points
+                    .iter()
+                    .any(|known| distance(*known, point) <= MERGE_DISTANCE)
+                {
+                    continue;
+                }
+                points.push(point);
+
+                first_cuts[i].push((t_first, point));
+                second_cuts[j].push((t_second, point));
+            }
+        }
+    }
+
+    (first_cuts, second_cuts)
+}
+
+// Splits segments at cuts. Both sides of the cut end exactly at the intersection point.
+fn split(segments: &[Segment], cuts: &Cuts) -> Vec<Segment> {
+    let count = segments.len();
+    let mut segments = segments.to_vec();
+
+    // Cuts at the joint are moved to both segments, that share it, so chaining doesn't break there.
+    for (i, cuts) in cuts.iter().enumerate() {
+        for (t, point) in cuts {
+            if *t < PARAMETER_EPSILON {
+                segments[i].start = *point;
+                segments[(i + count - 1) % count].end = *point;
+            } else if *t > 1.0 - PARAMETER_EPSILON {
+                segments[i].end = *point;
+                segments[(i + 1) % count].start = *point;
+            }
+        }
+    }
+
+    let mut pieces: Vec<Segment> = vec![];
+
+    for (segment, cuts) in segments.iter().zip(cuts) {
+        let mut cuts: Vec<(f32, Point)> = cuts
+            .iter()
+            .filter(|(t, _)| (PARAMETER_EPSILON..=1.0 - PARAMETER_EPSILON).contains(t))
+            .copied()
+            .collect();
+        cuts.sort_by(|first, second| first.0.total_cmp(&second.0));
+
+        let mut rest = *segment;
+        let mut passed = 0.0;
+        for (t, point) in cuts {
+            let (mut left, mut right) = rest.split((t - passed) / (1.0 - passed));
+            left.end = point;
+            right.start = point;
+
+            pieces.push(left);
+            rest = right;
+            passed = t;
+        }
+
+        pieces.push(rest);
+    }
+
+    pieces
+}
+
+// Connects pieces into closed loops: every next piece starts where the previous one ends.
+// Chains, which do not return to their start, are dropped. Returns loops and count of dropped chains.
+fn chain(mut pieces: Vec<Segment>) -> (Vec<Vec<Segment>>, usize) {
+    let mut loops: Vec<Vec<Segment>> = vec![];
+    let mut open_chains = 0;
+
+    while !pieces.is_empty() {
+        let first = pieces.remove(0);
+        let mut current = vec![first];
+
+        let is_closed = loop {
+            let end = current[current.len() - 1].end;
+            if current.len() > 1 && distance(end, first.start) <= CLOSURE_EPSILON {
+                break true;
+            }
+
+            let next = pieces
+                .iter()
+                .enumerate()
+                .map(|(index, piece)| (index, distance(piece.start, end)))
+                .filter(|(_, gap)| *gap <= CLOSURE_EPSILON)
+                .min_by(|first, second| first.1.total_cmp(&second.1));
+
+            match next {
+                Some((index, _)) => current.push(pieces.remove(index)),
+                None => break false,
+            }
+        };
+
+        if is_closed {
+            loops.push(current);
+        } else {
+            open_chains += 1;
+        }
+    }
+
+    (loops, open_chains)
+}
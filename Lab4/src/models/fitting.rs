@@ -0,0 +1,589 @@
+use crate::errors::import::ImportError;
+use crate::geometry::line::Line;
+use crate::geometry::moveable_point::MoveablePoint;
+use crate::geometry::point::Point;
+use crate::graphics::screen::ScreenParams;
+use crate::models::bezier_curve::bezier_curve;
+use crate::models::bezier_point::{BezierPoint, SmoothnessType};
+use crate::models::segment::{cross, distance, lerp, Segment};
+use crate::traits::positionable::Positionable;
+use crate::ui::styles::strokes;
+use egui::{Response, Stroke};
+use std::fs;
+use std::path::PathBuf;
+
+// Samples closer than this are merged.
+const DUPLICATE_EPSILON: f32 = 1e-4;
+// Samples on each side of the point, used to estimate its turning angle and tangent.
+const WINDOW_SAMPLES: usize = 3;
+// Newton iterations of reparameterization before the piece is split.
+const MAX_REPARAMETERIZATIONS: u32 = 4;
+// Error after which reparameterization is not tried, the piece is split at once.
+const REPARAMETERIZATION_FACTOR: f32 = 4.0;
+const MAX_DEPTH: u32 = 16;
+// Sine of the maximum angle between chord and tangents of straight piece.
+const STRAIGHT_SINE: f32 = 0.05;
+
+// Chain of quadratic segments, approximating freehand stroke or imported points (Schneider's
+// algorithm, adapted to quadratics: defining point is the intersection of end tangents).
+pub struct CurveFitting {
+    // Drawing on the canvas instead of dragging it.
+    pub is_enabled: bool,
+
+    // Maximum distance between samples and the fitted curve.
+    pub tolerance: f32,
+    // Turning angle (in degrees), after which the sample is a corner.
+    pub corner_angle: f32,
+    // Minimum distance in pixels between recorded samples of the stroke.
+    pub sample_spacing: f32,
+
+    pub samples: Vec<Point>,
+    pub result: Vec<BezierPoint>,
+
+    pub samples_stroke: Stroke,
+    pub stroke: Stroke,
+}
+
+impl Default for CurveFitting {
+    fn default() -> Self {
+        Self {
+            is_enabled: false,
+
+            tolerance: 0.05,
+            corner_angle: 60.0,
+            sample_spacing: 3.0,
+
+            samples: vec![],
+            result: vec![],
+
+            samples_stroke: strokes::samples_gray(0.03),
+            stroke: strokes::fitting_red(0.06),
+        }
+    }
+}
+
+impl CurveFitting {
+    // Records the stroke while the canvas is dragged, fits it when dragging stops.
+    pub fn update_on_drag(&mut self, screen_params: ScreenParams, response: &Response) {
+        if response.drag_started() {
+            self.samples.clear();
+            self.result.clear();
+        }
+
+        if response.dragged() {
+            if let Some(position) = response.interact_pointer_pos() {
+                let point = screen_params.point_px_to_cm(MoveablePoint::from_pos2(position));
+                let point = Point::new(point.x, point.y);
+
+                let is_far = self.samples.last().map_or(true, |last| {
+                    screen_params.value_cm_to_px(distance(*last, point)) >= self.sample_spacing
+                });
+                if is_far {
+                    self.samples.push(point);
+                }
+            }
+        }
+
+        if response.drag_stopped() {
+            self.fit();
+        }
+    }
+
+    pub fn load_dialog(&mut self) -> Result<(), ImportError> {
+        let file_dialog = rfd::FileDialog::new().add_filter("CSV", &["csv", "txt"]);
+
+        if let Some(path) = file_dialog.pick_file() {
+            self.load_from(path)
+        } else {
+            Ok(())
+        }
+    }
+
+    // Every line is "x, y". Separator is comma, semicolon or whitespace, first line may be a header.
+    pub fn load_from(&mut self, path: PathBuf) -> Result<(), ImportError> {
+        let content = fs::read_to_string(&path)
+            .map_err(|error| ImportError::CantOpenFile(format!("{}: {}", path.display(), error)))?;
+
+        let mut samples: Vec<Point> = vec![];
+        for (index, line) in content.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let values: Vec<&str> = line
+                .split(|c: char| c == ',' || c == ';' || c.is_whitespace())
+                .filter(|value| !value.is_empty())
+                .collect();
+            let coordinates: Option<Vec<f32>> = values
+                .iter()
+                .take(2)
+                .map(|value| value.parse::<f32>().ok())
+                .collect();
+
+            match coordinates.as_deref() {
+                Some([x, y]) => samples.push(Point::new(*x, *y)),
+                _ if index == 0 => continue,
+                _ => {
+                    return Err(ImportError::ParsingFailed(format!(
+                        "Line {}: \"{}\" is not a pair of numbers.",
+                        index + 1,
+                        line
+                    )))
+                },
+            }
+        }
+
+        if samples.len() < 2 {
+            return Err(ImportError::NotEnoughPoints);
+        }
+
+        self.samples = samples;
+        self.fit();
+
+        Ok(())
+    }
+
+    pub fn fit(&mut self) {
+        self.result = fit(
+            &self.samples,
+            self.tolerance,
+            self.corner_angle.to_radians(),
+        );
+    }
+
+    pub fn samples_lines(&self, screen_params: ScreenParams) -> Vec<Line<Point>> {
+        let mut stroke = self.samples_stroke;
+        stroke.width = screen_params.value_cm_to_px(self.samples_stroke.width);
+
+        self.samples
+            .windows(2)
+            .map(|pair| Line::new(pair[0], pair[1], stroke))
+            .collect()
+    }
+
+    pub fn lines(&self, screen_params: ScreenParams) -> Vec<Line<Point>> {
+        let mut stroke = self.stroke;
+        stroke.width = screen_params.value_cm_to_px(self.stroke.width);
+
+        let mut vec: Vec<Line<Point>> = vec![];
+
+        for i in (0..self.result.len().saturating_sub(2)).step_by(2) {
+            bezier_curve(
+                &mut vec,
+                &stroke,
+                0.1,
+                &self.result[i].point,
+                &self.result[i + 2].point,
+                &self.result[i + 1].point,
+            );
+        }
+
+        vec
+    }
+}
+
+// Fitted segment. Defining point lies on tangents at both ends, so joints are smooth.
+struct FittedSegment {
+    segment: Segment,
+    is_tangent: bool,
+}
+
+fn fit(samples: &[Point], tolerance: f32, corner_angle: f32) -> Vec<BezierPoint> {
+    let mut points: Vec<Point> = vec![];
+    for sample in samples {
+        if points
+            .last()
+            .map_or(true, |last| distance(*last, *sample) > DUPLICATE_EPSILON)
+        {
+            points.push(*sample);
+        }
+    }
+
+    // Closed outline: the last sample repeats the first one.
+    let is_closed =
+        points.len() > 3 && distance(points[0], points[points.len() - 1]) <= DUPLICATE_EPSILON;
+    if is_closed {
+        points.pop();
+    }
+    if points.len() < 2 {
+        return vec![];
+    }
+
+    let mut breaks = corners(&points, corner_angle, is_closed);
+    // Breaks without corner, where the curve is split, but stays smooth.
+    let mut smooth_breaks: Vec<usize> = vec![];
+    if is_closed {
+        // Loop starts at its first corner. Without corners it is also split at the farthest sample.
+        let count = points.len();
+        let start = breaks.first().copied().unwrap_or(0);
+        points.rotate_left(start);
+        breaks = breaks
+            .iter()
+            .map(|index| (index + count - start) % count)
+            .filter(|index| *index != 0)
+            .collect();
+
+        if breaks.is_empty() {
+            let farthest = (1..count)
+                .max_by(|first, second| {
+                    distance(points[0], points[*first])
+                        .total_cmp(&distance(points[0], points[*second]))
+                })
+                .unwrap_or(count / 2);
+            breaks.push(farthest);
+            smooth_breaks.extend([0, farthest, count]);
+        }
+
+        points.push(points[0]);
+    }
+    breaks.insert(0, 0);
+    breaks.push(points.len() - 1);
+    breaks.dedup();
+
+    // Tangent at the smooth break is taken from samples on both sides of it.
+    let tangent = |index: usize| {
+        let last = points.len() - 1;
+        let (before, after) = if index == 0 || index == last {
+            (points[last - 1], points[1])
+        } else {
+            (points[index - 1], points[index + 1])
+        };
+        normalized(difference(after, before))
+    };
+
+    let mut result: Vec<BezierPoint> = vec![BezierPoint::control(points[0].x, points[0].y)];
+    for pair in breaks.windows(2) {
+        let piece = &points[pair[0]..=pair[1]];
+        let is_smooth = |index: usize| smooth_breaks.contains(&index);
+
+        let tangent_start = if is_smooth(pair[0]) {
+            tangent(pair[0])
+        } else {
+            start_tangent(piece)
+        };
+        let tangent_end = if is_smooth(pair[1]) {
+            tangent(pair[1])
+        } else {
+            end_tangent(piece)
+        };
+
+        let mut segments: Vec<FittedSegment> = vec![];
+        fit_piece(
+            piece,
+            tangent_start,
+            tangent_end,
+            tolerance,
+            0,
+            &mut segments,
+        );
+
+        for (index, fitted) in segments.iter().enumerate() {
+            // Joints inside the piece are smooth, if both segments keep the tangents.
+            let is_joint_smooth = if index == 0 {
+                pair[0] != 0 && is_smooth(pair[0])
+            } else {
+                fitted.is_tangent && segments[index - 1].is_tangent
+            };
+            if is_joint_smooth {
+                if let Some(last) = result.last_mut() {
+                    last.smoothness = SmoothnessType::Smooth;
+                }
+            }
+
+            let segment = fitted.segment;
+            result.push(BezierPoint::defining(segment.middle.x, segment.middle.y));
+            result.push(BezierPoint::control(segment.end.x, segment.end.y));
+        }
+    }
+
+    result
+}
+
+// Samples, where the stroke turns sharper than the angle. Only the sharpest sample of the window.
+// Samples of the closed outline are taken cyclically, so its first sample can be a corner too.
+fn corners(points: &[Point], corner_angle: f32, is_closed: bool) -> Vec<usize> {
+    let count = points.len();
+    // Sample at the signed offset from the index, clamped to ends for the open stroke.
+    let at = |index: usize, offset: isize| {
+        if is_closed {
+            (index as isize + offset).rem_euclid(count as isize) as usize
+        } else {
+            (index as isize + offset).clamp(0, count as isize - 1) as usize
+        }
+    };
+    let window = WINDOW_SAMPLES as isize;
+
+    let turning = |index: usize| {
+        let before = points[at(index, -window)];
+        let after = points[at(index, window)];
+
+        let incoming = difference(points[index], before);
+        let outgoing = difference(after, points[index]);
+        f32::atan2(cross(incoming, outgoing), dot(incoming, outgoing)).abs()
+    };
+
+    let angles: Vec<f32> = (0..count).map(turning).collect();
+
+    let candidates = if is_closed {
+        0..count
+    } else {
+        1..count.saturating_sub(1)
+    };
+    candidates
+        .filter(|index| {
+            angles[*index] > corner_angle
+                && (-window..=window).all(|offset| {
+                    let other = at(*index, offset);
+                    other == *index
+                        || angles[other] < angles[*index]
+                        || (angles[other] == angles[*index] && offset > 0)
+                })
+        })
+        .collect()
+}
+
+fn fit_piece(
+    points: &[Point], tangent_start: Point, tangent_end: Point, tolerance: f32, depth: u32,
+    segments: &mut Vec<FittedSegment>,
+) {
+    let start = points[0];
+    let end = points[points.len() - 1];
+
+    // Straight piece is fitted by its chord, also the last resort for two samples.
+    // Piece, returning to its start, has no chord and is never straight.
+    let chord = normalized(difference(end, start));
+    let is_straight = distance(start, end) > DUPLICATE_EPSILON
+        && [tangent_start, tangent_end]
+            .iter()
+            .all(|tangent| cross(chord, *tangent).abs() <= STRAIGHT_SINE)
+        && points
+            .iter()
+            .all(|point| cross(chord, difference(*point, start)).abs() <= tolerance);
+
+    if points.len() == 2 || is_straight {
+        segments.push(FittedSegment {
+            segment: Segment {
+                start,
+                middle: lerp(start, end, 0.5),
+                end,
+            },
+            is_tangent: is_straight,
+        });
+        return;
+    }
+
+    let mut parameters = chord_length_parameters(points);
+
+    // Defining point on both tangents exists only for arcs without inflection.
+    let Some(middle) = tangent_intersection(start, tangent_start, end, tangent_end) else {
+        if points.len() > 3 && depth < MAX_DEPTH {
+            split_piece(
+                points,
+                points.len() / 2,
+                tangent_start,
+                tangent_end,
+                tolerance,
+                depth,
+                segments,
+            );
+        } else {
+            let segment = least_squares(points, &parameters);
+            segments.push(FittedSegment {
+                segment,
+                is_tangent: false,
+            });
+        }
+        return;
+    };
+    let segment = Segment { start, middle, end };
+
+    let (mut error, mut split) = max_error(points, &segment, &parameters);
+    if error <= tolerance * REPARAMETERIZATION_FACTOR {
+        for _ in 0..MAX_REPARAMETERIZATIONS {
+            if error <= tolerance {
+                break;
+            }
+
+            parameters = reparameterize(points, &segment, &parameters);
+            (error, split) = max_error(points, &segment, &parameters);
+        }
+    }
+
+    if error <= tolerance || depth >= MAX_DEPTH {
+        segments.push(FittedSegment {
+            segment,
+            is_tangent: true,
+        });
+        return;
+    }
+
+    split_piece(
+        points,
+        split,
+        tangent_start,
+        tangent_end,
+        tolerance,
+        depth,
+        segments,
+    );
+}
+
+fn split_piece(
+    points: &[Point], split: usize, tangent_start: Point, tangent_end: Point, tolerance: f32,
+    depth: u32, segments: &mut Vec<FittedSegment>,
+) {
+    let split = split.clamp(1, points.len() - 2);
+    let tangent = normalized(difference(points[split + 1], points[split - 1]));
+
+    fit_piece(
+        &points[..=split],
+        tangent_start,
+        tangent,
+        tolerance,
+        depth + 1,
+        segments,
+    );
+    fit_piece(
+        &points[split..],
+        tangent,
+        tangent_end,
+        tolerance,
+        depth + 1,
+        segments,
+    );
+}
+
+// Direction of the curve at the start of piece.
+fn start_tangent(points: &[Point]) -> Point {
+    let index = usize::min(WINDOW_SAMPLES, points.len() - 1);
+    normalized(difference(points[index], points[0]))
+}
+
+// Direction of the curve at the end of piece.
+fn end_tangent(points: &[Point]) -> Point {
+    let last = points.len() - 1;
+    normalized(difference(
+        points[last],
+        points[last.saturating_sub(WINDOW_SAMPLES)],
+    ))
+}
+
+// Intersection of the ray from start along the tangent and the ray from end against its tangent.
+fn tangent_intersection(
+    start: Point, tangent_start: Point, end: Point, tangent_end: Point,
+) -> Option<Point> {
+    let denominator = cross(tangent_start, tangent_end);
+    if denominator.abs() < f32::EPSILON {
+        return None;
+    }
+
+    let chord = difference(end, start);
+    let length_start = cross(chord, tangent_end) / denominator;
+    let length_end = cross(tangent_start, chord) / denominator;
+    if length_start <= 0.0 || length_end <= 0.0 {
+        return None;
+    }
+
+    Some(Point::new(
+        start.x + tangent_start.x * length_start,
+        start.y + tangent_start.y * length_start,
+    ))
+}
+
+// Defining point, minimizing the sum of squared distances at the given parameters.
+fn least_squares(points: &[Point], parameters: &[f32]) -> Segment {
+    let start = points[0];
+    let end = points[points.len() - 1];
+
+    let (mut numerator, mut denominator) = (Point::new(0.0, 0.0), 0.0);
+    for (point, t) in points.iter().zip(parameters) {
+        let basis = 2.0 * t * (1.0 - t);
+        let rest = Point::new(
+            point.x - (1.0 - t).powf(2.0) * start.x - t.powf(2.0) * end.x,
+            point.y - (1.0 - t).powf(2.0) * start.y - t.powf(2.0) * end.y,
+        );
+
+        numerator.x += basis * rest.x;
+        numerator.y += basis * rest.y;
+        denominator += basis * basis;
+    }
+
+    let middle = if denominator > f32::EPSILON {
+        Point::new(numerator.x / denominator, numerator.y / denominator)
+    } else {
+        lerp(start, end, 0.5)
+    };
+
+    Segment { start, middle, end }
+}
+
+fn chord_length_parameters(points: &[Point]) -> Vec<f32> {
+    let mut parameters = vec![0.0];
+    for pair in points.windows(2) {
+        let last = parameters[parameters.len() - 1];
+        parameters.push(last + distance(pair[0], pair[1]));
+    }
+
+    let total = parameters[parameters.len() - 1];
+    parameters.iter().map(|length| length / total).collect()
+}
+
+// Maximum distance and index of the sample, where it is reached.
+fn max_error(points: &[Point], segment: &Segment, parameters: &[f32]) -> (f32, usize) {
+    points
+        .iter()
+        .zip(parameters)
+        .enumerate()
+        .map(|(index, (point, t))| (distance(segment.point(*t), *point), index))
+        .fold((0.0, points.len() / 2), |max, current| {
+            if current.0 > max.0 {
+                current
+            } else {
+                max
+            }
+        })
+}
+
+// Newton step for the root of (B(t) - P) * B'(t) at every sample.
+fn reparameterize(points: &[Point], segment: &Segment, parameters: &[f32]) -> Vec<f32> {
+    let second = Point::new(
+        2.0 * (segment.start.x - 2.0 * segment.middle.x + segment.end.x),
+        2.0 * (segment.start.y - 2.0 * segment.middle.y + segment.end.y),
+    );
+
+    points
+        .iter()
+        .zip(parameters)
+        .map(|(point, t)| {
+            let offset = difference(segment.point(*t), *point);
+            let (dx, dy) = segment.derivative(*t);
+            let first = Point::new(dx, dy);
+
+            let numerator = dot(offset, first);
+            let denominator = dot(first, first) + dot(offset, second);
+            if denominator.abs() < f32::EPSILON {
+                *t
+            } else {
+                (t - numerator / denominator).clamp(0.0, 1.0)
+            }
+        })
+        .collect()
+}
+
+fn difference(first: Point, second: Point) -> Point {
+    Point::new(first.x - second.x, first.y - second.y)
+}
+
+fn dot(first: Point, second: Point) -> f32 {
+    first.x * second.x + first.y * second.y
+}
+
+fn normalized(vector: Point) -> Point {
+    let length = f32::sqrt(dot(vector, vector));
+    if length < f32::EPSILON {
+        return vector;
+    }
+
+    Point::new(vector.x / length, vector.y / length)
+}
@@ -2,11 +2,14 @@ use crate::geometry::line::Line;
 use crate::geometry::point::Point;
 use crate::graphics::screen::ScreenParams;
 use crate::models::bezier_curve;
-use crate::models::bezier_curve::bezier_curve;
-use crate::models::bezier_point::{BezierPoint, SmoothnessType};
+use crate::models::bezier_curve::{bezier_curve, Continuity};
+use crate::models::bezier_point::{BezierPoint, BezierPointType, SmoothnessType, TangentDirection};
+use crate::models::projection;
+use crate::models::projection::Projection;
+use crate::models::segment::Segment;
 use crate::traits::positionable::Positionable;
 use crate::ui::styles::{colors, strokes};
-use egui::{Color32, Stroke};
+use egui::{Color32, Id, Stroke};
 
 pub struct Model {
     pub points: Vec<BezierPoint>,
@@ -14,16 +17,43 @@ pub struct Model {
     pub fill_control: Color32,
     pub fill_defining: Color32,
     pub fill_smooth: Color32,
+    pub fill_curvature: Color32,
+    pub fill_selected: Color32,
 
     pub skeleton_stroke: Stroke,
     pub model_stroke: Stroke,
     pub tangent_stroke: Stroke,
+    pub comb_stroke: Stroke,
+    pub continuity_color: Color32,
+    pub projection_color: Color32,
 
     pub bezier_step: f32,
 
+    pub comb_scale: f32,
+    pub comb_samples: usize,
+    pub continuity_tolerance: f32,
+    // Maximum distance in pixels from the pointer to the curve for hover and snapping.
+    pub snap_radius: f32,
+
+    // Position on the curve, selected by click.
+    pub selected: Option<Projection>,
+
+    // Linked points are mirrored across this axis.
+    pub mirror_axis: MirrorAxis,
+    pub mirror_position: f32,
+    pub mirror_stroke: Stroke,
+    // Point, chosen in the context menu to be mirrored by the next linked one.
+    pub mirror_source: Option<Id>,
+    // Points, chosen in the context menu to be mirrored together.
+    pub mirror_selection: Vec<Id>,
+
     pub are_tooltips_enabled: bool,
     pub is_skeleton_enabled: bool,
     pub is_tangent_enabled: bool,
+    pub is_comb_enabled: bool,
+    pub is_continuity_enabled: bool,
+    pub is_projection_enabled: bool,
+    pub is_snapping_enabled: bool,
 
     pub radius: f32,
 }
@@ -36,16 +66,38 @@ impl Default for Model {
             fill_control: colors::RED,
             fill_defining: colors::GREEN,
             fill_smooth: colors::PEONY,
+            fill_curvature: colors::ORANGE,
+            fill_selected: colors::YELLOW,
 
             skeleton_stroke: strokes::skeleton_dark_grey(0.05),
             tangent_stroke: strokes::tangent_pink(0.1),
+            comb_stroke: strokes::comb_blue(0.02),
+            continuity_color: colors::BLUE,
+            projection_color: colors::PINK,
 
             model_stroke: strokes::model_black(0.1),
             bezier_step: 0.1,
 
+            comb_scale: 0.5,
+            comb_samples: 20,
+            continuity_tolerance: 0.01,
+            snap_radius: 10.0,
+
+            selected: None,
+
+            mirror_axis: MirrorAxis::Vertical,
+            mirror_position: 0.0,
+            mirror_stroke: strokes::mirror_axis_red(0.03),
+            mirror_source: None,
+            mirror_selection: vec![],
+
             are_tooltips_enabled: false,
             is_skeleton_enabled: false,
             is_tangent_enabled: false,
+            is_comb_enabled: false,
+            is_continuity_enabled: false,
+            is_projection_enabled: false,
+            is_snapping_enabled: false,
 
             radius: 0.1,
         }
@@ -97,6 +149,78 @@ impl Model {
         vec
     }
 
+    pub fn segments(&self) -> Vec<Segment> {
+        (0..self.points.len().saturating_sub(2))
+            .step_by(2)
+            .map(|i| {
+                Segment::new(
+                    &self.points[i].point,
+                    &self.points[i + 1].point,
+                    &self.points[i + 2].point,
+                )
+            })
+            .collect()
+    }
+
+    // Lines of the segment, selected by click.
+    pub fn selected_lines(&self, screen_params: ScreenParams) -> Vec<Line<Point>> {
+        let Some(segment) = self
+            .selected
+            .and_then(|selected| self.segments().get(selected.segment).copied())
+        else {
+            return vec![];
+        };
+
+        let mut stroke = self.model_stroke;
+        stroke.width = screen_params.value_cm_to_px(2.0 * self.model_stroke.width);
+        stroke.color = self.projection_color;
+
+        let mut vec: Vec<Line<Point>> = vec![];
+        bezier_curve(
+            &mut vec,
+            &stroke,
+            self.bezier_step,
+            &segment.start,
+            &segment.end,
+            &segment.middle,
+        );
+
+        vec
+    }
+
+    // The closest point of the curve, flattened with the same step as for drawing.
+    pub fn project(&self, point: Point) -> Option<Projection> {
+        projection::project(&self.segments(), self.bezier_step, point)
+    }
+
+    // De Casteljau split of the segment: defining point is replaced by two new ones and the
+    // control point between them. The shape of the curve is not changed.
+    pub fn split_segment(&mut self, segment: usize, t: f32) {
+        let Some(original) = self.segments().get(segment).copied() else {
+            return;
+        };
+        if t <= 0.0 || t >= 1.0 {
+            return;
+        }
+
+        let (left, right) = original.split(t);
+
+        // Split keeps the tangent, so new joint is smooth.
+        let mut control = BezierPoint::control(left.end.x, left.end.y);
+        control.smoothness = SmoothnessType::Smooth;
+
+        let index = 2 * segment + 1;
+        self.points.splice(
+            index..=index,
+            [
+                BezierPoint::defining(left.middle.x, left.middle.y),
+                control,
+                BezierPoint::defining(right.middle.x, right.middle.y),
+            ],
+        );
+        self.selected = None;
+    }
+
     pub fn default_points() -> Vec<BezierPoint> {
         vec![
             BezierPoint::control(-5.691, -0.951),  // 1
@@ -233,33 +357,358 @@ impl Model {
 
             for control_point_index in [index - 1, index, index + 1] {
                 if control_point_index != 0 && control_point_index != self.points.len() - 1 {
-                    if let SmoothnessType::Smooth = self.points[control_point_index].smoothness {
-                        let (left, right) = self.points.split_at_mut(control_point_index);
-                        let defining_first = &mut left[left.len() - 1].point;
-
-                        let (mid, right) = right.split_at_mut(1);
-
-                        let control = &mut mid[0].point;
-                        let defining_second = &mut right[0].point;
-
+                    let smoothness = self.points[control_point_index].smoothness;
+                    if let SmoothnessType::Smooth | SmoothnessType::Curvature = smoothness {
                         let change = if control_point_index == index - 1 {
                             1
                         } else {
                             2
                         };
 
-                        bezier_curve::c1_continuity(
-                            defining_first,
-                            control,
-                            defining_second,
-                            change as u32,
-                        );
+                        self.update_joint(control_point_index, change);
+                    }
+                }
+            }
+
+            // Curvature also depends on the far ends of both segments.
+            for (control_point_index, change) in [(index.checked_sub(2), 1), (Some(index + 2), 2)] {
+                let Some(control_point_index) = control_point_index else {
+                    continue;
+                };
+                if control_point_index != 0 && control_point_index < self.points.len() - 1 {
+                    if let SmoothnessType::Curvature = self.points[control_point_index].smoothness {
+                        self.update_joint(control_point_index, change);
                     }
                 }
             }
         })
     }
 
+    // Keeps constraints of points after the drag of points with given indexes.
+    pub fn update_constraints(&mut self, updated_points_indexes: &[usize]) {
+        let locked = self.locked_points();
+
+        let mut indexes = updated_points_indexes.to_vec();
+        indexes.extend(self.update_mirrors(updated_points_indexes));
+
+        self.update_smoothness(&indexes);
+        self.update_handles(&indexes);
+
+        self.restore_points(&locked);
+    }
+
+    // Constraints of all points, mirrored points follow their sources.
+    pub fn apply_constraints(&mut self) {
+        let locked = self.locked_points();
+
+        self.update_mirrors(&[]);
+        let indexes: Vec<usize> = (0..self.points.len()).collect();
+        self.update_handles(&indexes);
+
+        self.restore_points(&locked);
+    }
+
+    pub fn clear_constraints(&mut self) {
+        self.points
+            .iter_mut()
+            .for_each(|bezier| bezier.constraint = Default::default());
+        self.mirror_source = None;
+    }
+
+    // Appends linked mirrored copies of the chain from the first to the last selected point.
+    // Copies go in reverse order, so the chain continues across the axis, as when closing a symmetric shape.
+    pub fn mirror_selected(&mut self) {
+        let indexes: Vec<usize> = self
+            .points
+            .iter()
+            .enumerate()
+            .filter(|(_, bezier)| self.mirror_selection.contains(&bezier.point.id))
+            .map(|(index, _)| index)
+            .collect();
+        let (Some(first), Some(last)) = (indexes.first(), indexes.last()) else {
+            return;
+        };
+
+        // Control points have even indexes, the mirrored part starts and ends with them.
+        let first = first - first % 2;
+        let last = last + last % 2;
+
+        let mut copies: Vec<BezierPoint> = self.points[first..=last]
+            .iter()
+            .rev()
+            .map(|source| {
+                let (x, y) = self.mirrored(source.point.x, source.point.y);
+                let mut copy = match source.kind {
+                    BezierPointType::Control => BezierPoint::control(x, y),
+                    BezierPointType::Defining => BezierPoint::defining(x, y),
+                };
+                copy.smoothness = source.smoothness;
+                copy.constraint.tangent = source.constraint.tangent;
+                copy.constraint.is_symmetric = source.constraint.is_symmetric;
+                copy.constraint.mirror_of = Some(source.point.id);
+
+                copy
+            })
+            .collect();
+
+        // Last point on the axis is its own mirror, otherwise it is joined to the copy by the straight segment.
+        let end = self.points[self.points.len() - 1].point;
+        let start = copies[0].point;
+        if (start.x - end.x).hypot(start.y - end.y) < f32::EPSILON {
+            copies.remove(0);
+        } else {
+            copies.insert(
+                0,
+                BezierPoint::defining((start.x + end.x) / 2.0, (start.y + end.y) / 2.0),
+            );
+        }
+
+        self.points.extend(copies);
+        self.mirror_selection.clear();
+        self.apply_constraints();
+    }
+
+    pub fn mirror_links(&self) -> usize {
+        self.points
+            .iter()
+            .filter(|bezier| bezier.constraint.mirror_of.is_some())
+            .count()
+    }
+
+    pub fn mirror_axis_lines(&self, screen_params: ScreenParams) -> Vec<Line<Point>> {
+        let mut stroke = self.mirror_stroke;
+        stroke.width = screen_params.value_cm_to_px(self.mirror_stroke.width);
+
+        // Long enough to cross the visible part of the canvas.
+        let length = 1000.0;
+        let (start, end) = match self.mirror_axis {
+            MirrorAxis::Vertical => (
+                Point::new(self.mirror_position, -length),
+                Point::new(self.mirror_position, length),
+            ),
+            MirrorAxis::Horizontal => (
+                Point::new(-length, self.mirror_position),
+                Point::new(length, self.mirror_position),
+            ),
+        };
+
+        vec![Line::new(start, end, stroke)]
+    }
+
+    fn locked_points(&self) -> Vec<(usize, f32, f32)> {
+        self.points
+            .iter()
+            .enumerate()
+            .filter(|(_, bezier)| bezier.constraint.is_locked)
+            .map(|(index, bezier)| (index, bezier.point.x, bezier.point.y))
+            .collect()
+    }
+
+    fn restore_points(&mut self, points: &[(usize, f32, f32)]) {
+        for (index, x, y) in points {
+            if let Some(bezier) = self.points.get_mut(*index) {
+                bezier.point.x = *x;
+                bezier.point.y = *y;
+            }
+        }
+    }
+
+    // Moves mirrored points. Source follows the target, if only target was updated.
+    // Returns indexes of moved points.
+    fn update_mirrors(&mut self, updated_points_indexes: &[usize]) -> Vec<usize> {
+        let mut moved: Vec<usize> = vec![];
+
+        for target in 0..self.points.len() {
+            let Some(source_id) = self.points[target].constraint.mirror_of else {
+                continue;
+            };
+            let Some(source) = self
+                .points
+                .iter()
+                .position(|bezier| bezier.point.id == source_id)
+            else {
+                continue;
+            };
+
+            let (from, to) = if updated_points_indexes.contains(&target)
+                && !updated_points_indexes.contains(&source)
+            {
+                (target, source)
+            } else {
+                (source, target)
+            };
+
+            let from = self.points[from].point;
+            let (x, y) = self.mirrored(from.x, from.y);
+            self.points[to].point.x = x;
+            self.points[to].point.y = y;
+
+            moved.push(to);
+        }
+
+        moved
+    }
+
+    fn mirrored(&self, x: f32, y: f32) -> (f32, f32) {
+        match self.mirror_axis {
+            MirrorAxis::Vertical => (2.0 * self.mirror_position - x, y),
+            MirrorAxis::Horizontal => (x, 2.0 * self.mirror_position - y),
+        }
+    }
+
+    // Tangent direction and symmetric handles of control points near updated ones.
+    fn update_handles(&mut self, updated_points_indexes: &[usize]) {
+        updated_points_indexes.iter().for_each(|index| {
+            let index = *index;
+
+            // Change is 1 for the control point before the updated one, 2 otherwise.
+            let neighbours = [
+                (index.checked_sub(1), 1),
+                (Some(index), 2),
+                (Some(index + 1), 2),
+            ];
+            for (control_point_index, change) in neighbours {
+                let Some(control_point_index) = control_point_index else {
+                    continue;
+                };
+                let Some(control) = self.points.get(control_point_index) else {
+                    continue;
+                };
+                if control.kind != BezierPointType::Control {
+                    continue;
+                }
+
+                self.update_handle(control_point_index, change);
+            }
+        })
+    }
+
+    // Change - 1st or 2nd defining point, that is adjusted. The other one is kept.
+    fn update_handle(&mut self, control_point_index: usize, change: u32) {
+        let control = self.points[control_point_index];
+        let constraint = control.constraint;
+        if constraint.tangent == TangentDirection::Free && !constraint.is_symmetric {
+            return;
+        }
+
+        // Handles are vectors from the control point to defining points.
+        let handle = |index: Option<usize>| {
+            index
+                .and_then(|index| self.points.get(index))
+                .map(|bezier| {
+                    (
+                        bezier.point.x - control.point.x,
+                        bezier.point.y - control.point.y,
+                    )
+                })
+        };
+        let first_index = control_point_index.checked_sub(1);
+        let second_index = Some(control_point_index + 1);
+        let mut first = handle(first_index);
+        let mut second = handle(second_index);
+
+        for handle in [&mut first, &mut second].into_iter().flatten() {
+            match constraint.tangent {
+                TangentDirection::Free => {},
+                TangentDirection::Horizontal => handle.1 = 0.0,
+                TangentDirection::Vertical => handle.0 = 0.0,
+            }
+        }
+
+        if let (Some(first), Some(second)) = (&mut first, &mut second) {
+            let (kept, adjusted) = if change == 1 {
+                (*second, first)
+            } else {
+                (*first, second)
+            };
+
+            let kept_length = f32::sqrt(kept.0 * kept.0 + kept.1 * kept.1);
+            let adjusted_length = f32::sqrt(adjusted.0 * adjusted.0 + adjusted.1 * adjusted.1);
+
+            let is_smooth = matches!(
+                control.smoothness,
+                SmoothnessType::Smooth | SmoothnessType::Curvature
+            );
+            let length = if constraint.is_symmetric {
+                kept_length
+            } else {
+                adjusted_length
+            };
+
+            // Direction is opposite to the kept handle for smooth joint or for degenerate handle.
+            if kept_length > f32::EPSILON && (is_smooth || adjusted_length < f32::EPSILON) {
+                *adjusted = (
+                    -kept.0 / kept_length * length,
+                    -kept.1 / kept_length * length,
+                );
+            } else if adjusted_length > f32::EPSILON {
+                *adjusted = (
+                    adjusted.0 / adjusted_length * length,
+                    adjusted.1 / adjusted_length * length,
+                );
+            }
+        }
+
+        for (index, handle) in [(first_index, first), (second_index, second)] {
+            if let (Some(index), Some(handle)) = (index, handle) {
+                self.points[index].point.x = control.point.x + handle.0;
+                self.points[index].point.y = control.point.y + handle.1;
+            }
+        }
+    }
+
+    fn update_joint(&mut self, control_point_index: usize, change: u32) {
+        let smoothness = self.points[control_point_index].smoothness;
+
+        let (left, right) = self.points.split_at_mut(control_point_index);
+        let (left, defining_first) = left.split_at_mut(left.len() - 1);
+        let defining_first = &mut defining_first[0].point;
+
+        let (mid, right) = right.split_at_mut(1);
+
+        let control = &mut mid[0].point;
+        let (defining_second, right) = right.split_at_mut(1);
+        let defining_second = &mut defining_second[0].point;
+
+        match smoothness {
+            SmoothnessType::Smooth => {
+                bezier_curve::c1_continuity(defining_first, control, defining_second, change);
+            },
+            SmoothnessType::Curvature => {
+                let control_first = &left[left.len() - 1].point;
+                let control_second = &right[0].point;
+
+                bezier_curve::g2_continuity(
+                    control_first,
+                    defining_first,
+                    control,
+                    defining_second,
+                    control_second,
+                    change,
+                );
+            },
+            _ => {},
+        }
+    }
+
+    // Curvature on both sides of the joint, if point is an inner control point.
+    pub fn joint_curvature(&self, index: usize) -> Option<(f32, f32)> {
+        if index < 2
+            || index + 2 >= self.points.len()
+            || self.points[index].kind != BezierPointType::Control
+        {
+            return None;
+        }
+
+        Some(bezier_curve::joint_curvature(
+            &self.points[index - 2].point,
+            &self.points[index - 1].point,
+            &self.points[index].point,
+            &self.points[index + 1].point,
+            &self.points[index + 2].point,
+        ))
+    }
+
     pub fn tangent_lines(
         &self, updated_points_indexes: &[usize], screen_params: ScreenParams,
     ) -> Vec<Line<Point>> {
@@ -274,7 +723,9 @@ impl Model {
 
             for control_point_index in [index - 1, index, index + 1] {
                 if control_point_index != 0 && control_point_index != self.points.len() - 1 {
-                    if let SmoothnessType::Smooth = self.points[control_point_index].smoothness {
+                    if let SmoothnessType::Smooth | SmoothnessType::Curvature =
+                        self.points[control_point_index].smoothness
+                    {
                         let defining_first = self.points[control_point_index - 1];
                         let defining_second = self.points[control_point_index + 1];
 
@@ -295,4 +746,52 @@ impl Model {
 
         lines
     }
+
+    pub fn comb_lines(&self, screen_params: ScreenParams) -> Vec<Line<Point>> {
+        let mut stroke = self.comb_stroke;
+        stroke.width = screen_params.value_cm_to_px(self.comb_stroke.width);
+
+        let mut vec: Vec<Line<Point>> = vec![];
+
+        for i in (0..self.points.len().saturating_sub(2)).step_by(2) {
+            bezier_curve::curvature_comb(
+                &mut vec,
+                &stroke,
+                self.comb_samples,
+                self.comb_scale,
+                &self.points[i].point,
+                &self.points[i + 2].point,
+                &self.points[i + 1].point,
+            );
+        }
+
+        vec
+    }
+
+    // Continuity of every inner joint: index of the control point and its measured continuity.
+    pub fn joints_continuity(&self) -> Vec<(usize, Continuity)> {
+        (2..self.points.len().saturating_sub(2))
+            .step_by(2)
+            .map(|index| {
+                let continuity = bezier_curve::continuity(
+                    &self.points[index - 2].point,
+                    &self.points[index - 1].point,
+                    &self.points[index].point,
+                    &self.points[index + 1].point,
+                    &self.points[index + 2].point,
+                    self.continuity_tolerance,
+                );
+
+                (index, continuity)
+            })
+            .collect()
+    }
+}
+
+#[derive(PartialEq)]
+pub enum MirrorAxis {
+    // Mirroring across vertical line: x = position.
+    Vertical,
+    // Mirroring across horizontal line: y = position.
+    Horizontal,
 }
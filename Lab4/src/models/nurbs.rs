@@ -0,0 +1,321 @@
+use crate::geometry::line::Line;
+use crate::geometry::moveable_point::MoveablePoint;
+use crate::geometry::point::Point;
+use crate::graphics::screen::ScreenParams;
+use crate::traits::positionable::Positionable;
+use crate::ui::styles::{colors, strokes};
+use egui::{Color32, Response, Stroke};
+
+pub const MIN_DEGREE: usize = 1;
+pub const MAX_DEGREE: usize = 5;
+
+// Homogeneous point: (w * x, w * y, w).
+type Homogeneous = (f32, f32, f32);
+
+// Non-uniform rational B-spline. Knot vector has `points + degree + 1` values.
+pub struct Nurbs {
+    pub is_enabled: bool,
+    pub is_polygon_enabled: bool,
+
+    pub degree: usize,
+    pub knot_mode: KnotMode,
+    pub knots: Vec<f32>,
+    // Parameter of the knot, that will be inserted.
+    pub insertion_knot: f32,
+
+    pub points: Vec<MoveablePoint>,
+    pub weights: Vec<f32>,
+
+    // Lines per knot span.
+    pub samples: usize,
+
+    pub stroke: Stroke,
+    pub polygon_stroke: Stroke,
+    pub point_color: Color32,
+}
+
+impl Default for Nurbs {
+    fn default() -> Self {
+        let points: Vec<MoveablePoint> = [
+            (-4.0, 0.0),
+            (-3.0, 3.0),
+            (0.0, 4.0),
+            (3.0, 3.0),
+            (4.0, 0.0),
+            (3.0, -3.0),
+            (0.0, -4.0),
+        ]
+        .iter()
+        .map(|(x, y)| MoveablePoint::new(*x, *y))
+        .collect();
+
+        let mut nurbs = Self {
+            is_enabled: false,
+            is_polygon_enabled: true,
+
+            degree: 3,
+            knot_mode: KnotMode::Clamped,
+            knots: vec![],
+            insertion_knot: 0.5,
+
+            weights: vec![1.0; points.len()],
+            points,
+
+            samples: 20,
+
+            stroke: strokes::nurbs_purple(0.07),
+            polygon_stroke: strokes::skeleton_dark_grey(0.03),
+            point_color: colors::PURPLE,
+        };
+        nurbs.update_knots();
+
+        nurbs
+    }
+}
+
+impl Nurbs {
+    // Degree can not be bigger than number of points minus one.
+    pub fn effective_degree(&self) -> usize {
+        usize::min(self.degree, self.points.len().saturating_sub(1))
+    }
+
+    pub fn is_valid(&self) -> bool {
+        self.points.len() >= 2
+            && self.weights.len() == self.points.len()
+            && self.knots.len() == self.points.len() + self.effective_degree() + 1
+    }
+
+    // Parameter range, where the curve is defined: [u(p); u(n)].
+    pub fn domain(&self) -> (f32, f32) {
+        if !self.is_valid() {
+            return (0.0, 1.0);
+        }
+
+        let degree = self.effective_degree();
+        (self.knots[degree], self.knots[self.points.len()])
+    }
+
+    // Keeps weights and knots consistent with points, degree and knot mode.
+    pub fn update_knots(&mut self) {
+        self.weights.resize(self.points.len(), 1.0);
+
+        if self.points.len() < 2 {
+            self.knots.clear();
+            return;
+        }
+
+        let length = self.points.len() + self.effective_degree() + 1;
+        match self.knot_mode {
+            KnotMode::Uniform => self.knots = Self::uniform_knots(length),
+            KnotMode::Clamped => {
+                self.knots = Self::clamped_knots(length, self.effective_degree());
+            },
+            KnotMode::Custom => {
+                if self.knots.len() != length {
+                    self.knots = Self::clamped_knots(length, self.effective_degree());
+                }
+
+                // Knot vector must be non-decreasing.
+                for i in 1..self.knots.len() {
+                    self.knots[i] = f32::max(self.knots[i], self.knots[i - 1]);
+                }
+            },
+        }
+    }
+
+    fn uniform_knots(length: usize) -> Vec<f32> {
+        (0..length)
+            .map(|i| i as f32 / (length - 1) as f32)
+            .collect()
+    }
+
+    // First and last knots are repeated `degree + 1` times, so the curve passes end points.
+    fn clamped_knots(length: usize, degree: usize) -> Vec<f32> {
+        let inner = length - 2 * (degree + 1);
+
+        (0..length)
+            .map(|i| {
+                if i <= degree {
+                    0.0
+                } else if i >= length - degree - 1 {
+                    1.0
+                } else {
+                    (i - degree) as f32 / (inner + 1) as f32
+                }
+            })
+            .collect()
+    }
+
+    // Index `k` of knot span: u(k) <= t < u(k + 1), degree <= k < points.
+    fn span(&self, t: f32) -> usize {
+        let degree = self.effective_degree();
+        let last = self.points.len() - 1;
+
+        if t >= self.knots[last + 1] {
+            return last;
+        }
+
+        (degree..=last)
+            .find(|k| self.knots[*k] <= t && t < self.knots[k + 1])
+            .unwrap_or(degree)
+    }
+
+    fn homogeneous(&self, index: usize) -> Homogeneous {
+        let (point, weight) = (self.points[index], self.weights[index]);
+        (point.x * weight, point.y * weight, weight)
+    }
+
+    // De Boor's algorithm in homogeneous coordinates.
+    pub fn point(&self, t: f32) -> Point {
+        let degree = self.effective_degree();
+        let k = self.span(t);
+
+        let mut d: Vec<Homogeneous> = (0..=degree)
+            .map(|j| self.homogeneous(j + k - degree))
+            .collect();
+
+        for r in 1..=degree {
+            for j in (r..=degree).rev() {
+                let left = self.knots[j + k - degree];
+                let right = self.knots[j + 1 + k - r];
+
+                let alpha = if (right - left).abs() < f32::EPSILON {
+                    0.0
+                } else {
+                    (t - left) / (right - left)
+                };
+
+                d[j] = (
+                    (1.0 - alpha) * d[j - 1].0 + alpha * d[j].0,
+                    (1.0 - alpha) * d[j - 1].1 + alpha * d[j].1,
+                    (1.0 - alpha) * d[j - 1].2 + alpha * d[j].2,
+                );
+            }
+        }
+
+        let (x, y, w) = d[degree];
+        if w.abs() < f32::EPSILON {
+            return Point::new(x, y);
+        }
+
+        Point::new(x / w, y / w)
+    }
+
+    // Boehm's knot insertion: the curve is not changed, one control point is added.
+    pub fn insert_knot(&mut self, t: f32) {
+        if !self.is_valid() {
+            return;
+        }
+
+        let (start, end) = self.domain();
+        if t <= start || t >= end {
+            return;
+        }
+
+        let degree = self.effective_degree();
+        let k = self.span(t);
+
+        let mut points: Vec<MoveablePoint> = Vec::with_capacity(self.points.len() + 1);
+        let mut weights: Vec<f32> = Vec::with_capacity(self.points.len() + 1);
+
+        for i in 0..=self.points.len() {
+            let (x, y, w) = if i + degree <= k {
+                self.homogeneous(i)
+            } else if i > k {
+                self.homogeneous(i - 1)
+            } else {
+                let alpha = (t - self.knots[i]) / (self.knots[i + degree] - self.knots[i]);
+                let (previous, current) = (self.homogeneous(i - 1), self.homogeneous(i));
+
+                (
+                    (1.0 - alpha) * previous.0 + alpha * current.0,
+                    (1.0 - alpha) * previous.1 + alpha * current.1,
+                    (1.0 - alpha) * previous.2 + alpha * current.2,
+                )
+            };
+
+            points.push(MoveablePoint::new(x / w, y / w));
+            weights.push(w);
+        }
+
+        self.points = points;
+        self.weights = weights;
+        self.knots.insert(k + 1, t);
+
+        // Knot vector is not uniform anymore.
+        self.knot_mode = KnotMode::Custom;
+    }
+
+    pub fn remove_point(&mut self, index: usize) {
+        if index < self.points.len() {
+            self.points.remove(index);
+            self.weights.remove(index);
+        }
+    }
+
+    // Click on the free space of canvas adds new point at the end of the curve.
+    // Snapped position is used instead of the pointer, if it is given.
+    pub fn update_on_click(
+        &mut self, screen_params: ScreenParams, response: &Response, snap: Option<Point>,
+    ) {
+        if !response.clicked() {
+            return;
+        }
+
+        if let Some(position) = response.interact_pointer_pos() {
+            let point = match snap {
+                Some(snap) => MoveablePoint::new(snap.x, snap.y),
+                None => screen_params.point_px_to_cm(MoveablePoint::from_pos2(position)),
+            };
+            self.points.push(point);
+            self.weights.push(1.0);
+        }
+    }
+
+    pub fn lines(&self, screen_params: ScreenParams) -> Vec<Line<Point>> {
+        if !self.is_valid() {
+            return vec![];
+        }
+
+        let mut stroke = self.stroke;
+        stroke.width = screen_params.value_cm_to_px(self.stroke.width);
+
+        let (start, end) = self.domain();
+        let spans = self.points.len() - self.effective_degree();
+        let count = usize::max(self.samples * spans, 1);
+
+        let points: Vec<Point> = (0..=count)
+            .map(|i| self.point(start + (end - start) * i as f32 / count as f32))
+            .collect();
+
+        points
+            .windows(2)
+            .map(|pair| Line::new(pair[0], pair[1], stroke))
+            .collect()
+    }
+
+    pub fn polygon_lines(&self, screen_params: ScreenParams) -> Vec<Line<Point>> {
+        let mut stroke = self.polygon_stroke;
+        stroke.width = screen_params.value_cm_to_px(self.polygon_stroke.width);
+
+        self.points
+            .windows(2)
+            .map(|pair| {
+                let start = Point::new(pair[0].x, pair[0].y);
+                let end = Point::new(pair[1].x, pair[1].y);
+
+                Line::new(start, end, stroke)
+            })
+            .collect()
+    }
+}
+
+#[derive(PartialEq)]
+pub enum KnotMode {
+    // Knots are evenly spaced, curve does not pass through end points.
+    Uniform,
+    // Ends are repeated `degree + 1` times, inner knots are evenly spaced.
+    Clamped,
+    // Knots are edited by user.
+    Custom,
+}
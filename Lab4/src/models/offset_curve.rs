@@ -0,0 +1,438 @@
+use crate::geometry::line::Line;
+use crate::geometry::point::Point;
+use crate::graphics::screen::ScreenParams;
+use crate::models::bezier_curve::bezier_curve;
+use crate::models::bezier_point::BezierPoint;
+use crate::models::model::Model;
+use crate::models::segment::{cross, distance, lerp, Segment};
+use crate::traits::positionable::Positionable;
+use crate::ui::styles::{colors, strokes};
+use egui::{Color32, Stroke};
+use std::f32::consts::FRAC_PI_4;
+
+// Maximum depth of subdivision of one segment, while approximating its offset.
+const MAX_SUBDIVISION_DEPTH: u32 = 10;
+// Parameters, where approximation error is measured.
+const ERROR_PARAMETERS: [f32; 3] = [0.25, 0.5, 0.75];
+// Lines of approximating quadratic, used to measure the error.
+const ERROR_SAMPLES: usize = 16;
+// Samples of `1 - d * k(t)`, used to find cusps.
+const CUSP_SAMPLES: usize = 16;
+const BISECTION_ITERATIONS: u32 = 24;
+// Ends of pieces closer than this are considered joined.
+const JOIN_EPSILON: f32 = 1e-4;
+// Intersections closer to the shared end of neighbouring segments are ignored.
+const PARAMETER_EPSILON: f32 = 1e-3;
+
+// Inner and (or) outer contours at the given distance from the model.
+pub struct OffsetCurve {
+    pub is_enabled: bool,
+    pub is_closed: bool,
+
+    pub side: OffsetSide,
+    pub distance: f32,
+    pub tolerance: f32,
+
+    // Generated contours, they are edited like the model.
+    pub contours: Vec<Vec<BezierPoint>>,
+
+    pub stroke: Stroke,
+    pub point_color: Color32,
+}
+
+impl Default for OffsetCurve {
+    fn default() -> Self {
+        Self {
+            is_enabled: false,
+            is_closed: true,
+
+            side: OffsetSide::Outer,
+            distance: 0.5,
+            tolerance: 0.01,
+
+            contours: vec![],
+
+            stroke: strokes::offset_orange(0.05),
+            point_color: colors::ORANGE,
+        }
+    }
+}
+
+impl OffsetCurve {
+    pub fn generate(&mut self, model: &Model) {
+        let segments: Vec<Segment> = (0..model.points.len().saturating_sub(2))
+            .step_by(2)
+            .map(|i| {
+                Segment::new(
+                    &model.points[i].point,
+                    &model.points[i + 1].point,
+                    &model.points[i + 2].point,
+                )
+            })
+            .collect();
+
+        self.contours.clear();
+        if segments.is_empty() {
+            return;
+        }
+
+        // Interior of counter-clockwise contour is on the left side.
+        let area: f32 = segments.iter().map(signed_area).sum();
+        let outer = if area >= 0.0 { -1.0 } else { 1.0 };
+
+        let sides = match self.side {
+            OffsetSide::Outer => vec![outer],
+            OffsetSide::Inner => vec![-outer],
+            OffsetSide::Both => vec![outer, -outer],
+        };
+
+        for side in sides {
+            let chain = self.offset_chain(&segments, side * self.distance);
+            let chain = trim_loops(chain, self.is_closed);
+
+            // Inner offset bigger than the contour turns it inside out.
+            let offset_area: f32 = chain.iter().map(signed_area).sum();
+            if self.is_closed && offset_area * area < 0.0 {
+                log::warn!("Offset contour at distance {} collapsed.", self.distance);
+                continue;
+            }
+
+            if let Some(first) = chain.first() {
+                let mut points = vec![BezierPoint::control(first.start.x, first.start.y)];
+                for segment in &chain {
+                    points.push(BezierPoint::defining(segment.middle.x, segment.middle.y));
+                    points.push(BezierPoint::control(segment.end.x, segment.end.y));
+                }
+
+                self.contours.push(points);
+            }
+        }
+    }
+
+    pub fn lines(&self, screen_params: ScreenParams) -> Vec<Line<Point>> {
+        let mut stroke = self.stroke;
+        stroke.width = screen_params.value_cm_to_px(self.stroke.width);
+
+        let mut vec: Vec<Line<Point>> = vec![];
+
+        for points in &self.contours {
+            for i in (0..points.len().saturating_sub(2)).step_by(2) {
+                bezier_curve(
+                    &mut vec,
+                    &stroke,
+                    0.1,
+                    &points[i].point,
+                    &points[i + 2].point,
+                    &points[i + 1].point,
+                );
+            }
+        }
+
+        vec
+    }
+
+    // Offset of every segment, with round joins at convex corners.
+    fn offset_chain(&self, segments: &[Segment], offset: f32) -> Vec<Segment> {
+        let pieces: Vec<Vec<Segment>> = segments
+            .iter()
+            .map(|segment| {
+                let mut pieces: Vec<Segment> = vec![];
+                offset_segment(segment, offset, self.tolerance, 0, &mut pieces);
+                pieces
+            })
+            .collect();
+
+        let mut chain: Vec<Segment> = vec![];
+        for (i, current) in pieces.iter().enumerate() {
+            chain.extend(current);
+
+            let next_index = if i + 1 < pieces.len() {
+                i + 1
+            } else if self.is_closed {
+                0
+            } else {
+                continue;
+            };
+
+            if let (Some(end), Some(start)) = (current.last(), pieces[next_index].first()) {
+                chain.extend(join(
+                    segments[next_index].start,
+                    end.end,
+                    start.start,
+                    offset,
+                ));
+            }
+        }
+
+        chain
+    }
+}
+
+#[derive(PartialEq)]
+pub enum OffsetSide {
+    Outer,
+    Inner,
+    // Both sides, like outline of the stroke.
+    Both,
+}
+
+// Closed form of 1/2 * integral(x dy - y dx) over quadratic segment.
+fn signed_area(segment: &Segment) -> f32 {
+    cross(segment.start, segment.middle) / 3.0
+        + cross(segment.start, segment.end) / 6.0
+        + cross(segment.middle, segment.end) / 3.0
+}
+
+fn normal(segment: &Segment, t: f32) -> Option<(f32, f32)> {
+    let (dx, dy) = segment.derivative(t);
+    let length = f32::sqrt(dx * dx + dy * dy);
+    if length < f32::EPSILON {
+        return None;
+    }
+
+    Some((-dy / length, dx / length))
+}
+
+// Exact offset point. Normal is to the left of the direction of the segment.
+fn offset_point(segment: &Segment, offset: f32, t: f32) -> Point {
+    let point = segment.point(t);
+    let (nx, ny) = normal(segment, t).unwrap_or((0.0, 0.0));
+
+    Point::new(point.x + nx * offset, point.y + ny * offset)
+}
+
+// Offset curve is B(t) + d * N(t), its derivative is B'(t) * (1 - d * k(t)).
+// Cusp is where this factor changes its sign.
+fn speed_factor(segment: &Segment, offset: f32, t: f32) -> f32 {
+    1.0 - offset * segment.curvature(t)
+}
+
+fn find_cusp(segment: &Segment, offset: f32) -> Option<f32> {
+    let step = 1.0 / CUSP_SAMPLES as f32;
+
+    (0..CUSP_SAMPLES).find_map(|i| {
+        let (mut low, mut high) = (i as f32 * step, (i + 1) as f32 * step);
+        let low_sign = speed_factor(segment, offset, low).signum();
+        if low_sign == speed_factor(segment, offset, high).signum() {
+            return None;
+        }
+
+        for _ in 0..BISECTION_ITERATIONS {
+            let middle = (low + high) / 2.0;
+            if speed_factor(segment, offset, middle).signum() == low_sign {
+                low = middle;
+            } else {
+                high = middle;
+            }
+        }
+
+        let t = (low + high) / 2.0;
+        (t > PARAMETER_EPSILON && t < 1.0 - PARAMETER_EPSILON).then_some(t)
+    })
+}
+
+fn offset_segment(
+    segment: &Segment, offset: f32, tolerance: f32, depth: u32, pieces: &mut Vec<Segment>,
+) {
+    if segment.length() < JOIN_EPSILON {
+        return;
+    }
+
+    // Offset is split at cusps, loops around them are trimmed later.
+    if depth < MAX_SUBDIVISION_DEPTH {
+        if let Some(t) = find_cusp(segment, offset) {
+            let (left, right) = segment.split(t);
+            offset_segment(&left, offset, tolerance, depth + 1, pieces);
+            offset_segment(&right, offset, tolerance, depth + 1, pieces);
+            return;
+        }
+    }
+
+    let approximation = approximate(segment, offset);
+
+    let polyline: Vec<Point> = (0..=ERROR_SAMPLES)
+        .map(|i| approximation.point(i as f32 / ERROR_SAMPLES as f32))
+        .collect();
+    let error = ERROR_PARAMETERS
+        .iter()
+        .map(|t| polyline_distance(&polyline, offset_point(segment, offset, *t)))
+        .fold(0.0, f32::max);
+
+    if error > tolerance && depth < MAX_SUBDIVISION_DEPTH {
+        let (left, right) = segment.split(0.5);
+        offset_segment(&left, offset, tolerance, depth + 1, pieces);
+        offset_segment(&right, offset, tolerance, depth + 1, pieces);
+    } else {
+        pieces.push(approximation);
+    }
+}
+
+// Quadratic with offset ends, its middle point is the intersection of end tangents.
+// If tangents do not intersect in front of both ends, quadratic passes through offset of the middle.
+fn approximate(segment: &Segment, offset: f32) -> Segment {
+    let start = offset_point(segment, offset, 0.0);
+    let end = offset_point(segment, offset, 1.0);
+
+    let start_direction = segment.derivative(0.0);
+    let end_direction = segment.derivative(1.0);
+    let between = (end.x - start.x, end.y - start.y);
+
+    let denominator = start_direction.0 * end_direction.1 - start_direction.1 * end_direction.0;
+    if denominator.abs() > f32::EPSILON {
+        let s = (between.0 * end_direction.1 - between.1 * end_direction.0) / denominator;
+        let u = (between.0 * start_direction.1 - between.1 * start_direction.0) / denominator;
+
+        let middle = Point::new(
+            start.x + start_direction.0 * s,
+            start.y + start_direction.1 * s,
+        );
+
+        // Nearly parallel tangents give too distant intersection.
+        let limit = 2.0 * distance(start, end);
+        let is_near = distance(middle, start) <= limit && distance(middle, end) <= limit;
+
+        if s > 0.0 && u > 0.0 && is_near {
+            return Segment { start, middle, end };
+        }
+    }
+
+    let center = offset_point(segment, offset, 0.5);
+    let middle = Point::new(
+        2.0 * center.x - (start.x + end.x) / 2.0,
+        2.0 * center.y - (start.y + end.y) / 2.0,
+    );
+
+    Segment { start, middle, end }
+}
+
+fn polyline_distance(polyline: &[Point], point: Point) -> f32 {
+    polyline
+        .windows(2)
+        .map(|pair| {
+            let (start, end) = (pair[0], pair[1]);
+            let length = distance(start, end).powf(2.0);
+            if length < f32::EPSILON {
+                return distance(start, point);
+            }
+
+            let t = ((point.x - start.x) * (end.x - start.x)
+                + (point.y - start.y) * (end.y - start.y))
+                / length;
+
+            distance(lerp(start, end, t.clamp(0.0, 1.0)), point)
+        })
+        .fold(f32::MAX, f32::min)
+}
+
+// Connects offsets of two neighbouring segments around their common point.
+// Convex corner gets circular arc, concave one gets straight line, which is trimmed later.
+fn join(corner: Point, from: Point, to: Point, offset: f32) -> Vec<Segment> {
+    if distance(from, to) < JOIN_EPSILON {
+        return vec![];
+    }
+
+    let first = Point::new(from.x - corner.x, from.y - corner.y);
+    let second = Point::new(to.x - corner.x, to.y - corner.y);
+    let angle = f32::atan2(
+        cross(first, second),
+        first.x * second.x + first.y * second.y,
+    );
+
+    // Arc goes around the corner in the same direction as the offset side.
+    let is_convex = angle * offset < 0.0;
+    if !is_convex {
+        return vec![Segment {
+            start: from,
+            middle: lerp(from, to, 0.5),
+            end: to,
+        }];
+    }
+
+    let count = (angle.abs() / FRAC_PI_4).ceil().max(1.0) as usize;
+    let step = angle / count as f32;
+    let radius = offset.abs();
+    let start_angle = f32::atan2(first.y, first.x);
+
+    let arc_point = |angle: f32, radius: f32| {
+        Point::new(
+            corner.x + radius * angle.cos(),
+            corner.y + radius * angle.sin(),
+        )
+    };
+
+    (0..count)
+        .map(|i| {
+            let angle = start_angle + step * i as f32;
+
+            Segment {
+                start: arc_point(angle, radius),
+                // Intersection of tangents at the ends of the arc piece.
+                middle: arc_point(angle + step / 2.0, radius / (step / 2.0).cos()),
+                end: arc_point(angle + step, radius),
+            }
+        })
+        .collect()
+}
+
+// Removes loops, which appear at concave corners and cusps. Every time the smallest loop is cut.
+fn trim_loops(mut chain: Vec<Segment>, is_closed: bool) -> Vec<Segment> {
+    for _ in 0..chain.len() {
+        let length = chain.len();
+        if length < 3 {
+            break;
+        }
+
+        // Pairs of segments are checked in order of the size of the loop between them.
+        let mut gaps: Vec<usize> = (1..length).collect();
+        if is_closed {
+            gaps.sort_by_key(|gap| usize::min(*gap, length - gap));
+        }
+
+        let hit = gaps.iter().find_map(|gap| {
+            (0..length - gap).find_map(|i| {
+                let j = i + gap;
+
+                chain[i]
+                    .intersections(&chain[j])
+                    .into_iter()
+                    .find(|(t_first, t_second)| {
+                        // Shared ends of neighbouring segments are not intersections.
+                        let is_adjacent = j == i + 1
+                            && *t_first > 1.0 - PARAMETER_EPSILON
+                            && *t_second < PARAMETER_EPSILON;
+                        let is_wrapped = is_closed
+                            && i == 0
+                            && j == length - 1
+                            && *t_first < PARAMETER_EPSILON
+                            && *t_second > 1.0 - PARAMETER_EPSILON;
+
+                        !is_adjacent && !is_wrapped
+                    })
+                    .map(|(t_first, t_second)| (i, j, t_first, t_second))
+            })
+        });
+
+        let Some((i, j, t_first, t_second)) = hit else {
+            break;
+        };
+
+        let (first_left, first_right) = chain[i].split(t_first);
+        let (second_left, second_right) = chain[j].split(t_second);
+
+        chain = if is_closed && j - i > length - (j - i) {
+            // Loop goes through the end of the chain, so the part between is kept.
+            let mut kept = vec![first_right];
+            kept.extend_from_slice(&chain[i + 1..j]);
+            kept.push(second_left);
+            kept
+        } else {
+            let mut kept = chain[..i].to_vec();
+            kept.push(first_left);
+            kept.push(second_right);
+            kept.extend_from_slice(&chain[j + 1..]);
+            kept
+        };
+    }
+
+    chain
+}
@@ -0,0 +1,124 @@
+use crate::geometry::point::Point;
+use crate::math::angle::Angle;
+use crate::models::segment::{distance, lerp, Segment};
+
+const NEWTON_ITERATIONS: u32 = 8;
+
+// The closest point of the contour to some point.
+#[derive(Debug, Clone, Copy)]
+pub struct Projection {
+    pub segment: usize,
+    pub t: f32,
+
+    pub point: Point,
+    pub distance: f32,
+
+    // Angle of the tangent in degrees.
+    pub angle: f32,
+    pub curvature: f32,
+}
+
+impl Projection {
+    pub fn details(&self) -> String {
+        format!(
+            "Segment: {}\nParameter t: {:.4}\nTangent Angle: {:.2}°\nCurvature: {:.4}",
+            self.segment + 1,
+            self.t,
+            self.angle,
+            self.curvature,
+        )
+    }
+}
+
+// Every segment is flattened with the given step, the closest line gives the initial parameter,
+// which is refined by Newton iteration.
+pub fn project(segments: &[Segment], step: f32, point: Point) -> Option<Projection> {
+    segments
+        .iter()
+        .enumerate()
+        .map(|(index, segment)| {
+            // Newton may go to the farthest point, then the initial parameter is better.
+            let initial = seed(segment, step, point);
+            let refined = newton(segment, point, initial);
+            let t = if distance(segment.point(refined), point)
+                <= distance(segment.point(initial), point)
+            {
+                refined
+            } else {
+                initial
+            };
+
+            let closest = segment.point(t);
+            let (dx, dy) = segment.derivative(t);
+
+            Projection {
+                segment: index,
+                t,
+                point: closest,
+                distance: distance(closest, point),
+                angle: Angle::from_radian(f32::atan2(dy, dx)).degree(),
+                curvature: segment.curvature(t),
+            }
+        })
+        .min_by(|first, second| first.distance.total_cmp(&second.distance))
+}
+
+fn seed(segment: &Segment, step: f32, point: Point) -> f32 {
+    let count = usize::max((1.0 / step).ceil() as usize, 1);
+    let samples: Vec<(f32, Point)> = (0..=count)
+        .map(|i| {
+            let t = f32::min(i as f32 / count as f32, 1.0);
+            (t, segment.point(t))
+        })
+        .collect();
+
+    samples
+        .windows(2)
+        .map(|pair| {
+            let ((t_start, start), (t_end, end)) = (pair[0], pair[1]);
+
+            let length = distance(start, end).powf(2.0);
+            let fraction = if length < f32::EPSILON {
+                0.0
+            } else {
+                (((point.x - start.x) * (end.x - start.x)
+                    + (point.y - start.y) * (end.y - start.y))
+                    / length)
+                    .clamp(0.0, 1.0)
+            };
+
+            let closest = lerp(start, end, fraction);
+            (
+                t_start + (t_end - t_start) * fraction,
+                distance(closest, point),
+            )
+        })
+        .min_by(|first, second| first.1.total_cmp(&second.1))
+        .map_or(0.0, |(t, _)| t)
+}
+
+// Minimizes f(t) = |B(t) - P|^2 / 2: t -= f'(t) / f''(t),
+// f' = (B - P) * B', f'' = B' * B' + (B - P) * B''.
+fn newton(segment: &Segment, point: Point, mut t: f32) -> f32 {
+    let second = (
+        2.0 * (segment.start.x - 2.0 * segment.middle.x + segment.end.x),
+        2.0 * (segment.start.y - 2.0 * segment.middle.y + segment.end.y),
+    );
+
+    for _ in 0..NEWTON_ITERATIONS {
+        let current = segment.point(t);
+        let (dx, dy) = segment.derivative(t);
+        let difference = (current.x - point.x, current.y - point.y);
+
+        let first_derivative = difference.0 * dx + difference.1 * dy;
+        let second_derivative =
+            dx * dx + dy * dy + difference.0 * second.0 + difference.1 * second.1;
+        if second_derivative.abs() < f32::EPSILON {
+            break;
+        }
+
+        t = (t - first_derivative / second_derivative).clamp(0.0, 1.0);
+    }
+
+    t
+}
@@ -0,0 +1,248 @@
+use crate::geometry::point::Point;
+use crate::traits::positionable::Positionable;
+
+// Gauss-Legendre nodes and weights on [-1; 1].
+const GAUSS_NODES: [f32; 5] = [0.0, -0.538_469_3, 0.538_469_3, -0.906_179_8, 0.906_179_8];
+const GAUSS_WEIGHTS: [f32; 5] = [
+    0.568_888_9,
+    0.478_628_67,
+    0.478_628_67,
+    0.236_926_88,
+    0.236_926_88,
+];
+
+// Intersection search parameters.
+const INTERSECTION_FLATNESS: f32 = 1e-4;
+const INTERSECTION_MAX_DEPTH: u32 = 24;
+
+// Quadratic Bezier segment: `start` and `end` lie on the curve, `middle` defines its shape.
+#[derive(Debug, Clone, Copy)]
+pub struct Segment {
+    pub start: Point,
+    pub middle: Point,
+    pub end: Point,
+}
+
+impl Segment {
+    pub fn new<T: Positionable>(control_first: &T, defining: &T, control_second: &T) -> Self {
+        Self {
+            start: Point::new(control_first.x(), control_first.y()),
+            middle: Point::new(defining.x(), defining.y()),
+            end: Point::new(control_second.x(), control_second.y()),
+        }
+    }
+
+    pub fn point(&self, t: f32) -> Point {
+        let x = self.start.x * (1.0 - t).powf(2.0)
+            + 2.0 * self.middle.x * t * (1.0 - t)
+            + self.end.x * t.powf(2.0);
+        let y = self.start.y * (1.0 - t).powf(2.0)
+            + 2.0 * self.middle.y * t * (1.0 - t)
+            + self.end.y * t.powf(2.0);
+
+        Point::new(x, y)
+    }
+
+    pub fn derivative(&self, t: f32) -> (f32, f32) {
+        let x = 2.0 * (1.0 - t) * (self.middle.x - self.start.x)
+            + 2.0 * t * (self.end.x - self.middle.x);
+        let y = 2.0 * (1.0 - t) * (self.middle.y - self.start.y)
+            + 2.0 * t * (self.end.y - self.middle.y);
+
+        (x, y)
+    }
+
+    // Maximum distance between segment and its chord: |P0 - 2P1 + P2| / 4.
+    pub fn deviation(&self) -> f32 {
+        0.25 * f32::sqrt(
+            (self.start.x - 2.0 * self.middle.x + self.end.x).powf(2.0)
+                + (self.start.y - 2.0 * self.middle.y + self.end.y).powf(2.0),
+        )
+    }
+
+    // Signed curvature: k = (B' x B'') / |B'|^3
+    pub fn curvature(&self, t: f32) -> f32 {
+        let (dx, dy) = self.derivative(t);
+        let ddx = 2.0 * (self.start.x - 2.0 * self.middle.x + self.end.x);
+        let ddy = 2.0 * (self.start.y - 2.0 * self.middle.y + self.end.y);
+
+        let length = f32::sqrt(dx * dx + dy * dy);
+        if length < f32::EPSILON {
+            return 0.0;
+        }
+
+        (dx * ddy - dy * ddx) / length.powf(3.0)
+    }
+
+    // De Casteljau split at parameter t.
+    pub fn split(&self, t: f32) -> (Self, Self) {
+        let left = lerp(self.start, self.middle, t);
+        let right = lerp(self.middle, self.end, t);
+        let center = lerp(left, right, t);
+
+        (
+            Self {
+                start: self.start,
+                middle: left,
+                end: center,
+            },
+            Self {
+                start: center,
+                middle: right,
+                end: self.end,
+            },
+        )
+    }
+
+    // Gauss-Legendre quadrature of |B'(t)| on [0; t].
+    pub fn length_to(&self, t: f32) -> f32 {
+        GAUSS_NODES
+            .iter()
+            .zip(GAUSS_WEIGHTS)
+            .map(|(node, weight)| {
+                let (dx, dy) = self.derivative(t * (node + 1.0) / 2.0);
+                weight * f32::sqrt(dx * dx + dy * dy)
+            })
+            .sum::<f32>()
+            * t
+            / 2.0
+    }
+
+    pub fn length(&self) -> f32 {
+        self.length_to(1.0)
+    }
+
+    // Box around all three points. Always contains the segment.
+    pub fn control_box(&self) -> BoundingBox {
+        BoundingBox::from_points(&[self.start, self.middle, self.end])
+    }
+
+    // Parameters of intersections with other segment, found by recursive subdivision.
+    pub fn intersections(&self, other: &Self) -> Vec<(f32, f32)> {
+        let mut hits: Vec<(f32, f32)> = vec![];
+        intersect(self, (0.0, 1.0), other, (0.0, 1.0), 0, &mut hits);
+
+        hits
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct BoundingBox {
+    pub min: Point,
+    pub max: Point,
+}
+
+impl BoundingBox {
+    pub fn from_points(points: &[Point]) -> Self {
+        let mut min = Point::new(f32::MAX, f32::MAX);
+        let mut max = Point::new(f32::MIN, f32::MIN);
+
+        for point in points {
+            min.x = f32::min(min.x, point.x);
+            min.y = f32::min(min.y, point.y);
+            max.x = f32::max(max.x, point.x);
+            max.y = f32::max(max.y, point.y);
+        }
+
+        Self { min, max }
+    }
+
+    pub fn overlaps(&self, other: &Self) -> bool {
+        self.min.x <= other.max.x
+            && other.min.x <= self.max.x
+            && self.min.y <= other.max.y
+            && other.min.y <= self.max.y
+    }
+}
+
+fn intersect(
+    first: &Segment, first_range: (f32, f32), second: &Segment, second_range: (f32, f32),
+    depth: u32, hits: &mut Vec<(f32, f32)>,
+) {
+    if !first.control_box().overlaps(&second.control_box()) {
+        return;
+    }
+
+    let is_flat =
+        first.deviation() <= INTERSECTION_FLATNESS && second.deviation() <= INTERSECTION_FLATNESS;
+    if is_flat || depth >= INTERSECTION_MAX_DEPTH {
+        if let Some((t_first, t_second)) =
+            chord_intersection(first.start, first.end, second.start, second.end)
+        {
+            hits.push((
+                first_range.0 + (first_range.1 - first_range.0) * t_first,
+                second_range.0 + (second_range.1 - second_range.0) * t_second,
+            ));
+        }
+        return;
+    }
+
+    let (first_left, first_right) = first.split(0.5);
+    let (second_left, second_right) = second.split(0.5);
+    let first_middle = (first_range.0 + first_range.1) / 2.0;
+    let second_middle = (second_range.0 + second_range.1) / 2.0;
+
+    let halves_first = [
+        (first_left, (first_range.0, first_middle)),
+        (first_right, (first_middle, first_range.1)),
+    ];
+    let halves_second = [
+        (second_left, (second_range.0, second_middle)),
+        (second_right, (second_middle, second_range.1)),
+    ];
+
+    for (segment_first, range_first) in &halves_first {
+        for (segment_second, range_second) in &halves_second {
+            intersect(
+                segment_first,
+                *range_first,
+                segment_second,
+                *range_second,
+                depth + 1,
+                hits,
+            );
+        }
+    }
+}
+
+// Intersection of two line segments: local parameters of both.
+fn chord_intersection(
+    first_start: Point, first_end: Point, second_start: Point, second_end: Point,
+) -> Option<(f32, f32)> {
+    let first = Point::new(first_end.x - first_start.x, first_end.y - first_start.y);
+    let second = Point::new(second_end.x - second_start.x, second_end.y - second_start.y);
+    let between = Point::new(
+        second_start.x - first_start.x,
+        second_start.y - first_start.y,
+    );
+
+    let denominator = cross(first, second);
+    if denominator.abs() < f32::EPSILON {
+        return None;
+    }
+
+    let t_first = cross(between, second) / denominator;
+    let t_second = cross(between, first) / denominator;
+
+    let range = 0.0..=1.0;
+    if !range.contains(&t_first) || !range.contains(&t_second) {
+        return None;
+    }
+
+    Some((t_first, t_second))
+}
+
+pub fn cross(first: Point, second: Point) -> f32 {
+    first.x * second.y - first.y * second.x
+}
+
+pub fn distance(first: Point, second: Point) -> f32 {
+    f32::sqrt((second.x - first.x).powf(2.0) + (second.y - first.y).powf(2.0))
+}
+
+pub fn lerp(first: Point, second: Point, t: f32) -> Point {
+    Point::new(
+        first.x + (second.x - first.x) * t,
+        first.y + (second.y - first.y) * t,
+    )
+}
@@ -0,0 +1,388 @@
+use crate::geometry::line::Line;
+use crate::geometry::moveable_point::MoveablePoint;
+use crate::geometry::point::Point;
+use crate::graphics::screen::ScreenParams;
+use crate::models::bezier_point::{BezierPoint, BezierPointType, SmoothnessType};
+use crate::models::model::Model;
+use crate::traits::positionable::Positionable;
+use crate::ui::styles::{colors, strokes};
+use egui::{Color32, Response, Stroke};
+
+// Cubic Bezier segment: start, two handles, end.
+type Cubic = [Point; 4];
+
+// Interpolating spline through user placed (pass-through) points.
+pub struct Spline {
+    pub is_enabled: bool,
+
+    pub method: SplineMethod,
+    pub parameterization: Parameterization,
+
+    // Kochanek-Bartels parameters.
+    pub tension: f32,
+    pub bias: f32,
+
+    pub points: Vec<MoveablePoint>,
+
+    // Lines per spline segment.
+    pub samples: usize,
+    // Quadratic segments per spline segment, when converting to Bezier chain.
+    pub pieces: usize,
+
+    pub stroke: Stroke,
+    pub point_color: Color32,
+}
+
+impl Default for Spline {
+    fn default() -> Self {
+        Self {
+            is_enabled: false,
+
+            method: SplineMethod::CatmullRom,
+            parameterization: Parameterization::Centripetal,
+
+            tension: 0.0,
+            bias: 0.0,
+
+            points: vec![],
+
+            samples: 20,
+            pieces: 2,
+
+            stroke: strokes::spline_blue(0.07),
+            point_color: colors::BLUE,
+        }
+    }
+}
+
+impl Spline {
+    // Pass-through points are taken from control points of the model.
+    pub fn from_model(&mut self, model: &Model) {
+        self.points = model
+            .points
+            .iter()
+            .filter(|bezier| bezier.kind == BezierPointType::Control)
+            .map(|bezier| MoveablePoint::new(bezier.point.x, bezier.point.y))
+            .collect();
+    }
+
+    // Click on the free space of canvas adds new point at the end of the spline.
+    // Snapped position is used instead of the pointer, if it is given.
+    pub fn update_on_click(
+        &mut self, screen_params: ScreenParams, response: &Response, snap: Option<Point>,
+    ) {
+        if !response.clicked() {
+            return;
+        }
+
+        if let Some(position) = response.interact_pointer_pos() {
+            let point = match snap {
+                Some(snap) => MoveablePoint::new(snap.x, snap.y),
+                None => screen_params.point_px_to_cm(MoveablePoint::from_pos2(position)),
+            };
+            self.points.push(point);
+        }
+    }
+
+    pub fn lines(&self, screen_params: ScreenParams) -> Vec<Line<Point>> {
+        let mut stroke = self.stroke;
+        stroke.width = screen_params.value_cm_to_px(self.stroke.width);
+
+        let mut vec: Vec<Line<Point>> = vec![];
+
+        for cubic in self.cubic_segments() {
+            let points: Vec<Point> = (0..=self.samples)
+                .map(|i| cubic_point(&cubic, i as f32 / self.samples as f32))
+                .collect();
+
+            points.windows(2).for_each(|pair| {
+                vec.push(Line::new(pair[0], pair[1], stroke));
+            });
+        }
+
+        vec
+    }
+
+    // Approximates the spline by the chain of quadratic segments: control, defining, control, ...
+    pub fn to_bezier_points(&self) -> Vec<BezierPoint> {
+        let cubics = self.cubic_segments();
+        let Some(first) = cubics.first() else {
+            return vec![];
+        };
+
+        let mut points = vec![BezierPoint::control(first[0].x, first[0].y)];
+        // Whether previous quadratic keeps the tangent at its end.
+        let mut is_previous_tangent = false;
+
+        for cubic in cubics {
+            for piece in split_into_pieces(&cubic, self.pieces) {
+                let (middle, is_tangent) = quadratic_middle(&piece);
+
+                if let Some(last) = points.last_mut() {
+                    if is_previous_tangent && is_tangent {
+                        last.smoothness = SmoothnessType::Smooth;
+                    }
+                }
+
+                points.push(BezierPoint::defining(middle.x, middle.y));
+                points.push(BezierPoint::control(piece[3].x, piece[3].y));
+
+                is_previous_tangent = is_tangent;
+            }
+        }
+
+        points
+    }
+
+    pub fn cubic_segments(&self) -> Vec<Cubic> {
+        let points: Vec<Point> = self
+            .points
+            .iter()
+            .map(|point| Point::new(point.x, point.y))
+            .collect();
+
+        if points.len() < 2 {
+            return vec![];
+        }
+
+        match self.method {
+            SplineMethod::CatmullRom => catmull_rom(&points, self.parameterization.alpha()),
+            SplineMethod::NaturalCubic => natural_cubic(&points),
+            SplineMethod::KochanekBartels => kochanek_bartels(&points, self.tension, self.bias),
+        }
+    }
+}
+
+#[derive(PartialEq)]
+pub enum SplineMethod {
+    CatmullRom,
+    NaturalCubic,
+    KochanekBartels,
+}
+
+// Knot intervals of Catmull-Rom spline: |P(i+1) - P(i)|^alpha.
+#[derive(PartialEq)]
+pub enum Parameterization {
+    Uniform,
+    Centripetal,
+    Chordal,
+}
+
+impl Parameterization {
+    pub fn alpha(&self) -> f32 {
+        match self {
+            Parameterization::Uniform => 0.0,
+            Parameterization::Centripetal => 0.5,
+            Parameterization::Chordal => 1.0,
+        }
+    }
+}
+
+fn add(first: Point, second: Point) -> Point {
+    Point::new(first.x + second.x, first.y + second.y)
+}
+
+fn sub(first: Point, second: Point) -> Point {
+    Point::new(first.x - second.x, first.y - second.y)
+}
+
+fn scale(point: Point, factor: f32) -> Point {
+    Point::new(point.x * factor, point.y * factor)
+}
+
+fn lerp(first: Point, second: Point, t: f32) -> Point {
+    add(first, scale(sub(second, first), t))
+}
+
+fn distance(first: Point, second: Point) -> f32 {
+    f32::sqrt((second.x - first.x).powf(2.0) + (second.y - first.y).powf(2.0))
+}
+
+// Points with reflected phantom ends: P(-1) = 2P(0) - P(1), P(n) = 2P(n-1) - P(n-2).
+fn with_phantom_ends(points: &[Point]) -> Vec<Point> {
+    let length = points.len();
+
+    let mut extended = Vec::with_capacity(length + 2);
+    extended.push(sub(scale(points[0], 2.0), points[1]));
+    extended.extend_from_slice(points);
+    extended.push(sub(scale(points[length - 1], 2.0), points[length - 2]));
+
+    extended
+}
+
+// Hermite form to Bezier form: handles are a third of the tangent, scaled by knot interval.
+fn hermite_to_cubics(points: &[Point], tangents: &[Point], intervals: &[f32]) -> Vec<Cubic> {
+    (0..points.len() - 1)
+        .map(|i| {
+            [
+                points[i],
+                add(points[i], scale(tangents[i], intervals[i] / 3.0)),
+                sub(points[i + 1], scale(tangents[i + 1], intervals[i] / 3.0)),
+                points[i + 1],
+            ]
+        })
+        .collect()
+}
+
+// Non-uniform Catmull-Rom: alpha = 0 (uniform), 0.5 (centripetal), 1 (chordal).
+fn catmull_rom(points: &[Point], alpha: f32) -> Vec<Cubic> {
+    let extended = with_phantom_ends(points);
+
+    // Interval `i` is between extended[i] and extended[i + 1].
+    let intervals: Vec<f32> = extended
+        .windows(2)
+        .map(|pair| {
+            let interval = distance(pair[0], pair[1]).powf(alpha);
+            if interval < f32::EPSILON {
+                1.0
+            } else {
+                interval
+            }
+        })
+        .collect();
+
+    let tangents: Vec<Point> = (1..extended.len() - 1)
+        .map(|i| {
+            let (previous, next) = (intervals[i - 1], intervals[i]);
+
+            let incoming = scale(sub(extended[i], extended[i - 1]), 1.0 / previous);
+            let across = scale(
+                sub(extended[i + 1], extended[i - 1]),
+                1.0 / (previous + next),
+            );
+            let outgoing = scale(sub(extended[i + 1], extended[i]), 1.0 / next);
+
+            add(sub(incoming, across), outgoing)
+        })
+        .collect();
+
+    hermite_to_cubics(points, &tangents, &intervals[1..])
+}
+
+// Kochanek-Bartels (TCB) spline with zero continuity parameter.
+fn kochanek_bartels(points: &[Point], tension: f32, bias: f32) -> Vec<Cubic> {
+    let extended = with_phantom_ends(points);
+
+    let incoming_factor = (1.0 - tension) * (1.0 + bias) / 2.0;
+    let outgoing_factor = (1.0 - tension) * (1.0 - bias) / 2.0;
+
+    let tangents: Vec<Point> = (1..extended.len() - 1)
+        .map(|i| {
+            add(
+                scale(sub(extended[i], extended[i - 1]), incoming_factor),
+                scale(sub(extended[i + 1], extended[i]), outgoing_factor),
+            )
+        })
+        .collect();
+
+    hermite_to_cubics(points, &tangents, &vec![1.0; points.len() - 1])
+}
+
+// Natural cubic spline (second derivative is zero at the ends), uniform parameterization.
+// Tangents are found from tridiagonal system by Thomas algorithm:
+// 2D(0) + D(1) = 3(P(1) - P(0)),
+// D(i-1) + 4D(i) + D(i+1) = 3(P(i+1) - P(i-1)),
+// D(n-2) + 2D(n-1) = 3(P(n-1) - P(n-2)).
+fn natural_cubic(points: &[Point]) -> Vec<Cubic> {
+    let length = points.len();
+    let last = length - 1;
+
+    let mut diagonal = vec![4.0; length];
+    diagonal[0] = 2.0;
+    diagonal[last] = 2.0;
+
+    let mut right: Vec<Point> = (0..length)
+        .map(|i| {
+            let (previous, next) = (i.saturating_sub(1), usize::min(i + 1, last));
+            scale(sub(points[next], points[previous]), 3.0)
+        })
+        .collect();
+
+    // Forward elimination, all off-diagonal coefficients are 1.
+    for i in 1..length {
+        let factor = 1.0 / diagonal[i - 1];
+        diagonal[i] -= factor;
+        right[i] = sub(right[i], scale(right[i - 1], factor));
+    }
+
+    // Back substitution.
+    let mut tangents = vec![Point::new(0.0, 0.0); length];
+    tangents[last] = scale(right[last], 1.0 / diagonal[last]);
+    for i in (0..last).rev() {
+        tangents[i] = scale(sub(right[i], tangents[i + 1]), 1.0 / diagonal[i]);
+    }
+
+    hermite_to_cubics(points, &tangents, &vec![1.0; length - 1])
+}
+
+fn cubic_point(cubic: &Cubic, t: f32) -> Point {
+    let first = lerp(cubic[0], cubic[1], t);
+    let second = lerp(cubic[1], cubic[2], t);
+    let third = lerp(cubic[2], cubic[3], t);
+
+    lerp(lerp(first, second, t), lerp(second, third, t), t)
+}
+
+// De Casteljau split at parameter t.
+fn split_cubic(cubic: &Cubic, t: f32) -> (Cubic, Cubic) {
+    let first = lerp(cubic[0], cubic[1], t);
+    let second = lerp(cubic[1], cubic[2], t);
+    let third = lerp(cubic[2], cubic[3], t);
+
+    let left = lerp(first, second, t);
+    let right = lerp(second, third, t);
+    let center = lerp(left, right, t);
+
+    (
+        [cubic[0], first, left, center],
+        [center, right, third, cubic[3]],
+    )
+}
+
+fn split_into_pieces(cubic: &Cubic, pieces: usize) -> Vec<Cubic> {
+    let pieces = usize::max(pieces, 1);
+
+    let mut result = Vec::with_capacity(pieces);
+    let mut rest = *cubic;
+
+    for i in 0..pieces - 1 {
+        let (left, right) = split_cubic(&rest, 1.0 / (pieces - i) as f32);
+        result.push(left);
+        rest = right;
+    }
+    result.push(rest);
+
+    result
+}
+
+// Defining point of the quadratic approximating the cubic. It is the intersection of end
+// tangents, so the tangents are kept (returns true). If tangents do not intersect in front
+// of both ends (or too far), the midpoint approximation (3(B1 + B2) - B0 - B3) / 4 is used.
+fn quadratic_middle(cubic: &Cubic) -> (Point, bool) {
+    let start_direction = sub(cubic[1], cubic[0]);
+    let end_direction = sub(cubic[2], cubic[3]);
+    let between = sub(cubic[3], cubic[0]);
+
+    let denominator = start_direction.x * end_direction.y - start_direction.y * end_direction.x;
+    if denominator.abs() > f32::EPSILON {
+        let s = (between.x * end_direction.y - between.y * end_direction.x) / denominator;
+        let u = (between.x * start_direction.y - between.y * start_direction.x) / denominator;
+
+        let middle = add(cubic[0], scale(start_direction, s));
+
+        // Nearly parallel tangents give too distant intersection.
+        let limit = 2.0 * distance(cubic[0], cubic[3]);
+        let is_near = distance(middle, cubic[0]) <= limit && distance(middle, cubic[3]) <= limit;
+
+        if s > 0.0 && u > 0.0 && is_near {
+            return (middle, true);
+        }
+    }
+
+    let middle = scale(
+        sub(scale(add(cubic[1], cubic[2]), 3.0), add(cubic[0], cubic[3])),
+        0.25,
+    );
+
+    (middle, false)
+}
@@ -1,56 +1,96 @@
-use crate::models::bezier_point::BezierPoint;
+use crate::errors::animation::AnimationError;
+use crate::models::bezier_point::{BezierPoint, BezierPointType, SmoothnessType};
 use crate::models::model::Model;
+use crate::models::segment::Segment;
+
+// Cuts closer than this (in fractions of the contour) are merged.
+const FRACTION_EPSILON: f32 = 1e-4;
+// Iterations of bisection, that finds parameter by arc length.
+const BISECTION_ITERATIONS: u32 = 24;
+
+pub struct Contour {
+    pub name: String,
+    pub points: Vec<BezierPoint>,
+}
 
 pub struct AnimationSettings {
     pub is_running: bool,
+    pub is_ping_pong: bool,
 
-    figure_state: Vec<BezierPoint>,
-    model_state: Vec<BezierPoint>,
+    pub contours: Vec<Contour>,
+    pub start: usize,
+    pub end: usize,
+    pub contour_name: String,
 
-    step: i32,
-    max_steps: i32,
+    pub interpolation: Interpolation,
+    pub correspondence: Correspondence,
+
+    pub step: i32,
+    pub max_steps: i32,
     direction: i32,
+
+    // Reason of the last failed morph, taken by the settings panel.
+    pub error: Option<AnimationError>,
+
+    // Start and end contours, subdivided to the same number of points.
+    start_state: Vec<BezierPoint>,
+    end_state: Vec<BezierPoint>,
+    prepared: Option<(usize, usize, Correspondence)>,
 }
 
 impl Default for AnimationSettings {
     fn default() -> Self {
         Self {
             is_running: false,
-            figure_state: Self::figure_state(),
-            model_state: vec![],
+            is_ping_pong: true,
+
+            contours: vec![
+                Contour {
+                    name: String::from("Initial"),
+                    points: Model::default_points(),
+                },
+                Contour {
+                    name: String::from("Circle"),
+                    points: Self::circle_state(),
+                },
+            ],
+            start: 0,
+            end: 1,
+            contour_name: String::from("Contour"),
+
+            interpolation: Interpolation::Linear,
+            correspondence: Correspondence::Parameter,
 
             step: 0,
             max_steps: 150,
             direction: 1,
+
+            error: None,
+
+            start_state: vec![],
+            end_state: vec![],
+            prepared: None,
         }
     }
 }
 
 impl AnimationSettings {
     pub fn process_animation(&mut self, model: &mut Model) {
-        assert_eq!(model.points.len(), self.figure_state.len());
-        assert_eq!(model.points.len(), self.model_state.len());
-        assert_eq!(model.points.len(), 121);
-
-        let length = model.points.len();
-        for i in 0..length {
-            let x = self.model_state[i].point.x
-                * (1.0 - (self.step as f32 / self.max_steps as f32))
-                + self.figure_state[i].point.x * (self.step as f32 / self.max_steps as f32);
-            let y = self.model_state[i].point.y
-                * (1.0 - (self.step as f32 / self.max_steps as f32))
-                + self.figure_state[i].point.y * (self.step as f32 / self.max_steps as f32);
-
-            model.points[i].point.x = x;
-            model.points[i].point.y = y;
+        if !self.apply(model) {
+            self.is_running = false;
+            return;
         }
-        model.points[120] = model.points[0];
 
         self.step += self.direction;
 
         if self.step > self.max_steps {
             self.step = self.max_steps;
-            self.direction *= -1;
+
+            if self.is_ping_pong {
+                self.direction *= -1;
+            } else {
+                self.is_running = false;
+            }
         }
 
         if self.step < 0 {
@@ -59,7 +99,140 @@ impl AnimationSettings {
         }
     }
 
-    fn figure_state() -> Vec<BezierPoint> {
+    // Sets model to the state of current step. Returns false, if contours can not be morphed.
+    pub fn apply(&mut self, model: &mut Model) -> bool {
+        if let Err(e) = self.prepare() {
+            self.error = Some(e);
+            return false;
+        }
+
+        let progress = self.step as f32 / self.max_steps as f32;
+        let t = match self.interpolation {
+            Interpolation::Linear => progress,
+            // Smoothstep: slow start and slow end.
+            Interpolation::Eased => progress * progress * (3.0 - 2.0 * progress),
+        };
+
+        model.points = self
+            .start_state
+            .iter()
+            .zip(&self.end_state)
+            .map(|(start, end)| {
+                let mut bezier = *start;
+                if start.constraint.is_locked {
+                    return bezier;
+                }
+
+                bezier.point.x = start.point.x * (1.0 - t) + end.point.x * t;
+                bezier.point.y = start.point.y * (1.0 - t) + end.point.y * t;
+
+                bezier
+            })
+            .collect();
+        model.apply_constraints();
+
+        true
+    }
+
+    pub fn checkout_status(&mut self) {
+        self.is_running = !self.is_running;
+
+        // Playing again from the start, if the end was reached.
+        if self.is_running && !self.is_ping_pong && self.step >= self.max_steps {
+            self.step = 0;
+            self.direction = 1;
+        }
+    }
+
+    pub fn save_contour(&mut self, model: &Model) {
+        self.contours.push(Contour {
+            name: self.contour_name.clone(),
+            points: model.points.clone(),
+        });
+    }
+
+    pub fn remove_contour(&mut self, index: usize) {
+        if index >= self.contours.len() {
+            return;
+        }
+
+        self.contours.remove(index);
+        self.start = self.start.min(self.contours.len().saturating_sub(1));
+        self.end = self.end.min(self.contours.len().saturating_sub(1));
+        self.prepared = None;
+    }
+
+    // Subdivides both contours, so they have the same number of segments.
+    fn prepare(&mut self) -> Result<(), AnimationError> {
+        let key = (self.start, self.end, self.correspondence);
+        if self.prepared == Some(key) {
+            return Ok(());
+        }
+
+        let (Some(start), Some(end)) = (self.contours.get(self.start), self.contours.get(self.end))
+        else {
+            return Err(AnimationError::ContourNotFound);
+        };
+
+        let start_segments = segments(&start.points);
+        let end_segments = segments(&end.points);
+        for (contour, segments) in [(start, &start_segments), (end, &end_segments)] {
+            if segments.is_empty() {
+                return Err(AnimationError::InvalidContour(format!(
+                    "Contour \"{}\" has {} points, odd number of at least 3 is required.",
+                    contour.name,
+                    contour.points.len()
+                )));
+            }
+        }
+
+        let mut start_fractions = joint_fractions(&start_segments, self.correspondence);
+        let mut end_fractions = joint_fractions(&end_segments, self.correspondence);
+
+        let mut cuts: Vec<f32> = start_fractions
+            .iter()
+            .chain(&end_fractions)
+            .copied()
+            .collect();
+        cuts.sort_by(|first, second| first.total_cmp(second));
+        cuts.dedup_by(|next, previous| (*next - *previous).abs() < FRACTION_EPSILON);
+
+        // Joints are moved onto the merged cuts, so both contours are split at the same places.
+        snap(&mut start_fractions, &cuts);
+        snap(&mut end_fractions, &cuts);
+
+        self.start_state = subdivide(
+            &start.points,
+            &start_segments,
+            &start_fractions,
+            &cuts,
+            self.correspondence,
+        );
+        self.end_state = subdivide(
+            &end.points,
+            &end_segments,
+            &end_fractions,
+            &cuts,
+            self.correspondence,
+        );
+
+        // Segments, shorter than the merge distance, leave one of contours with extra points.
+        if self.start_state.len() != self.end_state.len() {
+            self.prepared = None;
+            return Err(AnimationError::MismatchedContours(format!(
+                "\"{}\" and \"{}\" give {} and {} points. Remove very short segments and try again.",
+                start.name,
+                end.name,
+                self.start_state.len(),
+                self.end_state.len()
+            )));
+        }
+
+        self.prepared = Some(key);
+        Ok(())
+    }
+
+    fn circle_state() -> Vec<BezierPoint> {
         let mut points: Vec<BezierPoint> = Vec::with_capacity(121);
 
         let max_points = 120;
@@ -82,14 +255,138 @@ impl AnimationSettings {
 
         points
     }
+}
 
-    pub fn checkout_status(&mut self, model: &mut Model) {
-        self.is_running = !self.is_running;
+#[derive(PartialEq)]
+pub enum Interpolation {
+    Linear,
+    // Slow start and slow end.
+    Eased,
+}
+
+// Which points of two contours are matched with each other.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Correspondence {
+    // Equal fractions of segments count.
+    Parameter,
+    // Equal fractions of contour length.
+    ArcLength,
+}
+
+fn segments(points: &[BezierPoint]) -> Vec<Segment> {
+    if points.len() < 3 || (points.len() - 1) % 2 != 0 {
+        return vec![];
+    }
+
+    (0..points.len() - 2)
+        .step_by(2)
+        .map(|i| Segment::new(&points[i].point, &points[i + 1].point, &points[i + 2].point))
+        .collect()
+}
 
-        if self.is_running {
-            self.model_state = model.points.clone();
-        } else {
-            self.model_state = vec![];
+// Position of every joint in [0; 1], from the first point to the last one.
+fn joint_fractions(segments: &[Segment], correspondence: Correspondence) -> Vec<f32> {
+    let count = segments.len() as f32;
+    let by_parameter = || (0..=segments.len()).map(|i| i as f32 / count).collect();
+
+    match correspondence {
+        Correspondence::Parameter => by_parameter(),
+        Correspondence::ArcLength => {
+            let total: f32 = segments.iter().map(Segment::length).sum();
+            if total < f32::EPSILON {
+                return by_parameter();
+            }
+
+            let mut length = 0.0;
+            let mut fractions = vec![0.0];
+            for segment in segments {
+                length += segment.length();
+                fractions.push(length / total);
+            }
+
+            fractions
+        },
+    }
+}
+
+// Replaces every fraction by the closest cut.
+fn snap(fractions: &mut [f32], cuts: &[f32]) {
+    for fraction in fractions.iter_mut() {
+        if let Some(cut) = cuts.iter().min_by(|first, second| {
+            (**first - *fraction)
+                .abs()
+                .total_cmp(&(**second - *fraction).abs())
+        }) {
+            *fraction = *cut;
         }
     }
 }
+
+// Local parameter of the cut inside the segment, which takes [from; to] of the contour.
+fn local_parameter(
+    segment: &Segment, from: f32, to: f32, cut: f32, correspondence: Correspondence,
+) -> f32 {
+    let fraction = (cut - from) / (to - from);
+
+    match correspondence {
+        Correspondence::Parameter => fraction,
+        Correspondence::ArcLength => {
+            let target = fraction * segment.length();
+            let (mut low, mut high) = (0.0, 1.0);
+
+            for _ in 0..BISECTION_ITERATIONS {
+                let middle = (low + high) / 2.0;
+                if segment.length_to(middle) < target {
+                    low = middle;
+                } else {
+                    high = middle;
+                }
+            }
+
+            (low + high) / 2.0
+        },
+    }
+}
+
+// Splits segments at every cut, shape of the contour is not changed.
+fn subdivide(
+    points: &[BezierPoint], segments: &[Segment], fractions: &[f32], cuts: &[f32],
+    correspondence: Correspondence,
+) -> Vec<BezierPoint> {
+    let mut result = vec![points[0]];
+
+    for (i, segment) in segments.iter().enumerate() {
+        let (from, to) = (fractions[i], fractions[i + 1]);
+
+        let parameters: Vec<f32> = cuts
+            .iter()
+            .filter(|cut| **cut > from && **cut < to)
+            .map(|cut| local_parameter(segment, from, to, *cut, correspondence))
+            .collect();
+
+        let mut rest = *segment;
+        let mut passed = 0.0;
+        for t in parameters {
+            let (left, right) = rest.split((t - passed) / (1.0 - passed));
+            passed = t;
+            rest = right;
+
+            // De Casteljau split keeps the tangent, so new joint is smooth.
+            let mut control = BezierPoint::control(left.end.x, left.end.y);
+            control.smoothness = SmoothnessType::Smooth;
+
+            result.push(BezierPoint::defining(left.middle.x, left.middle.y));
+            result.push(control);
+        }
+
+        result.push(BezierPoint::defining(rest.middle.x, rest.middle.y));
+        result.push(points[2 * i + 2]);
+    }
+
+    debug_assert!(result
+        .iter()
+        .step_by(2)
+        .all(|bezier| bezier.kind == BezierPointType::Control));
+
+    result
+}
@@ -3,6 +3,7 @@ use crate::models::model::Model;
 use crate::traits::positionable::Positionable;
 use crate::ui::styles::colors;
 use egui::Color32;
+use nalgebra::Matrix3;
 
 pub struct Offset {
     pub is_enabled: bool,
@@ -26,21 +27,16 @@ impl Default for Offset {
 }
 
 impl Offset {
-    pub fn process(&mut self, model: &mut Model) {
+    // Translation by the change of the dot since the last step.
+    pub fn step(&mut self) -> Option<Matrix3<f32>> {
         if self.dot.x == 0.0 && self.dot.y == 0.0 {
-            return;
+            return None;
         };
 
-        let delta = (self.dot.x - self.old.0, self.dot.y - self.old.1);
-        model.points.iter_mut().for_each(|bezier| {
-            self.update_point(&mut bezier.point, delta);
-        });
+        let (dx, dy) = (self.dot.x - self.old.0, self.dot.y - self.old.1);
         self.old = (self.dot.x, self.dot.y);
-    }
 
-    pub fn update_point(&mut self, point: &mut MoveablePoint, delta: (f32, f32)) {
-        point.x += delta.0;
-        point.y += delta.1;
+        Some(Matrix3::new(1.0, 0.0, 0.0, 0.0, 1.0, 0.0, dx, dy, 1.0))
     }
 
     pub fn checkout_status(&mut self, model: &mut Model) {
@@ -1,6 +1,5 @@
 use crate::geometry::moveable_point::MoveablePoint;
 use crate::math::angle::Angle;
-use crate::models::model::Model;
 use crate::traits::positionable::Positionable;
 use crate::ui::styles::colors;
 use egui::Color32;
@@ -33,30 +32,19 @@ impl Default for Rotation {
 }
 
 impl Rotation {
-    pub fn process(&mut self, model: &mut Model) {
+    // Rotation by the change of the angle since the last step.
+    pub fn step(&mut self) -> Option<Matrix3<f32>> {
         if self.angle == 0.0 {
-            return;
+            return None;
         };
         if self.dot.x == self.old.0 && self.dot.y == self.old.1 && self.angle == self.old.2 {
-            return;
+            return None;
         }
 
         let delta = self.angle - self.old.2;
-        model.points.iter_mut().for_each(|bezier| {
-            self.update_point(&mut bezier.point, delta);
-        });
-
         self.old = (self.dot.x, self.dot.y, self.angle);
-    }
-
-    pub fn update_point(&self, point: &mut MoveablePoint, angle: f32) {
-        let point_vector = point.to_vector();
-        let matrix = self.matrix(angle);
-
-        let answer = point_vector * matrix;
 
-        point.x = answer.x;
-        point.y = answer.y;
+        Some(self.matrix(delta))
     }
 
     fn matrix(&self, angle: f32) -> Matrix3<f32> {
@@ -5,7 +5,8 @@ use crate::graphics::screen::{Resolution, ScreenParams};
 use crate::traits::positionable::Positionable;
 use crate::ui::styles::{colors, strokes};
 use eframe::epaint::Shape;
-use egui::{Frame, Response, Sense};
+use egui::{Align2, FontId, Frame, Response, Sense, Vec2};
+use nalgebra::Matrix3;
 
 #[derive(Default)]
 pub struct Canvas {
@@ -14,6 +15,14 @@ pub struct Canvas {
     pub grid_lines: Vec<Line<Point>>,
     pub model_lines: Vec<Line<Point>>,
     pub skeleton_lines: Vec<Line<Point>>,
+    pub comb_lines: Vec<Line<Point>>,
+    pub spline_lines: Vec<Line<Point>>,
+    pub nurbs_lines: Vec<Line<Point>>,
+    pub nurbs_polygon_lines: Vec<Line<Point>>,
+    pub offset_curve_lines: Vec<Line<Point>>,
+    pub boolean_lines: Vec<Line<Point>>,
+    pub fitting_samples_lines: Vec<Line<Point>>,
+    pub fitting_lines: Vec<Line<Point>>,
 }
 
 impl Canvas {
@@ -25,12 +34,16 @@ impl Canvas {
 
         // Euclidean Offset
         if context.euclidean_offset.is_enabled {
-            context.euclidean_offset.process(&mut context.model);
+            if let Some(matrix) = context.euclidean_offset.step() {
+                transform_layers(context, matrix);
+            }
         }
 
         // Euclidean Rotation
         if context.euclidean_rotation.is_enabled {
-            context.euclidean_rotation.process(&mut context.model);
+            if let Some(matrix) = context.euclidean_rotation.step() {
+                transform_layers(context, matrix);
+            }
         }
 
         // Animation:
@@ -51,6 +64,42 @@ impl Canvas {
 
         // Creating model:
         self.model_lines = context.model.lines(self.screen_params);
+
+        // Creating spline:
+        if context.spline.is_enabled {
+            self.spline_lines = context.spline.lines(self.screen_params);
+        }
+
+        // Creating NURBS:
+        if context.nurbs.is_enabled {
+            context.nurbs.update_knots();
+
+            self.nurbs_lines = context.nurbs.lines(self.screen_params);
+            if context.nurbs.is_polygon_enabled {
+                self.nurbs_polygon_lines = context.nurbs.polygon_lines(self.screen_params);
+            }
+        }
+
+        // Creating offset curves:
+        if context.offset_curve.is_enabled {
+            self.offset_curve_lines = context.offset_curve.lines(self.screen_params);
+        }
+
+        // Creating result of boolean operation:
+        if context.boolean.is_enabled {
+            self.boolean_lines = context.boolean.lines(self.screen_params);
+        }
+
+        // Creating fitted curve:
+        if context.fitting.is_enabled {
+            self.fitting_samples_lines = context.fitting.samples_lines(self.screen_params);
+            self.fitting_lines = context.fitting.lines(self.screen_params);
+        }
+
+        // Creating curvature comb:
+        if context.model.is_comb_enabled {
+            self.comb_lines = context.model.comb_lines(self.screen_params);
+        }
     }
 
     pub fn draw(&mut self, ui: &mut egui::Ui, context: &mut Context) -> Response {
@@ -77,6 +126,40 @@ impl Canvas {
             .collect();
         painter.extend(model_lines);
 
+        // Draw curvature comb
+        if context.model.is_comb_enabled {
+            let comb_lines: Vec<Shape> = self
+                .comb_lines
+                .iter()
+                .map(|line| line.to_screen(self.screen_params).to_shape())
+                .collect();
+            painter.extend(comb_lines);
+        }
+
+        // Draw continuity of joints
+        if context.model.is_continuity_enabled {
+            let label_offset = Vec2::new(8.0, -8.0);
+
+            context
+                .model
+                .joints_continuity()
+                .iter()
+                .for_each(|(index, continuity)| {
+                    let position = context.model.points[*index]
+                        .point
+                        .to_screen(self.screen_params)
+                        .to_pos2();
+
+                    painter.text(
+                        position + label_offset,
+                        Align2::LEFT_BOTTOM,
+                        continuity.label(),
+                        FontId::proportional(14.0),
+                        context.model.continuity_color,
+                    );
+                });
+        }
+
         // Dot radius
         let dot_radius = self.screen_params.value_cm_to_px(context.model.radius);
 
@@ -84,6 +167,55 @@ impl Canvas {
         let outline_width = self.screen_params.value_cm_to_px(0.02);
         let outline_stroke = strokes::bezier_outline(outline_width);
 
+        // Selected segment
+        let selected_lines: Vec<Shape> = context
+            .model
+            .selected_lines(self.screen_params)
+            .iter()
+            .map(|line| line.to_screen(self.screen_params).to_shape())
+            .collect();
+        painter.extend(selected_lines);
+
+        // Closest point on the curve, searched only if it is shown or snapped to.
+        let is_projected = context.model.is_projection_enabled || context.model.is_snapping_enabled;
+        let projection = response
+            .hover_pos()
+            .filter(|_| is_projected)
+            .and_then(|position| {
+                let point = self
+                    .screen_params
+                    .point_px_to_cm(Point::from_pos2(position));
+                context.model.project(point)
+            })
+            .filter(|projection| {
+                self.screen_params.value_cm_to_px(projection.distance) <= context.model.snap_radius
+            });
+        let snap = projection
+            .filter(|_| context.model.is_snapping_enabled)
+            .map(|projection| projection.point);
+
+        if context.model.is_projection_enabled {
+            if let Some(projection) = projection {
+                let dot = projection.point.to_screen(self.screen_params).to_dot(
+                    dot_radius / 2.0,
+                    context.model.projection_color,
+                    outline_stroke,
+                );
+                painter.add(dot);
+
+                response.clone().on_hover_text(format!(
+                    "Closest Point.\nCoordinates:\n- X: {}\n- Y: {}\n{}",
+                    projection.point.x,
+                    projection.point.y,
+                    projection.details()
+                ));
+
+                if response.clicked() {
+                    context.model.selected = Some(projection);
+                }
+            }
+        }
+
         if context.model.is_skeleton_enabled {
             // Draw skeleton lines
             let skeleton_lines: Vec<Shape> = self
@@ -93,6 +225,17 @@ impl Canvas {
                 .collect();
             painter.extend(skeleton_lines);
 
+            // Draw mirror axis:
+            if context.model.mirror_links() > 0 || context.model.mirror_source.is_some() {
+                let mirror_axis_lines: Vec<Shape> = context
+                    .model
+                    .mirror_axis_lines(self.screen_params)
+                    .iter()
+                    .map(|line| line.to_screen(self.screen_params).to_shape())
+                    .collect();
+                painter.extend(mirror_axis_lines);
+            }
+
             // Draw skeleton points:
             let point_shapes: Vec<Shape> = context
                 .model
@@ -112,6 +255,11 @@ impl Canvas {
 
             // Updating model
             let mut changed_points_indexes: Vec<usize> = vec![];
+            let mut are_constraints_changed = false;
+
+            let curvatures: Vec<Option<(f32, f32)>> = (0..context.model.points.len())
+                .map(|index| context.model.joint_curvature(index))
+                .collect();
 
             context
                 .model
@@ -126,22 +274,46 @@ impl Canvas {
                         &response,
                     );
 
-                    if bezier
-                        .point
-                        .update_on_drag(self.screen_params, ui, &interaction_response)
+                    if !bezier.constraint.is_locked
+                        && bezier.point.update_on_drag(
+                            self.screen_params,
+                            ui,
+                            &interaction_response,
+                        )
                     {
                         changed_points_indexes.push(index);
                     };
 
-                    bezier.update_on_change_smoothness(ui, &interaction_response);
+                    if bezier.update_on_context_menu(
+                        index,
+                        &mut context.model.mirror_source,
+                        &mut context.model.mirror_selection,
+                        &interaction_response,
+                    ) {
+                        are_constraints_changed = true;
+                    }
 
                     if context.model.are_tooltips_enabled {
-                        bezier.point.show_tooltip(index + 1, interaction_response);
+                        let curvature = curvatures[index].map(|(left, right)| {
+                            format!("Curvature:\n- Left: {:.4}\n- Right: {:.4}", left, right)
+                        });
+                        let details = [curvature, bezier.constraint_details()]
+                            .into_iter()
+                            .flatten()
+                            .reduce(|first, second| format!("{first}\n{second}"));
+                        bezier
+                            .point
+                            .show_tooltip(index + 1, details, interaction_response);
                     }
                 });
 
+            if are_constraints_changed {
+                context.model.apply_constraints();
+                ui.ctx().request_repaint();
+            }
+
             if !changed_points_indexes.is_empty() {
-                context.model.update_smoothness(&changed_points_indexes);
+                context.model.update_constraints(&changed_points_indexes);
 
                 // Draw tangent lines:
                 if context.model.is_tangent_enabled {
@@ -156,6 +328,229 @@ impl Canvas {
             }
         }
 
+        // Spline
+        if context.spline.is_enabled {
+            let spline_lines: Vec<Shape> = self
+                .spline_lines
+                .iter()
+                .map(|line| line.to_screen(self.screen_params).to_shape())
+                .collect();
+            painter.extend(spline_lines);
+
+            let point_shapes: Vec<Shape> = context
+                .spline
+                .points
+                .iter()
+                .map(|point| {
+                    point.to_screen(self.screen_params).to_dot(
+                        dot_radius,
+                        context.spline.point_color,
+                        outline_stroke,
+                    )
+                })
+                .collect();
+            painter.extend(point_shapes);
+
+            // Secondary click removes the point.
+            let mut removed_point_index: Option<usize> = None;
+
+            context
+                .spline
+                .points
+                .iter_mut()
+                .enumerate()
+                .for_each(|(index, point)| {
+                    let interaction_response =
+                        point.interaction_response(dot_radius, self.screen_params, ui, &response);
+
+                    point.update_on_drag(self.screen_params, ui, &interaction_response);
+
+                    if interaction_response.secondary_clicked() {
+                        removed_point_index = Some(index);
+                    }
+
+                    if context.model.are_tooltips_enabled {
+                        point.show_tooltip(index + 1, None, interaction_response);
+                    }
+                });
+
+            if let Some(index) = removed_point_index {
+                context.spline.points.remove(index);
+            }
+
+            context
+                .spline
+                .update_on_click(self.screen_params, &response, snap);
+        }
+
+        // NURBS
+        if context.nurbs.is_enabled {
+            if context.nurbs.is_polygon_enabled {
+                let polygon_lines: Vec<Shape> = self
+                    .nurbs_polygon_lines
+                    .iter()
+                    .map(|line| line.to_screen(self.screen_params).to_shape())
+                    .collect();
+                painter.extend(polygon_lines);
+            }
+
+            let nurbs_lines: Vec<Shape> = self
+                .nurbs_lines
+                .iter()
+                .map(|line| line.to_screen(self.screen_params).to_shape())
+                .collect();
+            painter.extend(nurbs_lines);
+
+            let point_shapes: Vec<Shape> = context
+                .nurbs
+                .points
+                .iter()
+                .map(|point| {
+                    point.to_screen(self.screen_params).to_dot(
+                        dot_radius,
+                        context.nurbs.point_color,
+                        outline_stroke,
+                    )
+                })
+                .collect();
+            painter.extend(point_shapes);
+
+            // Secondary click removes the point.
+            let mut removed_point_index: Option<usize> = None;
+
+            let weights = context.nurbs.weights.clone();
+            context
+                .nurbs
+                .points
+                .iter_mut()
+                .enumerate()
+                .for_each(|(index, point)| {
+                    let interaction_response =
+                        point.interaction_response(dot_radius, self.screen_params, ui, &response);
+
+                    point.update_on_drag(self.screen_params, ui, &interaction_response);
+
+                    if interaction_response.secondary_clicked() {
+                        removed_point_index = Some(index);
+                    }
+
+                    if context.model.are_tooltips_enabled {
+                        let details = weights
+                            .get(index)
+                            .map(|weight| format!("Weight: {:.3}", weight));
+                        point.show_tooltip(index + 1, details, interaction_response);
+                    }
+                });
+
+            if let Some(index) = removed_point_index {
+                context.nurbs.remove_point(index);
+            }
+
+            // Spline takes clicks, when both are enabled.
+            if !context.spline.is_enabled {
+                context
+                    .nurbs
+                    .update_on_click(self.screen_params, &response, snap);
+            }
+        }
+
+        // Offset curves
+        if context.offset_curve.is_enabled {
+            let offset_curve_lines: Vec<Shape> = self
+                .offset_curve_lines
+                .iter()
+                .map(|line| line.to_screen(self.screen_params).to_shape())
+                .collect();
+            painter.extend(offset_curve_lines);
+
+            if context.model.is_skeleton_enabled {
+                context
+                    .offset_curve
+                    .contours
+                    .iter_mut()
+                    .flatten()
+                    .enumerate()
+                    .for_each(|(index, bezier)| {
+                        let dot = bezier.point.to_screen(self.screen_params).to_dot(
+                            dot_radius,
+                            context.offset_curve.point_color,
+                            outline_stroke,
+                        );
+                        painter.add(dot);
+
+                        let interaction_response = bezier.point.interaction_response(
+                            dot_radius,
+                            self.screen_params,
+                            ui,
+                            &response,
+                        );
+
+                        bezier
+                            .point
+                            .update_on_drag(self.screen_params, ui, &interaction_response);
+
+                        if context.model.are_tooltips_enabled {
+                            bezier
+                                .point
+                                .show_tooltip(index + 1, None, interaction_response);
+                        }
+                    });
+            }
+        }
+
+        // Boolean operation result
+        if context.boolean.is_enabled {
+            if context.boolean.fill.is_enabled {
+                let polygons = context.boolean.polygons();
+                let fill = context
+                    .boolean
+                    .fill
+                    .shape(&polygons, self.screen_params, response.rect);
+                painter.add(fill);
+            }
+
+            let boolean_lines: Vec<Shape> = self
+                .boolean_lines
+                .iter()
+                .map(|line| line.to_screen(self.screen_params).to_shape())
+                .collect();
+            painter.extend(boolean_lines);
+
+            if context.model.is_skeleton_enabled {
+                context
+                    .boolean
+                    .result
+                    .iter_mut()
+                    .flatten()
+                    .enumerate()
+                    .for_each(|(index, bezier)| {
+                        let dot = bezier.point.to_screen(self.screen_params).to_dot(
+                            dot_radius,
+                            context.boolean.point_color,
+                            outline_stroke,
+                        );
+                        painter.add(dot);
+
+                        let interaction_response = bezier.point.interaction_response(
+                            dot_radius,
+                            self.screen_params,
+                            ui,
+                            &response,
+                        );
+
+                        bezier
+                            .point
+                            .update_on_drag(self.screen_params, ui, &interaction_response);
+
+                        if context.model.are_tooltips_enabled {
+                            bezier
+                                .point
+                                .show_tooltip(index + 1, None, interaction_response);
+                        }
+                    });
+            }
+        }
+
         // Offset Dot
         if context.euclidean_offset.is_enabled {
             let dot = context
@@ -202,8 +597,25 @@ impl Canvas {
             );
         }
 
-        // Check for dragging
-        self.screen_params.update_offset_on_drag(ui, &response);
+        // Fitted curve
+        if context.fitting.is_enabled {
+            let fitting_lines: Vec<Shape> = self
+                .fitting_samples_lines
+                .iter()
+                .chain(&self.fitting_lines)
+                .map(|line| line.to_screen(self.screen_params).to_shape())
+                .collect();
+            painter.extend(fitting_lines);
+        }
+
+        // Check for dragging. Stroke is drawn instead, while fitting is enabled.
+        if context.fitting.is_enabled {
+            context
+                .fitting
+                .update_on_drag(self.screen_params, &response);
+        } else {
+            self.screen_params.update_offset_on_drag(ui, &response);
+        }
 
         response
     }
@@ -221,3 +633,67 @@ impl Canvas {
             });
     }
 }
+
+// Moves the model and every visible layer, locked points of the model stay in place.
+fn transform_layers(context: &mut Context, matrix: Matrix3<f32>) {
+    context
+        .model
+        .points
+        .iter_mut()
+        .filter(|bezier| !bezier.constraint.is_locked)
+        .for_each(|bezier| transform(&mut bezier.point, matrix));
+
+    if context.spline.is_enabled {
+        context
+            .spline
+            .points
+            .iter_mut()
+            .for_each(|point| transform(point, matrix));
+    }
+
+    if context.nurbs.is_enabled {
+        context
+            .nurbs
+            .points
+            .iter_mut()
+            .for_each(|point| transform(point, matrix));
+    }
+
+    if context.offset_curve.is_enabled {
+        context
+            .offset_curve
+            .contours
+            .iter_mut()
+            .flatten()
+            .for_each(|bezier| transform(&mut bezier.point, matrix));
+    }
+
+    if context.boolean.is_enabled {
+        context
+            .boolean
+            .result
+            .iter_mut()
+            .flatten()
+            .for_each(|bezier| transform(&mut bezier.point, matrix));
+    }
+
+    if context.fitting.is_enabled {
+        context
+            .fitting
+            .samples
+            .iter_mut()
+            .for_each(|point| transform(point, matrix));
+        context
+            .fitting
+            .result
+            .iter_mut()
+            .for_each(|bezier| transform(&mut bezier.point, matrix));
+    }
+}
+
+fn transform(point: &mut impl Positionable, matrix: Matrix3<f32>) {
+    let answer = point.to_vector() * matrix;
+
+    point.set_x(answer.x);
+    point.set_y(answer.y);
+}
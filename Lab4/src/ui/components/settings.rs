@@ -1,12 +1,47 @@
 use crate::context::Context;
+use crate::graphics::fill::FillRule;
 use crate::graphics::screen::{MAX_PX_PER_CM, MIN_PX_PER_CM};
+use crate::models::bezier_point::SmoothnessType;
+use crate::models::boolean::Operation;
+use crate::models::model::{MirrorAxis, Model};
+use crate::models::nurbs::{KnotMode, MAX_DEGREE, MIN_DEGREE};
+use crate::models::offset_curve::OffsetSide;
+use crate::models::spline::{Parameterization, SplineMethod};
+use crate::operations::animation::{Contour, Correspondence, Interpolation};
 use crate::ui::components::canvas::Canvas;
+use crate::ui::components::message_box::MessageBox;
 use crate::ui::styles::colors;
 use egui::{DragValue, Grid, RichText};
 
 pub const SETTINGS_PANEL_WIDTH: f32 = 250.0;
 
-pub fn show_panel(context: &mut Context, canvas: &mut Canvas, ui: &mut egui::Ui) {
+#[derive(Default)]
+pub struct Settings {
+    error_box: Option<MessageBox>,
+}
+
+impl Settings {
+    pub fn show_panel(&mut self, context: &mut Context, canvas: &mut Canvas, ui: &mut egui::Ui) {
+        show_content(context, canvas, &mut self.error_box, ui);
+
+        self.show_errors_if_some(ui);
+    }
+
+    fn show_errors_if_some(&mut self, ui: &egui::Ui) {
+        if let Some(error) = &mut self.error_box {
+            error.show(ui);
+
+            if error.is_closed() {
+                self.error_box = None;
+            }
+        }
+    }
+}
+
+fn show_content(
+    context: &mut Context, canvas: &mut Canvas, error_box: &mut Option<MessageBox>,
+    ui: &mut egui::Ui,
+) {
     egui::ScrollArea::vertical().show(ui, |ui| {
         ui.vertical_centered(|ui| {
             ui.heading("Settings");
@@ -87,6 +122,37 @@ pub fn show_panel(context: &mut Context, canvas: &mut Canvas, ui: &mut egui::Ui)
             ui.checkbox(&mut context.model.is_tangent_enabled, "Smoothing Tangent")
                 .on_hover_text("When changes related to smoothness adjustments occur, display the tangents on the modified segment.");
 
+            ui.collapsing("Joint Curvature", |ui| {
+                ui.label("Right click on a control point: Break -> Smooth (G1) -> G2.");
+
+                ui.add_space(5.0);
+
+                Grid::new("JointCurvature").num_columns(4).striped(true).show(ui, |ui| {
+                    ui.label(RichText::new("Point").strong());
+                    ui.label(RichText::new("Type").strong());
+                    ui.label(RichText::new("Left").strong());
+                    ui.label(RichText::new("Right").strong());
+                    ui.end_row();
+
+                    for (index, bezier) in context.model.points.iter().enumerate() {
+                        let kind = match bezier.smoothness {
+                            SmoothnessType::Smooth => "G1",
+                            SmoothnessType::Curvature => "G2",
+                            _ => continue,
+                        };
+                        let Some((left, right)) = context.model.joint_curvature(index) else {
+                            continue;
+                        };
+
+                        ui.label(format!("#{}", index + 1));
+                        ui.label(kind);
+                        ui.label(format!("{:.4}", left));
+                        ui.label(format!("{:.4}", right));
+                        ui.end_row();
+                    }
+                });
+            });
+
             Grid::new("PointStrokes").num_columns(2).show(ui, |ui| {
                 ui.label("Radius:");
                 ui.add(
@@ -130,12 +196,30 @@ pub fn show_panel(context: &mut Context, canvas: &mut Canvas, ui: &mut egui::Ui)
 
                 ui.end_row();
 
+                ui.label("Control (G2) Point:");
+                egui::color_picker::color_edit_button_srgba(
+                    ui,
+                    &mut context.model.fill_curvature,
+                    egui::color_picker::Alpha::Opaque,
+                );
+
+                ui.end_row();
+
                 ui.label("Defining Point:");
                 egui::color_picker::color_edit_button_srgba(
                     ui,
                     &mut context.model.fill_defining,
                     egui::color_picker::Alpha::Opaque,
                 );
+
+                ui.end_row();
+
+                ui.label("Selected Point:");
+                egui::color_picker::color_edit_button_srgba(
+                    ui,
+                    &mut context.model.fill_selected,
+                    egui::color_picker::Alpha::Opaque,
+                );
             });
 
             ui.add_space(10.0);
@@ -149,6 +233,213 @@ pub fn show_panel(context: &mut Context, canvas: &mut Canvas, ui: &mut egui::Ui)
 
         ui.add_space(10.0);
 
+        ui.collapsing("Curvature Settings", |ui| {
+            ui.checkbox(&mut context.model.is_comb_enabled, "Curvature Comb")
+                .on_hover_text("Display spikes along the normal of every segment, their length is proportional to curvature.");
+
+            ui.checkbox(&mut context.model.is_continuity_enabled, "Joint Continuity")
+                .on_hover_text("Display measured continuity of every joint: C0, G1, C1 or G2.");
+
+            ui.add_space(5.0);
+
+            Grid::new("CurvatureGrid").num_columns(2).show(ui, |ui| {
+                ui.label("Comb Scale:");
+                ui.add(
+                    DragValue::new(&mut context.model.comb_scale)
+                        .speed(0.05)
+                        .range(0.0..=10.0),
+                );
+
+                ui.end_row();
+
+                ui.label("Comb Samples:");
+                ui.add(
+                    DragValue::new(&mut context.model.comb_samples)
+                        .speed(1)
+                        .range(1..=100),
+                );
+
+                ui.end_row();
+
+                ui.label("Tolerance:");
+                ui.add(
+                    DragValue::new(&mut context.model.continuity_tolerance)
+                        .speed(0.001)
+                        .range(0.0001..=0.5),
+                )
+                .on_hover_text("Relative tolerance used to compare tangents, derivatives and curvatures.");
+
+                ui.end_row();
+
+                ui.label("Comb:");
+                ui.add(&mut context.model.comb_stroke);
+
+                ui.end_row();
+
+                ui.label("Labels:");
+                egui::color_picker::color_edit_button_srgba(
+                    ui,
+                    &mut context.model.continuity_color,
+                    egui::color_picker::Alpha::Opaque,
+                );
+            });
+
+            ui.add_space(10.0);
+
+            ui.vertical_centered(|ui| {
+                if ui.button("Reset Settings").clicked() {
+                    let default = Model::default();
+
+                    context.model.comb_stroke = default.comb_stroke;
+                    context.model.continuity_color = default.continuity_color;
+                    context.model.comb_scale = default.comb_scale;
+                    context.model.comb_samples = default.comb_samples;
+                    context.model.continuity_tolerance = default.continuity_tolerance;
+                    context.model.is_comb_enabled = default.is_comb_enabled;
+                    context.model.is_continuity_enabled = default.is_continuity_enabled;
+                }
+            });
+        });
+
+        ui.add_space(10.0);
+
+        ui.collapsing("Curve Projection Settings", |ui| {
+            ui.checkbox(&mut context.model.is_projection_enabled, "Closest Point")
+                .on_hover_text("Hover the curve to see the closest point on it, click to select the segment.");
+            ui.checkbox(&mut context.model.is_snapping_enabled, "Snapping")
+                .on_hover_text("New points of spline and NURBS are placed on the curve, when the pointer is near it.");
+
+            ui.add_space(5.0);
+
+            Grid::new("ProjectionGrid").num_columns(2).show(ui, |ui| {
+                ui.label("Snap Radius (px):");
+                ui.add(
+                    DragValue::new(&mut context.model.snap_radius)
+                        .speed(1)
+                        .range(1.0..=100.0),
+                );
+
+                ui.end_row();
+
+                ui.label("Color:");
+                egui::color_picker::color_edit_button_srgba(
+                    ui,
+                    &mut context.model.projection_color,
+                    egui::color_picker::Alpha::Opaque,
+                );
+            });
+
+            ui.add_space(5.0);
+
+            match context.model.selected {
+                Some(selected) => {
+                    ui.label(RichText::new("Selected:").strong());
+                    ui.label(format!("X: {:.3}, Y: {:.3}", selected.point.x, selected.point.y));
+                    ui.label(selected.details());
+
+                    ui.add_space(5.0);
+
+                    ui.vertical_centered_justified(|ui| {
+                        if ui
+                            .button("Split Segment")
+                            .on_hover_text("Insert control point at the selected position, shape of the curve is not changed.")
+                            .clicked()
+                        {
+                            context.animation_settings.is_running = false;
+                            context.model.split_segment(selected.segment, selected.t);
+                        }
+
+                        if ui.button("Clear Selection").clicked() {
+                            context.model.selected = None;
+                        }
+                    });
+                },
+                None => {
+                    ui.label("Click the curve to select the segment.");
+                },
+            }
+        });
+
+        ui.add_space(10.0);
+
+        ui.collapsing("Constraint Settings", |ui| {
+            ui.label("Right click on the point of skeleton to set its smoothness and constraints.");
+
+            ui.add_space(5.0);
+
+            ui.label(RichText::new("Mirror Axis:").strong());
+            ui.horizontal(|ui| {
+                ui.radio_value(&mut context.model.mirror_axis, MirrorAxis::Vertical, "Vertical");
+                ui.radio_value(&mut context.model.mirror_axis, MirrorAxis::Horizontal, "Horizontal");
+            });
+
+            Grid::new("ConstraintsGrid").num_columns(2).show(ui, |ui| {
+                ui.label("Position:");
+                ui.add(
+                    DragValue::new(&mut context.model.mirror_position)
+                        .speed(0.01)
+                        .range(-100.0..=100.0),
+                );
+
+                ui.end_row();
+
+                ui.label("Stroke:");
+                ui.add(&mut context.model.mirror_stroke);
+            });
+
+            ui.add_space(5.0);
+
+            let source = context.model.mirror_source.and_then(|source| {
+                context
+                    .model
+                    .points
+                    .iter()
+                    .position(|bezier| bezier.point.id == source)
+            });
+            match source {
+                Some(index) => ui.label(format!("Mirror Source: #{}", index + 1)),
+                None => ui.label("Mirror Source: None"),
+            };
+            ui.label(format!("Mirror Links: {}", context.model.mirror_links()));
+            ui.label(format!("Mirror Selection: {}", context.model.mirror_selection.len()));
+
+            ui.add_space(5.0);
+
+            ui.vertical_centered_justified(|ui| {
+                if ui
+                    .button("Apply Constraints")
+                    .on_hover_text("Move linked points and defining points to satisfy constraints after changing the axis.")
+                    .clicked()
+                {
+                    context.model.apply_constraints();
+                }
+
+                let has_selection = !context.model.mirror_selection.is_empty();
+                if ui
+                    .add_enabled(has_selection, egui::Button::new("Mirror Selection"))
+                    .on_hover_text("Append linked mirrored copies of the chain from the first to the last selected point.")
+                    .on_disabled_hover_text("Add points to mirror selection from the right click menu first.")
+                    .clicked()
+                {
+                    context.animation_settings.is_running = false;
+                    context.model.mirror_selected();
+                }
+
+                if ui
+                    .add_enabled(has_selection, egui::Button::new("Clear Mirror Selection"))
+                    .clicked()
+                {
+                    context.model.mirror_selection.clear();
+                }
+
+                if ui.button("Clear Constraints").clicked() {
+                    context.model.clear_constraints();
+                }
+            });
+        });
+
+        ui.add_space(10.0);
+
         ui.collapsing("Model Settings", |ui| {
             Grid::new("PointStrokes").num_columns(2).show(ui, |ui| {
                 ui.label("Stroke:");
@@ -173,6 +464,567 @@ pub fn show_panel(context: &mut Context, canvas: &mut Canvas, ui: &mut egui::Ui)
 
         ui.add_space(10.0);
 
+        ui.collapsing("Spline Settings", |ui| {
+            ui.checkbox(&mut context.spline.is_enabled, "Enable Spline")
+                .on_hover_text("Click on the canvas to add a pass-through point, right click on the point to remove it.");
+
+            ui.add_space(5.0);
+
+            ui.label(RichText::new("Method:").strong());
+            ui.radio_value(&mut context.spline.method, SplineMethod::CatmullRom, "Catmull-Rom");
+            ui.radio_value(&mut context.spline.method, SplineMethod::NaturalCubic, "Natural Cubic");
+            ui.radio_value(&mut context.spline.method, SplineMethod::KochanekBartels, "Kochanek-Bartels");
+
+            ui.add_space(5.0);
+
+            match context.spline.method {
+                SplineMethod::CatmullRom => {
+                    ui.label(RichText::new("Parameterization:").strong());
+                    ui.radio_value(&mut context.spline.parameterization, Parameterization::Uniform, "Uniform");
+                    ui.radio_value(&mut context.spline.parameterization, Parameterization::Centripetal, "Centripetal");
+                    ui.radio_value(&mut context.spline.parameterization, Parameterization::Chordal, "Chordal");
+                },
+                SplineMethod::NaturalCubic => {},
+                SplineMethod::KochanekBartels => {
+                    Grid::new("KochanekBartelsGrid").num_columns(2).show(ui, |ui| {
+                        ui.label("Tension:");
+                        ui.add(
+                            DragValue::new(&mut context.spline.tension)
+                                .speed(0.01)
+                                .range(-1.0..=1.0),
+                        );
+
+                        ui.end_row();
+
+                        ui.label("Bias:");
+                        ui.add(
+                            DragValue::new(&mut context.spline.bias)
+                                .speed(0.01)
+                                .range(-1.0..=1.0),
+                        );
+                    });
+                },
+            }
+
+            ui.add_space(5.0);
+
+            Grid::new("SplineGrid").num_columns(2).show(ui, |ui| {
+                ui.label("Samples:");
+                ui.add(
+                    DragValue::new(&mut context.spline.samples)
+                        .speed(1)
+                        .range(1..=100),
+                );
+
+                ui.end_row();
+
+                ui.label("Quadratic Pieces:")
+                    .on_hover_text("Number of quadratic segments per spline segment, when converting to Bezier chain.");
+                ui.add(
+                    DragValue::new(&mut context.spline.pieces)
+                        .speed(1)
+                        .range(1..=8),
+                );
+
+                ui.end_row();
+
+                ui.label("Stroke:");
+                ui.add(&mut context.spline.stroke);
+
+                ui.end_row();
+
+                ui.label("Point:");
+                egui::color_picker::color_edit_button_srgba(
+                    ui,
+                    &mut context.spline.point_color,
+                    egui::color_picker::Alpha::Opaque,
+                );
+            });
+
+            ui.add_space(5.0);
+
+            ui.label(format!("Points: {}", context.spline.points.len()));
+
+            ui.add_space(5.0);
+
+            ui.vertical_centered_justified(|ui| {
+                if ui.button("Take Control Points of Model").clicked() {
+                    context.spline.from_model(&context.model);
+                }
+
+                if ui.button("Clear Points").clicked() {
+                    context.spline.points.clear();
+                }
+
+                let bezier_points = context.spline.to_bezier_points();
+                if ui
+                    .add_enabled(!bezier_points.is_empty(), egui::Button::new("Convert to Bezier Chain"))
+                    .on_hover_text("Replace the model by the chain of quadratic segments, approximating the spline.")
+                    .clicked()
+                {
+                    context.animation_settings.is_running = false;
+                    context.model.points = bezier_points;
+                    context.spline.is_enabled = false;
+                }
+
+                if ui.button("Reset Settings").clicked() {
+                    context.spline = Default::default();
+                }
+            });
+        });
+
+        ui.add_space(10.0);
+
+        ui.collapsing("NURBS Settings", |ui| {
+            ui.checkbox(&mut context.nurbs.is_enabled, "Enable NURBS")
+                .on_hover_text("Click on the canvas to add a control point, right click on the point to remove it.");
+            ui.checkbox(&mut context.nurbs.is_polygon_enabled, "Control Polygon");
+
+            ui.add_space(5.0);
+
+            Grid::new("NurbsGrid").num_columns(2).show(ui, |ui| {
+                ui.label("Degree:");
+                ui.add(
+                    DragValue::new(&mut context.nurbs.degree)
+                        .speed(1)
+                        .range(MIN_DEGREE..=MAX_DEGREE),
+                );
+
+                ui.end_row();
+
+                ui.label("Samples:");
+                ui.add(
+                    DragValue::new(&mut context.nurbs.samples)
+                        .speed(1)
+                        .range(1..=100),
+                );
+
+                ui.end_row();
+
+                ui.label("Curve:");
+                ui.add(&mut context.nurbs.stroke);
+
+                ui.end_row();
+
+                ui.label("Polygon:");
+                ui.add(&mut context.nurbs.polygon_stroke);
+
+                ui.end_row();
+
+                ui.label("Point:");
+                egui::color_picker::color_edit_button_srgba(
+                    ui,
+                    &mut context.nurbs.point_color,
+                    egui::color_picker::Alpha::Opaque,
+                );
+            });
+
+            ui.add_space(5.0);
+
+            ui.label(RichText::new("Knot Vector:").strong());
+            ui.radio_value(&mut context.nurbs.knot_mode, KnotMode::Uniform, "Uniform");
+            ui.radio_value(&mut context.nurbs.knot_mode, KnotMode::Clamped, "Clamped");
+            ui.radio_value(&mut context.nurbs.knot_mode, KnotMode::Custom, "Custom");
+
+            context.nurbs.update_knots();
+
+            let is_custom = context.nurbs.knot_mode == KnotMode::Custom;
+            ui.collapsing("Knots", |ui| {
+                Grid::new("NurbsKnots").num_columns(2).striped(true).show(ui, |ui| {
+                    for (index, knot) in context.nurbs.knots.iter_mut().enumerate() {
+                        ui.label(format!("u{index}:"));
+                        ui.add_enabled(is_custom, DragValue::new(knot).speed(0.01).range(0.0..=1.0));
+                        ui.end_row();
+                    }
+                });
+            });
+
+            ui.collapsing("Weights", |ui| {
+                Grid::new("NurbsWeights").num_columns(2).striped(true).show(ui, |ui| {
+                    for (index, weight) in context.nurbs.weights.iter_mut().enumerate() {
+                        ui.label(format!("Point #{}:", index + 1));
+                        ui.add(DragValue::new(weight).speed(0.01).range(0.01..=10.0));
+                        ui.end_row();
+                    }
+                });
+            });
+
+            ui.add_space(5.0);
+
+            let (start, end) = context.nurbs.domain();
+            ui.horizontal(|ui| {
+                ui.label("Knot:");
+                ui.add(
+                    DragValue::new(&mut context.nurbs.insertion_knot)
+                        .speed(0.01)
+                        .range(start..=end),
+                );
+
+                if ui
+                    .add_enabled(context.nurbs.is_valid(), egui::Button::new("Insert Knot"))
+                    .on_hover_text("Insert the knot without changing the curve, one control point is added.")
+                    .clicked()
+                {
+                    context.nurbs.insert_knot(context.nurbs.insertion_knot);
+                }
+            });
+
+            ui.add_space(5.0);
+
+            ui.vertical_centered_justified(|ui| {
+                if ui.button("Reset Settings").clicked() {
+                    context.nurbs = Default::default();
+                }
+            });
+        });
+
+        ui.add_space(10.0);
+
+        ui.collapsing("Offset Curve Settings", |ui| {
+            ui.checkbox(&mut context.offset_curve.is_enabled, "Enable Offset Curves")
+                .on_hover_text("Contours at the given distance from the model. Their points are displayed and dragged together with the skeleton.");
+            ui.checkbox(&mut context.offset_curve.is_closed, "Closed Contour")
+                .on_hover_text("Join the offset of the last segment with the offset of the first one.");
+
+            ui.add_space(5.0);
+
+            ui.label(RichText::new("Side:").strong());
+            ui.radio_value(&mut context.offset_curve.side, OffsetSide::Outer, "Outer");
+            ui.radio_value(&mut context.offset_curve.side, OffsetSide::Inner, "Inner");
+            ui.radio_value(&mut context.offset_curve.side, OffsetSide::Both, "Both (Stroke Outline)");
+
+            ui.add_space(5.0);
+
+            Grid::new("OffsetCurveGrid").num_columns(2).show(ui, |ui| {
+                ui.label("Distance:");
+                ui.add(
+                    DragValue::new(&mut context.offset_curve.distance)
+                        .speed(0.01)
+                        .range(0.01..=10.0),
+                );
+
+                ui.end_row();
+
+                ui.label("Tolerance:");
+                ui.add(
+                    DragValue::new(&mut context.offset_curve.tolerance)
+                        .speed(0.001)
+                        .range(0.001..=1.0),
+                )
+                .on_hover_text("Maximum distance between exact offset and its approximation by quadratic segments.");
+
+                ui.end_row();
+
+                ui.label("Stroke:");
+                ui.add(&mut context.offset_curve.stroke);
+
+                ui.end_row();
+
+                ui.label("Point:");
+                egui::color_picker::color_edit_button_srgba(
+                    ui,
+                    &mut context.offset_curve.point_color,
+                    egui::color_picker::Alpha::Opaque,
+                );
+            });
+
+            ui.add_space(5.0);
+
+            let segments: usize = context
+                .offset_curve
+                .contours
+                .iter()
+                .map(|points| points.len() / 2)
+                .sum();
+            ui.label(format!("Contours: {}, Segments: {}", context.offset_curve.contours.len(), segments));
+
+            ui.add_space(5.0);
+
+            ui.vertical_centered_justified(|ui| {
+                if ui.button("Generate").clicked() {
+                    context.offset_curve.generate(&context.model);
+                    context.offset_curve.is_enabled = true;
+                }
+
+                let has_contour = !context.offset_curve.contours.is_empty();
+                if ui
+                    .add_enabled(has_contour, egui::Button::new("Replace Model by First Contour"))
+                    .clicked()
+                {
+                    context.animation_settings.is_running = false;
+                    context.model.points = context.offset_curve.contours.remove(0);
+                }
+
+                if ui.button("Reset Settings").clicked() {
+                    context.offset_curve = Default::default();
+                }
+            });
+        });
+
+        ui.add_space(10.0);
+
+        ui.collapsing("Curve Fitting Settings", |ui| {
+            ui.checkbox(&mut context.fitting.is_enabled, "Enable Drawing")
+                .on_hover_text("Drag on the canvas to draw a stroke, it is fitted when the button is released.");
+
+            ui.add_space(5.0);
+
+            Grid::new("FittingGrid").num_columns(2).show(ui, |ui| {
+                ui.label("Tolerance:");
+                ui.add(
+                    DragValue::new(&mut context.fitting.tolerance)
+                        .speed(0.001)
+                        .range(0.001..=1.0),
+                )
+                .on_hover_text("Maximum distance between samples and the fitted curve.");
+
+                ui.end_row();
+
+                ui.label("Corner Angle:");
+                ui.add(
+                    DragValue::new(&mut context.fitting.corner_angle)
+                        .speed(1)
+                        .range(10.0..=170.0)
+                        .suffix("°"),
+                )
+                .on_hover_text("Joints, where the stroke turns sharper, are not smooth.");
+
+                ui.end_row();
+
+                ui.label("Sample Spacing (px):");
+                ui.add(
+                    DragValue::new(&mut context.fitting.sample_spacing)
+                        .speed(0.1)
+                        .range(1.0..=50.0),
+                );
+
+                ui.end_row();
+
+                ui.label("Samples Stroke:");
+                ui.add(&mut context.fitting.samples_stroke);
+
+                ui.end_row();
+
+                ui.label("Stroke:");
+                ui.add(&mut context.fitting.stroke);
+            });
+
+            ui.add_space(5.0);
+
+            ui.label(format!(
+                "Samples: {}, Segments: {}",
+                context.fitting.samples.len(),
+                context.fitting.result.len() / 2
+            ));
+
+            ui.add_space(5.0);
+
+            ui.vertical_centered_justified(|ui| {
+                if ui
+                    .button("Import CSV")
+                    .on_hover_text("Every line contains X and Y of the point (in centimeters).")
+                    .clicked()
+                {
+                    match context.fitting.load_dialog() {
+                        Ok(()) => context.fitting.is_enabled = true,
+                        Err(e) => {
+                            let mut error_message = format!("Error occurred while importing points:\n{}\n", e);
+                            if let Some(additional_info) = e.get_additional_info() {
+                                error_message.push_str(format!("\nAdditional Info:\n{}", additional_info).as_str());
+                            }
+
+                            *error_box = Some(MessageBox::new("Error!".to_string(), error_message));
+                        },
+                    }
+                }
+
+                if ui
+                    .add_enabled(!context.fitting.samples.is_empty(), egui::Button::new("Fit Again"))
+                    .on_hover_text("Fit the samples with the current tolerance and corner angle.")
+                    .clicked()
+                {
+                    context.fitting.fit();
+                }
+
+                if ui
+                    .add_enabled(!context.fitting.result.is_empty(), egui::Button::new("Replace Model by Fitted Curve"))
+                    .clicked()
+                {
+                    context.animation_settings.is_running = false;
+                    context.model.points = context.fitting.result.clone();
+                    context.fitting.is_enabled = false;
+                }
+
+                if ui.button("Reset Settings").clicked() {
+                    context.fitting = Default::default();
+                }
+            });
+        });
+
+        ui.add_space(10.0);
+
+        ui.collapsing("Boolean Operation Settings", |ui| {
+            ui.checkbox(&mut context.boolean.is_enabled, "Enable Boolean Operations")
+                .on_hover_text("Open contours are closed by the chord from the last point to the first one.");
+            ui.checkbox(&mut context.boolean.fill.is_enabled, "Fill Result");
+
+            ui.add_space(5.0);
+
+            let operand_name = |operand: Option<usize>| match operand {
+                None => String::from("Model"),
+                Some(index) => context
+                    .animation_settings
+                    .contours
+                    .get(index)
+                    .map(|contour| contour.name.clone())
+                    .unwrap_or_default(),
+            };
+            let first_name = operand_name(context.boolean.first);
+            let second_name = operand_name(context.boolean.second);
+
+            Grid::new("BooleanOperandsGrid").num_columns(2).show(ui, |ui| {
+                let operands = [
+                    ("First:", &mut context.boolean.first, first_name),
+                    ("Second:", &mut context.boolean.second, second_name),
+                ];
+
+                for (label, operand, name) in operands {
+                    ui.label(label);
+                    egui::ComboBox::from_id_salt(label)
+                        .selected_text(name)
+                        .show_ui(ui, |ui| {
+                            ui.selectable_value(operand, None, "Model");
+                            for (index, contour) in context.animation_settings.contours.iter().enumerate() {
+                                ui.selectable_value(operand, Some(index), &contour.name);
+                            }
+                        });
+
+                    ui.end_row();
+                }
+            });
+
+            ui.add_space(5.0);
+
+            ui.label(RichText::new("Operation:").strong());
+            ui.radio_value(&mut context.boolean.operation, Operation::Union, "Union");
+            ui.radio_value(&mut context.boolean.operation, Operation::Intersection, "Intersection");
+            ui.radio_value(&mut context.boolean.operation, Operation::Difference, "Difference");
+
+            ui.add_space(5.0);
+
+            Grid::new("BooleanStyleGrid").num_columns(2).show(ui, |ui| {
+                ui.label("Stroke:");
+                ui.add(&mut context.boolean.stroke);
+
+                ui.end_row();
+
+                ui.label("Point:");
+                egui::color_picker::color_edit_button_srgba(
+                    ui,
+                    &mut context.boolean.point_color,
+                    egui::color_picker::Alpha::Opaque,
+                );
+
+                ui.end_row();
+
+                ui.label("Fill Rule:");
+                ui.horizontal(|ui| {
+                    ui.radio_value(&mut context.boolean.fill.rule, FillRule::EvenOdd, "Even-Odd");
+                    ui.radio_value(&mut context.boolean.fill.rule, FillRule::NonZero, "Non-Zero");
+                });
+
+                ui.end_row();
+
+                ui.label("Fill Color:");
+                egui::color_picker::color_edit_button_srgba(
+                    ui,
+                    &mut context.boolean.fill.color,
+                    egui::color_picker::Alpha::Opaque,
+                );
+
+                ui.end_row();
+
+                ui.label("Opacity:");
+                ui.add(egui::Slider::new(&mut context.boolean.fill.opacity, 0.0..=1.0));
+            });
+
+            ui.add_space(5.0);
+
+            ui.label(format!("Contours: {}", context.boolean.result.len()));
+            if context.boolean.open_chains > 0 {
+                ui.label(
+                    RichText::new(format!(
+                        "Dropped {} open chains, result may be incomplete.",
+                        context.boolean.open_chains
+                    ))
+                    .color(colors::RED),
+                );
+            }
+
+            ui.add_space(5.0);
+
+            ui.vertical_centered_justified(|ui| {
+                if ui.button("Compute").clicked() {
+                    let operand = |operand: Option<usize>| match operand {
+                        None => context.model.points.clone(),
+                        Some(index) => context
+                            .animation_settings
+                            .contours
+                            .get(index)
+                            .map(|contour| contour.points.clone())
+                            .unwrap_or_default(),
+                    };
+                    let (first, second) = (operand(context.boolean.first), operand(context.boolean.second));
+
+                    context.boolean.compute(&first, &second);
+                    context.boolean.is_enabled = true;
+                }
+
+                let has_result = !context.boolean.result.is_empty();
+                if ui
+                    .add_enabled(has_result, egui::Button::new("Replace Model by Result"))
+                    .on_hover_text("Only the first contour of the result is taken.")
+                    .clicked()
+                {
+                    context.animation_settings.is_running = false;
+                    context.model.points = context.boolean.result[0].clone();
+                }
+
+                if ui
+                    .add_enabled(has_result, egui::Button::new("Save Result to Contours"))
+                    .clicked()
+                {
+                    let name = context.animation_settings.contour_name.clone();
+                    for (index, points) in context.boolean.result.iter().enumerate() {
+                        context.animation_settings.contours.push(Contour {
+                            name: format!("{} {}", name, index + 1),
+                            points: points.clone(),
+                        });
+                    }
+                }
+
+                if ui
+                    .add_enabled(has_result, egui::Button::new("Export SVG"))
+                    .clicked()
+                {
+                    if let Err(e) = context.boolean.save_dialog() {
+                        let mut error_message = format!("Error occurred while exporting SVG:\n{}\n", e);
+                        if let Some(additional_info) = e.get_additional_info() {
+                            error_message.push_str(format!("\nAdditional Info:\n{}", additional_info).as_str());
+                        }
+
+                        *error_box = Some(MessageBox::new("Error!".to_string(), error_message));
+                    }
+                }
+
+                if ui.button("Reset Settings").clicked() {
+                    context.boolean = Default::default();
+                }
+            });
+        });
+
+        ui.add_space(10.0);
+
         ui.checkbox(&mut context.model.is_skeleton_enabled, "Display Skeleton");
 
         ui.add_space(10.0);
@@ -192,9 +1044,104 @@ pub fn show_panel(context: &mut Context, canvas: &mut Canvas, ui: &mut egui::Ui)
 
                 ui.vertical_centered(|ui| {
                     if ui.button("Start / Stop").clicked() {
-                        context.animation_settings.checkout_status(&mut context.model);
+                        context.animation_settings.checkout_status();
+                    }
+                });
+            });
+
+            if let Some(e) = context.animation_settings.error.take() {
+                let mut error_message = format!("Error occurred while morphing contours:\n{}\n", e);
+                if let Some(additional_info) = e.get_additional_info() {
+                    error_message.push_str(format!("\nAdditional Info:\n{}", additional_info).as_str());
+                }
+
+                *error_box = Some(MessageBox::new("Error!".to_string(), error_message));
+            }
+
+            ui.add_space(5.0);
+
+            let animation = &mut context.animation_settings;
+
+            Grid::new("AnimationGrid").num_columns(2).show(ui, |ui| {
+                ui.label("Start:");
+                egui::ComboBox::from_id_salt("AnimationStart")
+                    .selected_text(animation.contours.get(animation.start).map_or("", |contour| &contour.name))
+                    .show_ui(ui, |ui| {
+                        for (index, contour) in animation.contours.iter().enumerate() {
+                            ui.selectable_value(&mut animation.start, index, &contour.name);
+                        }
+                    });
+
+                ui.end_row();
+
+                ui.label("End:");
+                egui::ComboBox::from_id_salt("AnimationEnd")
+                    .selected_text(animation.contours.get(animation.end).map_or("", |contour| &contour.name))
+                    .show_ui(ui, |ui| {
+                        for (index, contour) in animation.contours.iter().enumerate() {
+                            ui.selectable_value(&mut animation.end, index, &contour.name);
+                        }
+                    });
+
+                ui.end_row();
+
+                ui.label("Steps:");
+                ui.add(
+                    DragValue::new(&mut animation.max_steps)
+                        .speed(1)
+                        .range(10..=1000),
+                );
+            });
+
+            ui.add_space(5.0);
+
+            ui.label(RichText::new("Interpolation:").strong());
+            ui.radio_value(&mut animation.interpolation, Interpolation::Linear, "Linear");
+            ui.radio_value(&mut animation.interpolation, Interpolation::Eased, "Eased");
+
+            ui.label(RichText::new("Correspondence:").strong());
+            ui.radio_value(&mut animation.correspondence, Correspondence::Parameter, "By Segments")
+                .on_hover_text("Points at equal fractions of segments count are matched.");
+            ui.radio_value(&mut animation.correspondence, Correspondence::ArcLength, "By Arc Length")
+                .on_hover_text("Points at equal fractions of contour length are matched.");
+
+            ui.add_space(5.0);
+
+            ui.checkbox(&mut animation.is_ping_pong, "Ping-Pong")
+                .on_hover_text("Play forward and backward. Otherwise animation stops at the end.");
+
+            let max_steps = animation.max_steps;
+            animation.step = animation.step.min(max_steps);
+            ui.horizontal(|ui| {
+                ui.label("Scrub:");
+                if ui.add(egui::Slider::new(&mut animation.step, 0..=max_steps)).changed() {
+                    animation.apply(&mut context.model);
+                }
+            });
+
+            ui.add_space(5.0);
+
+            ui.collapsing("Contours", |ui| {
+                ui.horizontal(|ui| {
+                    ui.text_edit_singleline(&mut animation.contour_name);
+                    if ui.button("Save Current").clicked() {
+                        animation.save_contour(&context.model);
                     }
                 });
+
+                let mut removed_contour_index: Option<usize> = None;
+                for (index, contour) in animation.contours.iter().enumerate() {
+                    ui.horizontal(|ui| {
+                        ui.label(format!("{} ({} points)", contour.name, contour.points.len()));
+                        if ui.small_button("Remove").clicked() {
+                            removed_contour_index = Some(index);
+                        }
+                    });
+                }
+
+                if let Some(index) = removed_contour_index {
+                    animation.remove_contour(index);
+                }
             });
         });
 
@@ -299,6 +1246,11 @@ fn reset_to_defaults(context: &mut Context, canvas: &mut Canvas) {
     canvas.screen_params = Default::default();
     context.grid = Default::default();
     context.model = Default::default();
+    context.spline = Default::default();
+    context.nurbs = Default::default();
+    context.offset_curve = Default::default();
+    context.boolean = Default::default();
+    context.fitting = Default::default();
     context.animation_settings = Default::default();
     context.euclidean_offset = Default::default();
     context.euclidean_rotation = Default::default();
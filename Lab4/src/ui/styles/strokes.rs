@@ -31,3 +31,35 @@ pub fn skeleton_dark_grey(width: f32) -> Stroke {
 pub fn tangent_pink(width: f32) -> Stroke {
     Stroke::new(width, colors::PINK)
 }
+
+pub fn comb_blue(width: f32) -> Stroke {
+    Stroke::new(width, colors::BLUE)
+}
+
+pub fn spline_blue(width: f32) -> Stroke {
+    Stroke::new(width, colors::BLUE)
+}
+
+pub fn nurbs_purple(width: f32) -> Stroke {
+    Stroke::new(width, colors::PURPLE)
+}
+
+pub fn offset_orange(width: f32) -> Stroke {
+    Stroke::new(width, colors::ORANGE)
+}
+
+pub fn boolean_lime(width: f32) -> Stroke {
+    Stroke::new(width, colors::LIME)
+}
+
+pub fn mirror_axis_red(width: f32) -> Stroke {
+    Stroke::new(width, colors::DARK_RED)
+}
+
+pub fn fitting_red(width: f32) -> Stroke {
+    Stroke::new(width, colors::RED)
+}
+
+pub fn samples_gray(width: f32) -> Stroke {
+    Stroke::new(width, colors::DARK_GRAY)
+}
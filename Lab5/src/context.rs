@@ -1,8 +1,13 @@
 use crate::graphics::axes::Axes;
 use crate::graphics::model::Model;
+use crate::graphics::multiview::MultiView;
+use crate::graphics::rasterizer::Rasterizer;
+use crate::graphics::visibility::Visibility;
 use crate::operations::animation::Animation;
 use crate::projections::orthographic::OrthographicProjection;
+use crate::projections::perspective::PerspectiveProjection;
 use crate::projections::trimetric::TrimetricProjection;
+use crate::transformations::arcball::Arcball;
 use crate::transformations::offset::Offset;
 use crate::transformations::rotation::Rotation;
 
@@ -11,12 +16,17 @@ pub struct Context {
     pub axes: Axes,
 
     pub model: Model,
+    pub visibility: Visibility,
+    pub rasterizer: Rasterizer,
 
     pub trimetric: TrimetricProjection,
     pub orthographic: OrthographicProjection,
+    pub perspective: PerspectiveProjection,
+    pub multi_view: MultiView,
 
     pub animation: Animation,
 
     pub offset: Offset,
     pub rotation: Rotation,
+    pub arcball: Arcball,
 }
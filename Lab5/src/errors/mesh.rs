@@ -0,0 +1,32 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum MeshError {
+    #[error("Cannot open file using specified path.")]
+    CantOpenFile(String),
+
+    #[error("Cannot save file using specified path.")]
+    CantSaveFile(String),
+
+    #[error("File format is not supported.")]
+    UnsupportedFormat(String),
+
+    #[error("Failed to parse file.")]
+    ParsingFailed(String),
+
+    #[error("File contains no edges.")]
+    EmptyMesh,
+}
+
+impl MeshError {
+    pub fn get_additional_info(&self) -> Option<&String> {
+        // Match only variants with String fields dynamically
+        match self {
+            Self::CantOpenFile(s)
+            | Self::CantSaveFile(s)
+            | Self::UnsupportedFormat(s)
+            | Self::ParsingFailed(s) => Some(s),
+            _ => None,
+        }
+    }
+}
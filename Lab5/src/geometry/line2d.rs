@@ -8,15 +8,36 @@ pub struct Line2D {
     pub end: Point2D,
 
     pub stroke: Stroke,
+    pub is_dashed: bool,
 }
 
+// Dashes of hidden lines (in pixels).
+const DASH_LENGTH_PX: f32 = 6.0;
+const GAP_LENGTH_PX: f32 = 4.0;
+
 impl Line2D {
     pub fn new(start: Point2D, end: Point2D, stroke: Stroke) -> Self {
-        Self { start, end, stroke }
+        Self {
+            start,
+            end,
+            stroke,
+            is_dashed: false,
+        }
     }
 
     pub fn to_shape(&self) -> Shape {
-        Shape::line(vec![self.start.to_pos2(), self.end.to_pos2()], self.stroke)
+        let points = [self.start.to_pos2(), self.end.to_pos2()];
+
+        if self.is_dashed {
+            Shape::Vec(Shape::dashed_line(
+                &points,
+                self.stroke,
+                DASH_LENGTH_PX,
+                GAP_LENGTH_PX,
+            ))
+        } else {
+            Shape::line(points.to_vec(), self.stroke)
+        }
     }
 
     pub fn to_screen(&self, screen_params: ScreenParams) -> Self {
@@ -24,6 +45,7 @@ impl Line2D {
             start: self.start.to_screen(screen_params),
             end: self.end.to_screen(screen_params),
             stroke: self.stroke,
+            is_dashed: self.is_dashed,
         }
     }
 
@@ -36,6 +58,7 @@ impl Line2D {
             start,
             end,
             stroke: Stroke::default(),
+            is_dashed: false,
         }
     }
 
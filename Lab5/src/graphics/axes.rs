@@ -9,6 +9,7 @@ pub const DEFAULT_UNIT_LENGTH: f32 = 1.0;
 
 pub struct Axes {
     pub are_enabled: bool,
+    pub are_labels_enabled: bool,
 
     pub origin: Point3D,
 
@@ -30,6 +31,7 @@ impl Default for Axes {
     fn default() -> Self {
         Self {
             are_enabled: false,
+            are_labels_enabled: true,
 
             origin: Point3D::new(0.0, 0.0, 0.0),
             unit_x: Point3D::new(DEFAULT_UNIT_LENGTH, 0.0, 0.0),
@@ -63,6 +65,15 @@ impl Axes {
         vec![axis_x, axis_y, axis_z]
     }
 
+    // Ends of axes with their names.
+    pub fn labels(&self) -> Vec<(Point3D, &'static str, Color32)> {
+        vec![
+            (self.unit_x, "X", self.color_x),
+            (self.unit_y, "Y", self.color_y),
+            (self.unit_z, "Z", self.color_z),
+        ]
+    }
+
     fn sync_stroke_colors(&mut self) {
         self.stroke_x.color = self.color_x;
         self.stroke_y.color = self.color_y;
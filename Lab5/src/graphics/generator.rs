@@ -0,0 +1,326 @@
+use crate::geometry::point3d::Point3D;
+use crate::graphics::mesh::Mesh;
+use crate::math::angle::Angle;
+use std::f32::consts::PI;
+
+// Scale of the top, below which it is replaced by the apex.
+const APEX_EPSILON: f32 = 1e-3;
+// Quad is flat, if the distance of its vertex from the plane is that small (relative to sides).
+const FLATNESS_EPSILON: f32 = 1e-4;
+// Bevel does not grow sharp corners more than that (relative to the bevel).
+const MAX_MITER: f32 = 4.0;
+// Part of the inset, after which an edge of the profile collapses or a vertex crosses the centre,
+// that the bevel can take.
+const MAX_INSET_PART: f32 = 0.5;
+
+// Solid from the polygon, extruded along OZ: the bottom lies on z = 0.
+#[derive(Clone, PartialEq)]
+pub struct Generator {
+    pub profile: Profile,
+    pub solid: Solid,
+
+    // Rays of the star or sides of the polygon.
+    pub rays: usize,
+    pub inner_radius: f32,
+
+    // Rotation of the top relative to the bottom.
+    pub twist_deg: f32,
+    // Scale of the top: 1 for prism, 0 for pyramid.
+    pub taper: f32,
+    // Size of chamfers on edges of the bottom and the top.
+    pub bevel: f32,
+}
+
+impl Default for Generator {
+    fn default() -> Self {
+        Self {
+            profile: Profile::Star,
+            solid: Solid::Prism,
+
+            rays: 5,
+            inner_radius: 2.5,
+
+            twist_deg: 0.0,
+            taper: 1.0,
+            bevel: 0.0,
+        }
+    }
+}
+
+#[derive(Clone, PartialEq)]
+pub enum Profile {
+    Star,
+    Polygon,
+}
+
+#[derive(Clone, PartialEq)]
+pub enum Solid {
+    Prism,
+    // Top is turned by half of the step between vertices, sides are triangles.
+    Antiprism,
+}
+
+// Copy of the profile at the height: single point, if it is collapsed into the apex.
+struct Ring {
+    height: f32,
+    scale: f32,
+    angle: f32,
+    inset: f32,
+}
+
+impl Generator {
+    pub fn name(&self) -> String {
+        let profile = match self.profile {
+            Profile::Star => format!("{}-Ray Star", self.rays),
+            Profile::Polygon => format!("{}-Gon", self.rays),
+        };
+
+        match self.solid {
+            Solid::Prism if self.taper < APEX_EPSILON => format!("{profile} Pyramid"),
+            Solid::Prism if (self.taper - 1.0).abs() >= APEX_EPSILON => {
+                format!("{profile} Frustum")
+            },
+            Solid::Prism => format!("{profile} Prism"),
+            Solid::Antiprism => format!("{profile} Antiprism"),
+        }
+    }
+
+    // Faces go counter-clockwise, when seen from outside.
+    pub fn mesh(&self, radius: f32, thickness: f32) -> Mesh {
+        let profile = self.profile(radius);
+        let rings = self.rings(&profile, thickness);
+
+        let mut vertices: Vec<Point3D> = vec![];
+        let mut indexes: Vec<Vec<usize>> = vec![];
+        for ring in &rings {
+            let start = vertices.len();
+            vertices.extend(Self::ring_points(&profile, ring));
+            indexes.push((start..vertices.len()).collect());
+        }
+
+        let mut faces: Vec<Vec<usize>> = vec![];
+
+        // Caps, if they are not collapsed.
+        if let Some(bottom) = indexes.first().filter(|bottom| bottom.len() > 1) {
+            faces.push(bottom.iter().rev().copied().collect());
+        }
+        if let Some(top) = indexes.last().filter(|top| top.len() > 1) {
+            faces.push(top.clone());
+        }
+
+        for pair in indexes.windows(2) {
+            let (lower, upper) = (&pair[0], &pair[1]);
+            let count = lower.len().max(upper.len());
+
+            for i in 0..count {
+                let next = (i + 1) % count;
+
+                if upper.len() == 1 {
+                    faces.push(vec![lower[i], lower[next], upper[0]]);
+                } else if lower.len() == 1 {
+                    faces.push(vec![lower[0], upper[next], upper[i]]);
+                } else {
+                    faces.extend(Self::side(
+                        [lower[i], lower[next], upper[next], upper[i]],
+                        &vertices,
+                    ));
+                }
+            }
+        }
+
+        Mesh::new(self.name(), vertices, faces, &[])
+    }
+
+    // Polygon on the plane, counter-clockwise. The first ray looks along OY.
+    // https://math.stackexchange.com/questions/3582342/coordinates-of-the-vertices-of-a-five-pointed-star
+    fn profile(&self, radius: f32) -> Vec<(f32, f32)> {
+        let rays = self.rays.max(3);
+        let point = |angle: f32, radius: f32| {
+            let angle = angle + PI / 2.0;
+            (radius * f32::cos(angle), radius * f32::sin(angle))
+        };
+
+        match self.profile {
+            Profile::Star => (0..2 * rays)
+                .map(|k| {
+                    let radius = if k % 2 == 0 {
+                        radius
+                    } else {
+                        self.inner_radius
+                    };
+                    point(k as f32 * PI / rays as f32, radius)
+                })
+                .collect(),
+            Profile::Polygon => (0..rays)
+                .map(|k| point(k as f32 * 2.0 * PI / rays as f32, radius))
+                .collect(),
+        }
+    }
+
+    // Bottom and top with chamfers between them, if there is a bevel.
+    fn rings(&self, profile: &[(f32, f32)], thickness: f32) -> Vec<Ring> {
+        let vertex_count = match self.profile {
+            Profile::Star => 2 * self.rays.max(3),
+            Profile::Polygon => self.rays.max(3),
+        };
+        let half_step = match self.solid {
+            Solid::Prism => 0.0,
+            Solid::Antiprism => PI / vertex_count as f32,
+        };
+        let twist = Angle::from_degree(self.twist_deg).radian();
+
+        let ring = |part: f32, inset: f32, extra_angle: f32| Ring {
+            height: part * thickness,
+            scale: 1.0 + (self.taper - 1.0) * part,
+            angle: twist * part + extra_angle,
+            inset,
+        };
+
+        let bevel = self
+            .bevel
+            .min(thickness / 2.0)
+            .min(Self::max_inset(profile) * MAX_INSET_PART)
+            .max(0.0);
+        if bevel <= 0.0 {
+            return vec![ring(0.0, 0.0, 0.0), ring(1.0, 0.0, half_step)];
+        }
+
+        let part = bevel / thickness;
+        vec![
+            ring(0.0, bevel, 0.0),
+            ring(part, 0.0, 0.0),
+            ring(1.0 - part, 0.0, half_step),
+            ring(1.0, bevel, half_step),
+        ]
+    }
+
+    fn ring_points(profile: &[(f32, f32)], ring: &Ring) -> Vec<Point3D> {
+        if ring.scale.abs() < APEX_EPSILON {
+            return vec![Point3D::new(0.0, 0.0, ring.height)];
+        }
+
+        let (sin, cos) = ring.angle.sin_cos();
+        Self::inset(profile, ring.inset)
+            .into_iter()
+            .map(|(x, y)| {
+                let (x, y) = (x * ring.scale, y * ring.scale);
+                Point3D::new(x * cos - y * sin, x * sin + y * cos, ring.height)
+            })
+            .collect()
+    }
+
+    // Every vertex is moved inside along the bisector, so edges move by the distance.
+    fn inset(profile: &[(f32, f32)], distance: f32) -> Vec<(f32, f32)> {
+        if distance <= 0.0 {
+            return profile.to_vec();
+        }
+
+        profile
+            .iter()
+            .zip(Self::miters(profile))
+            .map(|(&(x, y), ((bisector_x, bisector_y), miter))| {
+                (
+                    x + bisector_x * miter * distance,
+                    y + bisector_y * miter * distance,
+                )
+            })
+            .collect()
+    }
+
+    // Largest inset, before an edge shrinks to nothing or a vertex passes by the centre.
+    fn max_inset(profile: &[(f32, f32)]) -> f32 {
+        let count = profile.len();
+        let miters = Self::miters(profile);
+        let mut limit = f32::MAX;
+
+        for i in 0..count {
+            let (current, next) = (profile[i], profile[(i + 1) % count]);
+            let ((first_x, first_y), first_miter) = miters[i];
+            let ((second_x, second_y), second_miter) = miters[(i + 1) % count];
+
+            // Edge gets shorter by the difference of the shifts of its ends along it.
+            let (x, y) = (next.0 - current.0, next.1 - current.1);
+            let length = f32::sqrt(x * x + y * y);
+            if length > f32::EPSILON {
+                let (x, y) = (x / length, y / length);
+                let speed = first_miter * (first_x * x + first_y * y)
+                    - second_miter * (second_x * x + second_y * y);
+                if speed > f32::EPSILON {
+                    limit = limit.min(length / speed);
+                }
+            }
+
+            // Vertex comes closest to the centre.
+            let approach = -(current.0 * first_x + current.1 * first_y);
+            if approach > 0.0 && first_miter > f32::EPSILON {
+                limit = limit.min(approach / first_miter);
+            }
+        }
+
+        limit
+    }
+
+    // Bisector, looking inside, and the shift of the vertex along it per unit of the inset.
+    fn miters(profile: &[(f32, f32)]) -> Vec<((f32, f32), f32)> {
+        let count = profile.len();
+        let normal = |from: (f32, f32), to: (f32, f32)| {
+            let (x, y) = (to.0 - from.0, to.1 - from.1);
+            let length = f32::sqrt(x * x + y * y).max(f32::EPSILON);
+            (-y / length, x / length)
+        };
+
+        (0..count)
+            .map(|i| {
+                let (previous, current, next) = (
+                    profile[(i + count - 1) % count],
+                    profile[i],
+                    profile[(i + 1) % count],
+                );
+                let first = normal(previous, current);
+                let second = normal(current, next);
+
+                let (x, y) = (first.0 + second.0, first.1 + second.1);
+                let length = f32::sqrt(x * x + y * y);
+                if length < f32::EPSILON {
+                    return ((0.0, 0.0), 0.0);
+                }
+
+                let bisector = (x / length, y / length);
+                let cosine = bisector.0 * first.0 + bisector.1 * first.1;
+                let miter = (1.0 / cosine.max(1.0 / MAX_MITER)).min(MAX_MITER);
+
+                (bisector, miter)
+            })
+            .collect()
+    }
+
+    // Quad between rings or two triangles, if it is not flat (twisted prism or antiprism). The
+    // diagonal goes to the upper vertex, which is turned towards the next lower one.
+    fn side(quad: [usize; 4], vertices: &[Point3D]) -> Vec<Vec<usize>> {
+        let [first, second, third, fourth] = quad.map(|index| vertices[index]);
+        let vector = |from: Point3D, to: Point3D| (to.x - from.x, to.y - from.y, to.z - from.z);
+
+        let (a, b, c) = (
+            vector(first, second),
+            vector(first, fourth),
+            vector(first, third),
+        );
+        let normal = (
+            a.1 * b.2 - a.2 * b.1,
+            a.2 * b.0 - a.0 * b.2,
+            a.0 * b.1 - a.1 * b.0,
+        );
+        let distance = normal.0 * c.0 + normal.1 * c.1 + normal.2 * c.2;
+        let scale = f32::sqrt(normal.0.powf(2.0) + normal.1.powf(2.0) + normal.2.powf(2.0))
+            * f32::sqrt(c.0.powf(2.0) + c.1.powf(2.0) + c.2.powf(2.0));
+
+        if distance.abs() <= FLATNESS_EPSILON * scale {
+            vec![quad.to_vec()]
+        } else {
+            vec![
+                vec![quad[0], quad[1], quad[3]],
+                vec![quad[1], quad[2], quad[3]],
+            ]
+        }
+    }
+}
@@ -0,0 +1,286 @@
+use crate::errors::mesh::MeshError;
+use crate::geometry::line3d::Line3D;
+use crate::geometry::point3d::Point3D;
+use crate::loaders::{obj, off, stl};
+use eframe::epaint::{Color32, Stroke};
+use nalgebra::Vector3;
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+
+// Polygonal mesh. Vertices of faces go counter-clockwise, when seen from outside.
+#[derive(Debug, Default, Clone)]
+pub struct Mesh {
+    pub name: String,
+
+    pub vertices: Vec<Point3D>,
+    pub faces: Vec<Vec<usize>>,
+    // Unique edges of faces and separate polylines.
+    pub edges: Vec<(usize, usize)>,
+    // Indexes of faces, adjacent to every edge (none for polylines).
+    pub edge_faces: Vec<Vec<usize>>,
+    // Colors of faces from the file: empty, if there are none.
+    pub face_colors: Vec<Color32>,
+}
+
+impl Mesh {
+    pub fn new(
+        name: String, vertices: Vec<Point3D>, faces: Vec<Vec<usize>>, polylines: &[Vec<usize>],
+    ) -> Self {
+        let mut known: HashMap<(usize, usize), usize> = HashMap::new();
+        let mut edges: Vec<(usize, usize)> = vec![];
+        let mut edge_faces: Vec<Vec<usize>> = vec![];
+
+        let face_edges = faces.iter().enumerate().flat_map(|(index, face)| {
+            (0..face.len()).map(move |i| (face[i], face[(i + 1) % face.len()], Some(index)))
+        });
+        let polyline_edges = polylines
+            .iter()
+            .flat_map(|polyline| polyline.windows(2).map(|pair| (pair[0], pair[1], None)));
+
+        for (start, end, face) in face_edges.chain(polyline_edges) {
+            if start == end {
+                continue;
+            }
+
+            let edge = *known
+                .entry((start.min(end), start.max(end)))
+                .or_insert_with(|| {
+                    edges.push((start, end));
+                    edge_faces.push(vec![]);
+                    edges.len() - 1
+                });
+            if let Some(face) = face {
+                edge_faces[edge].push(face);
+            }
+        }
+
+        Self {
+            name,
+            vertices,
+            faces,
+            edges,
+            edge_faces,
+            face_colors: vec![],
+        }
+    }
+
+    // Format is chosen by the file extension.
+    pub fn load(path: PathBuf) -> Result<Self, MeshError> {
+        let extension = path
+            .extension()
+            .and_then(|extension| extension.to_str())
+            .map(|extension| extension.to_lowercase())
+            .unwrap_or_default();
+        let name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default();
+
+        let bytes = fs::read(&path)
+            .map_err(|error| MeshError::CantOpenFile(format!("{}: {}", path.display(), error)))?;
+
+        let text = || {
+            String::from_utf8(bytes.clone())
+                .map_err(|_| MeshError::ParsingFailed(String::from("File is not a text file.")))
+        };
+
+        let mut mesh = match extension.as_str() {
+            "obj" => obj::parse(name, &text()?)?,
+            "off" => off::parse(name, &text()?)?,
+            "stl" => stl::parse(name, &bytes)?,
+            _ => {
+                return Err(MeshError::UnsupportedFormat(format!(
+                    "Extension: \"{extension}\""
+                )))
+            },
+        };
+
+        if mesh.edges.is_empty() {
+            return Err(MeshError::EmptyMesh);
+        }
+        mesh.normalize();
+
+        Ok(mesh)
+    }
+
+    // Wavefront OBJ with vertices, faces and separate polylines.
+    pub fn save(&self, path: PathBuf) -> Result<(), MeshError> {
+        fs::write(&path, obj::write(self))
+            .map_err(|error| MeshError::CantSaveFile(format!("{}: {}", path.display(), error)))
+    }
+
+    // Moves the center of bounding box to the origin, all vertices fit into the unit sphere.
+    pub fn normalize(&mut self) {
+        let Some(first) = self.vertices.first() else {
+            return;
+        };
+
+        let (min, max) = self
+            .vertices
+            .iter()
+            .fold((*first, *first), |(min, max), point| {
+                (
+                    Point3D::new(min.x.min(point.x), min.y.min(point.y), min.z.min(point.z)),
+                    Point3D::new(max.x.max(point.x), max.y.max(point.y), max.z.max(point.z)),
+                )
+            });
+        let center = Point3D::new(
+            (min.x + max.x) / 2.0,
+            (min.y + max.y) / 2.0,
+            (min.z + max.z) / 2.0,
+        );
+
+        let radius = self
+            .vertices
+            .iter()
+            .map(|point| {
+                f32::sqrt(
+                    (point.x - center.x).powf(2.0)
+                        + (point.y - center.y).powf(2.0)
+                        + (point.z - center.z).powf(2.0),
+                )
+            })
+            .fold(0.0, f32::max);
+        let scale = if radius > f32::EPSILON {
+            1.0 / radius
+        } else {
+            1.0
+        };
+
+        self.vertices.iter_mut().for_each(|point| {
+            point.x = (point.x - center.x) * scale;
+            point.y = (point.y - center.y) * scale;
+            point.z = (point.z - center.z) * scale;
+        });
+    }
+
+    pub fn scale(&mut self, factor: f32) {
+        self.vertices.iter_mut().for_each(|point| {
+            point.x *= factor;
+            point.y *= factor;
+            point.z *= factor;
+        });
+    }
+
+    // Newell's method: works for non-planar and non-convex faces. Points outside, if vertices go
+    // counter-clockwise.
+    pub fn normal(&self, face: usize) -> Vector3<f32> {
+        let face = &self.faces[face];
+
+        (0..face.len())
+            .map(|i| {
+                let current = self.vertices[face[i]];
+                let next = self.vertices[face[(i + 1) % face.len()]];
+
+                Vector3::new(
+                    (current.y - next.y) * (current.z + next.z),
+                    (current.z - next.z) * (current.x + next.x),
+                    (current.x - next.x) * (current.y + next.y),
+                )
+            })
+            .sum()
+    }
+
+    // Average of normals of adjacent faces, weighted by their areas.
+    pub fn vertex_normals(&self) -> Vec<Vector3<f32>> {
+        let mut normals = vec![Vector3::zeros(); self.vertices.len()];
+
+        for face in 0..self.faces.len() {
+            let normal = self.normal(face);
+            self.faces[face]
+                .iter()
+                .for_each(|vertex| normals[*vertex] += normal);
+        }
+
+        normals
+            .into_iter()
+            .map(|normal| normal.try_normalize(f32::EPSILON).unwrap_or_default())
+            .collect()
+    }
+
+    // Ear clipping in the coordinate plane, where the face has the largest projection, so
+    // non-convex faces are split correctly.
+    pub fn triangles(&self, face: usize) -> Vec<[usize; 3]> {
+        let normal = self.normal(face);
+        let (x, y) = dominant_plane(normal);
+        // Orientation of projected face is flipped, if the normal looks along negative axis.
+        let sign = normal[3 - x - y].signum();
+
+        let point = |vertex: usize| {
+            let point = self.vertices[vertex];
+            let coordinates = [point.x, point.y, point.z];
+            (coordinates[x], coordinates[y])
+        };
+        let cross = |first: usize, second: usize, third: usize| {
+            let (a, b, c) = (point(first), point(second), point(third));
+            ((b.0 - a.0) * (c.1 - a.1) - (b.1 - a.1) * (c.0 - a.0)) * sign
+        };
+
+        let mut remaining = self.faces[face].clone();
+        let mut triangles: Vec<[usize; 3]> = Vec::with_capacity(remaining.len().saturating_sub(2));
+
+        while remaining.len() > 3 {
+            let count = remaining.len();
+            let ear = (0..count).find(|i| {
+                let (previous, current, next) = (
+                    remaining[(i + count - 1) % count],
+                    remaining[*i],
+                    remaining[(i + 1) % count],
+                );
+
+                cross(previous, current, next) > 0.0
+                    && remaining.iter().all(|vertex| {
+                        [previous, current, next].contains(vertex)
+                            || cross(previous, current, *vertex) < 0.0
+                            || cross(current, next, *vertex) < 0.0
+                            || cross(next, previous, *vertex) < 0.0
+                    })
+            });
+
+            // Degenerate face has no ears: the rest is split as a fan.
+            let Some(i) = ear else {
+                break;
+            };
+
+            triangles.push([
+                remaining[(i + count - 1) % count],
+                remaining[i],
+                remaining[(i + 1) % count],
+            ]);
+            remaining.remove(i);
+        }
+
+        triangles.extend(
+            (1..remaining.len().saturating_sub(1))
+                .map(|i| [remaining[0], remaining[i], remaining[i + 1]]),
+        );
+
+        triangles
+    }
+
+    pub fn edge_line(&self, edge: usize, stroke: Stroke) -> Line3D {
+        let (start, end) = self.edges[edge];
+
+        Line3D::new(self.vertices[start], self.vertices[end], stroke)
+    }
+
+    pub fn lines(&self, stroke: Stroke) -> Vec<Line3D> {
+        (0..self.edges.len())
+            .map(|edge| self.edge_line(edge, stroke))
+            .collect()
+    }
+}
+
+// Indexes of coordinates of the plane, where the projection of the face is the largest.
+pub fn dominant_plane(normal: Vector3<f32>) -> (usize, usize) {
+    let normal = normal.abs();
+
+    if normal.x >= normal.y && normal.x >= normal.z {
+        (1, 2)
+    } else if normal.y >= normal.z {
+        (2, 0)
+    } else {
+        (0, 1)
+    }
+}
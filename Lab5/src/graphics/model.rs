@@ -1,15 +1,35 @@
-use crate::geometry::line3d::Line3D;
+use crate::errors::mesh::MeshError;
 use crate::geometry::point3d::Point3D;
+use crate::graphics::generator::Generator;
+use crate::graphics::mesh::Mesh;
 use crate::graphics::screen::ScreenParams;
 use crate::ui::styles::strokes;
 use eframe::epaint::Stroke;
+use std::path::Path;
 
-// Star figure.
+// Generated figure or loaded mesh.
 pub struct Model {
     pub radius: f32,
     pub thickness: f32,
+    pub generator: Generator,
+
+    // Replaces the generated figure, scaled by the radius.
+    pub mesh: Option<Mesh>,
 
     pub stroke: Stroke,
+
+    // Figure for the parameters it was built with, rebuilt when any of them changes.
+    cache: Option<(Key, Mesh)>,
+    // Bumped on every load, so that a reloaded mesh is not taken for the cached one.
+    revision: usize,
+}
+
+#[derive(Clone, PartialEq)]
+struct Key {
+    radius: f32,
+    thickness: f32,
+    generator: Generator,
+    loaded: Option<usize>,
 }
 
 impl Default for Model {
@@ -19,69 +39,92 @@ impl Default for Model {
 
             radius: 5.0,
             thickness: 2.5,
+            generator: Default::default(),
+
+            mesh: None,
+
+            cache: None,
+            revision: 0,
         }
     }
 }
 
 impl Model {
-    pub fn lines(&self, screen_params: ScreenParams) -> Vec<Line3D> {
+    pub fn stroke(&self, screen_params: ScreenParams) -> Stroke {
         let mut stroke = self.stroke;
         stroke.width = screen_params.value_cm_to_px(self.stroke.width);
 
-        let mut points: Vec<Point3D> = Vec::with_capacity(10);
-        let mut upper_points: Vec<Point3D> = Vec::with_capacity(10);
-
-        let mut lines: Vec<Line3D> = Vec::with_capacity(30);
-
-        let radius = self.radius;
-        let inner_radius = radius / 2.0;
-
-        let initial_thickness = 0.0;
-        let thickness = self.thickness;
+        stroke
+    }
 
-        for k in 0..=4 {
-            let angle = k as f32 * 2.0 * std::f32::consts::PI / 5.0 + std::f32::consts::PI / 2.0;
-            let offset_angle = angle + 2.0 * std::f32::consts::PI / 10.0;
+    // Loaded mesh, scaled by the radius, or the generated figure. Faces go counter-clockwise from outside.
+    pub fn mesh(&mut self) -> &Mesh {
+        let key = Key {
+            radius: self.radius,
+            thickness: self.thickness,
+            generator: self.generator.clone(),
+            loaded: self.mesh.as_ref().map(|_| self.revision),
+        };
+
+        if !matches!(&self.cache, Some((cached, _)) if *cached == key) {
+            self.cache = None;
+        }
 
-            let outer = Self::point(angle, radius, initial_thickness);
-            let inner = Self::point(offset_angle, inner_radius, initial_thickness);
+        let (_, mesh) = self.cache.get_or_insert_with(|| {
+            let mesh = match &self.mesh {
+                Some(mesh) => {
+                    let mut mesh = mesh.clone();
+                    mesh.scale(self.radius);
+                    mesh
+                },
+                None => self.generator.mesh(self.radius, self.thickness),
+            };
+
+            (key, mesh)
+        });
 
-            let upper_outer = Self::point(angle, radius, thickness);
-            let upper_inner = Self::point(offset_angle, inner_radius, thickness);
+        mesh
+    }
 
-            points.push(outer);
-            points.push(inner);
-            upper_points.push(upper_outer);
-            upper_points.push(upper_inner);
+    pub fn load_dialog(&mut self) -> Result<(), MeshError> {
+        let file_dialog = rfd::FileDialog::new()
+            .add_filter("Models", &["obj", "off", "stl"])
+            .add_filter("Wavefront OBJ", &["obj"])
+            .add_filter("Object File Format", &["off"])
+            .add_filter("Stereolithography", &["stl"]);
 
-            // Add vertical lines directly
-            lines.push(Line3D::new(outer, upper_outer, stroke));
-            lines.push(Line3D::new(inner, upper_inner, stroke));
+        if let Some(path) = file_dialog.pick_file() {
+            self.mesh = Some(Mesh::load(path)?);
+            self.revision += 1;
         }
 
-        // Close the loops
-        points.push(points[0]);
-        upper_points.push(upper_points[0]);
-
-        // Add horizontal lines
-        points.windows(2).for_each(|pair| {
-            lines.push(Line3D::new(pair[0], pair[1], stroke));
-        });
-        upper_points.windows(2).for_each(|pair| {
-            lines.push(Line3D::new(pair[0], pair[1], stroke));
-        });
-
-        lines
+        Ok(())
     }
 
-    // https://math.stackexchange.com/questions/3582342/coordinates-of-the-vertices-of-a-five-pointed-star
-    fn point(angle: f32, radius: f32, thickness: f32) -> Point3D {
-        let x = radius * f32::cos(angle);
-        let y = radius * f32::sin(angle);
-        Point3D::new(x, y, thickness)
+    // Current figure with its faces, without transformations.
+    pub fn save_dialog(&mut self) -> Result<(), MeshError> {
+        let mesh = self.mesh();
+        let file_dialog = rfd::FileDialog::new()
+            .add_filter("Wavefront OBJ", &["obj"])
+            .set_file_name(
+                Path::new(&mesh.name)
+                    .with_extension("obj")
+                    .to_string_lossy(),
+            );
+
+        if let Some(path) = file_dialog.save_file() {
+            mesh.save(path)?;
+        }
+
+        Ok(())
     }
 
     pub fn pivot_point(&self, offset: Point3D) -> Point3D {
+        // Mesh is centered at the origin.
+        if self.mesh.is_some() {
+            return offset;
+        }
+
         Point3D::new(
             0.0 + offset.x,
             0.0 + offset.y,
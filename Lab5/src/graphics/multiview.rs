@@ -0,0 +1,116 @@
+use crate::geometry::line2d::Line2D;
+use crate::geometry::point2d::Point2D;
+use crate::geometry::point3d::Point3D;
+use crate::graphics::screen::ScreenParams;
+use crate::graphics::visibility::Viewer;
+use crate::projections::orthographic::{OrthographicMode, OrthographicProjection};
+use crate::ui::styles::strokes;
+use eframe::epaint::Stroke;
+use egui::{Rect, Vec2};
+use nalgebra::Vector3;
+
+// Four viewports of technical drawing: front, top and side views with the main one.
+pub struct MultiView {
+    pub is_enabled: bool,
+
+    // Lines between the same points of the front view and the top or side ones.
+    pub show_projection_lines: bool,
+    pub projection_stroke: Stroke,
+}
+
+impl Default for MultiView {
+    fn default() -> Self {
+        Self {
+            is_enabled: false,
+
+            show_projection_lines: true,
+            projection_stroke: strokes::projection_gray(),
+        }
+    }
+}
+
+// Third-angle layout: the top view is above the front one, the right side view is to the right
+// of it, the main view takes the free corner.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum View {
+    Top,
+    Main,
+    Front,
+    Side,
+}
+
+impl View {
+    // Orthographic views in the order of viewports.
+    pub const ORTHOGRAPHIC: [View; 3] = [View::Front, View::Top, View::Side];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Top => "Top",
+            Self::Main => "Main",
+            Self::Front => "Front",
+            Self::Side => "Right Side",
+        }
+    }
+
+    // Quarter of the canvas.
+    pub fn rect(&self, canvas: Rect) -> Rect {
+        let size = canvas.size() / 2.0;
+        let min = match self {
+            Self::Top => canvas.min,
+            Self::Main => canvas.min + Vec2::new(size.x, 0.0),
+            Self::Front => canvas.min + Vec2::new(0.0, size.y),
+            Self::Side => canvas.min + size,
+        };
+
+        Rect::from_min_size(min, size)
+    }
+
+    // Unit vector to the viewer for orthographic views.
+    pub fn viewer(&self) -> Option<Viewer> {
+        match self {
+            Self::Front => Some(Viewer::Direction(Vector3::z())),
+            Self::Top => Some(Viewer::Direction(Vector3::y())),
+            Self::Side => Some(Viewer::Direction(Vector3::x())),
+            Self::Main => None,
+        }
+    }
+
+    // Point is projected on the coordinate plane, then the plane is turned to the viewer.
+    pub fn point2d(&self, point: Point3D) -> Point2D {
+        let Some(mode) = self.mode() else {
+            return Point2D::new(point.x, point.y);
+        };
+
+        let projection = OrthographicProjection {
+            is_enabled: true,
+            mode,
+            p: 0.0,
+        };
+        let mut points = [point];
+        projection.apply_if_set(&mut points);
+        let [point] = points;
+
+        match self {
+            Self::Top => Point2D::new(point.x, -point.z),
+            Self::Side => Point2D::new(-point.z, point.y),
+            _ => Point2D::new(point.x, point.y),
+        }
+    }
+
+    fn mode(&self) -> Option<OrthographicMode> {
+        match self {
+            Self::Front => Some(OrthographicMode::ZOnZero),
+            Self::Top => Some(OrthographicMode::YOnZero),
+            Self::Side => Some(OrthographicMode::XOnZero),
+            Self::Main => None,
+        }
+    }
+}
+
+// Orthographic view with its own pan and zoom.
+#[derive(Default)]
+pub struct Viewport {
+    pub screen_params: ScreenParams,
+
+    pub lines: Vec<Line2D>,
+}
@@ -0,0 +1,200 @@
+use crate::geometry::point2d::Point2D;
+use crate::geometry::point3d::Point3D;
+use crate::graphics::mesh::Mesh;
+use eframe::epaint::{Color32, ColorImage, Hsva};
+use nalgebra::Vector3;
+
+// Hue step of generated colors of faces: golden ratio keeps neighbours distinct.
+const HUE_STEP: f32 = 0.618_034;
+
+// Solid model, rendered on CPU with the z-buffer.
+pub struct Rasterizer {
+    pub is_enabled: bool,
+    pub show_edges: bool,
+
+    pub shading: Shading,
+    pub coloring: Coloring,
+    pub color: Color32,
+
+    // Direction to the directional light.
+    pub light: Point3D,
+    pub ambient: f32,
+    pub diffuse: f32,
+}
+
+impl Default for Rasterizer {
+    fn default() -> Self {
+        Self {
+            is_enabled: false,
+            show_edges: true,
+
+            shading: Shading::Gouraud,
+            coloring: Coloring::Single,
+            color: Color32::from_rgb(70, 130, 180),
+
+            light: Point3D::new(1.0, 2.0, 3.0),
+            ambient: 0.25,
+            diffuse: 0.75,
+        }
+    }
+}
+
+#[derive(PartialEq)]
+pub enum Shading {
+    // One color per face from its normal.
+    Flat,
+    // Colors are computed at vertices from averaged normals and interpolated.
+    Gouraud,
+}
+
+#[derive(PartialEq)]
+pub enum Coloring {
+    Single,
+    // Colors from the file or generated ones.
+    PerFace,
+}
+
+// Vertex on the picture plane. Greater depth is closer to the viewer.
+#[derive(Debug, Clone, Copy)]
+pub struct ShadedVertex {
+    pub position: Point2D,
+    pub depth: f32,
+    pub color: Color32,
+}
+
+impl Rasterizer {
+    // Lit triangles of faces. Projection gives position and depth, which must change linearly
+    // on the picture plane. Triangles with unprojected vertices are skipped.
+    pub fn triangles(
+        &self, mesh: &Mesh, project: impl Fn(Point3D) -> Option<(Point2D, f32)>,
+    ) -> Vec<[ShadedVertex; 3]> {
+        let projected: Vec<Option<(Point2D, f32)>> =
+            mesh.vertices.iter().map(|point| project(*point)).collect();
+        let vertex_normals = match self.shading {
+            Shading::Flat => vec![],
+            Shading::Gouraud => mesh.vertex_normals(),
+        };
+
+        let mut triangles: Vec<[ShadedVertex; 3]> = vec![];
+        for face in 0..mesh.faces.len() {
+            let color = self.face_color(mesh, face);
+            let face_normal = mesh.normal(face).try_normalize(f32::EPSILON);
+
+            for triangle in mesh.triangles(face) {
+                let vertices = triangle.map(|vertex| {
+                    let (position, depth) = projected[vertex]?;
+                    let normal = match self.shading {
+                        Shading::Flat => face_normal,
+                        Shading::Gouraud => Some(vertex_normals[vertex]),
+                    };
+
+                    Some(ShadedVertex {
+                        position,
+                        depth,
+                        color: self.lit(color, normal.unwrap_or_default()),
+                    })
+                });
+
+                if let [Some(first), Some(second), Some(third)] = vertices {
+                    triangles.push([first, second, third]);
+                }
+            }
+        }
+
+        triangles
+    }
+
+    // Image of the given size in pixels, background is transparent.
+    pub fn render(
+        triangles: &[[ShadedVertex; 3]], size: [usize; 2], to_pixel: impl Fn(Point2D) -> Point2D,
+    ) -> ColorImage {
+        let [width, height] = size;
+        let mut image = ColorImage::new(size, Color32::TRANSPARENT);
+        let mut depths = vec![f32::NEG_INFINITY; width * height];
+
+        for triangle in triangles {
+            let points = triangle.map(|vertex| to_pixel(vertex.position));
+            let area = edge(points[0], points[1], points[2]);
+            if area.abs() < f32::EPSILON {
+                continue;
+            }
+
+            let min_x = points.iter().map(|point| point.x).fold(f32::MAX, f32::min);
+            let max_x = points.iter().map(|point| point.x).fold(f32::MIN, f32::max);
+            let min_y = points.iter().map(|point| point.y).fold(f32::MAX, f32::min);
+            let max_y = points.iter().map(|point| point.y).fold(f32::MIN, f32::max);
+            if max_x < 0.0 || max_y < 0.0 || min_x >= width as f32 || min_y >= height as f32 {
+                continue;
+            }
+
+            let (start_x, end_x) = (min_x.max(0.0) as usize, (max_x as usize).min(width - 1));
+            let (start_y, end_y) = (min_y.max(0.0) as usize, (max_y as usize).min(height - 1));
+
+            for y in start_y..=end_y {
+                for x in start_x..=end_x {
+                    // Barycentric coordinates of the pixel center.
+                    let pixel = Point2D::new(x as f32 + 0.5, y as f32 + 0.5);
+                    let weights = [
+                        edge(points[1], points[2], pixel) / area,
+                        edge(points[2], points[0], pixel) / area,
+                        edge(points[0], points[1], pixel) / area,
+                    ];
+                    if weights.iter().any(|weight| *weight < 0.0) {
+                        continue;
+                    }
+
+                    let index = y * width + x;
+                    let depth: f32 = (0..3).map(|i| triangle[i].depth * weights[i]).sum();
+                    if depth <= depths[index] {
+                        continue;
+                    }
+
+                    depths[index] = depth;
+                    image.pixels[index] = interpolate(triangle, weights);
+                }
+            }
+        }
+
+        image
+    }
+
+    fn face_color(&self, mesh: &Mesh, face: usize) -> Color32 {
+        match self.coloring {
+            Coloring::Single => self.color,
+            Coloring::PerFace => mesh.face_colors.get(face).copied().unwrap_or_else(|| {
+                Hsva::new((face as f32 * HUE_STEP).fract(), 0.5, 0.9, 1.0).into()
+            }),
+        }
+    }
+
+    // Ambient and diffuse (Lambert) terms.
+    fn lit(&self, color: Color32, normal: Vector3<f32>) -> Color32 {
+        let light = Vector3::new(self.light.x, self.light.y, self.light.z)
+            .try_normalize(f32::EPSILON)
+            .unwrap_or_default();
+        let intensity = self.ambient + self.diffuse * normal.dot(&light).max(0.0);
+
+        let channel = |value: u8| (value as f32 * intensity).clamp(0.0, 255.0) as u8;
+        Color32::from_rgb(channel(color.r()), channel(color.g()), channel(color.b()))
+    }
+}
+
+// Doubled signed area of the triangle.
+fn edge(first: Point2D, second: Point2D, point: Point2D) -> f32 {
+    (second.x - first.x) * (point.y - first.y) - (second.y - first.y) * (point.x - first.x)
+}
+
+fn interpolate(triangle: &[ShadedVertex; 3], weights: [f32; 3]) -> Color32 {
+    let channel = |get: fn(&Color32) -> u8| {
+        (0..3)
+            .map(|i| get(&triangle[i].color) as f32 * weights[i])
+            .sum::<f32>()
+            .round() as u8
+    };
+
+    Color32::from_rgb(
+        channel(Color32::r),
+        channel(Color32::g),
+        channel(Color32::b),
+    )
+}
@@ -0,0 +1,397 @@
+use crate::geometry::line3d::Line3D;
+use crate::geometry::point3d::Point3D;
+use crate::graphics::mesh::{dominant_plane, Mesh};
+use crate::ui::styles::strokes;
+use eframe::epaint::Stroke;
+use nalgebra::{Vector2, Vector3};
+
+// Distance (in centimeters), at which a face starts to hide the point behind it.
+const OCCLUSION_EPSILON: f32 = 1e-3;
+// Pieces of edges, shorter than that part of the edge, are not split off.
+const SPLIT_EPSILON: f32 = 1e-4;
+
+pub struct Visibility {
+    pub mode: DisplayMode,
+
+    // Hidden edges are drawn dashed, as in engineering drawings.
+    pub show_hidden: bool,
+    pub hidden_stroke: Stroke,
+}
+
+impl Default for Visibility {
+    fn default() -> Self {
+        Self {
+            mode: DisplayMode::AllEdges,
+
+            show_hidden: false,
+            hidden_stroke: strokes::hidden_gray(0.03),
+        }
+    }
+}
+
+impl Visibility {
+    // Visible and hidden parts of edges. Hidden ones are empty, if they are not shown.
+    pub fn lines(
+        &self, mesh: &Mesh, viewer: Viewer, stroke: Stroke, hidden_stroke: Stroke,
+    ) -> (Vec<Line3D>, Vec<Line3D>) {
+        let (visible, hidden) = match self.mode {
+            DisplayMode::AllEdges => return (mesh.lines(stroke), vec![]),
+            DisplayMode::BackFaceCulling => self.culled(mesh, viewer),
+            DisplayMode::HiddenLineRemoval => HiddenLines::new(mesh, viewer).pieces(),
+        };
+
+        let lines = |pieces: Vec<(usize, f32, f32)>, stroke: Stroke| {
+            pieces
+                .into_iter()
+                .map(|(edge, start, end)| {
+                    let line = mesh.edge_line(edge, stroke);
+                    Line3D::new(lerp(&line, start), lerp(&line, end), stroke)
+                })
+                .collect::<Vec<Line3D>>()
+        };
+
+        let hidden = if self.show_hidden {
+            lines(hidden, hidden_stroke)
+        } else {
+            vec![]
+        };
+
+        (lines(visible, stroke), hidden)
+    }
+
+    // Edge is visible, if any of its faces is front-facing. Separate polylines are always visible.
+    fn culled(&self, mesh: &Mesh, viewer: Viewer) -> (Vec<Piece>, Vec<Piece>) {
+        let is_front = front_faces(mesh, viewer);
+
+        (0..mesh.edges.len())
+            .map(|edge| (edge, 0.0, 1.0))
+            .partition(|(edge, _, _)| {
+                let faces = &mesh.edge_faces[*edge];
+                faces.is_empty() || faces.iter().any(|face| is_front[*face])
+            })
+    }
+}
+
+// Part of the edge between two parameters.
+type Piece = (usize, f32, f32);
+
+#[derive(PartialEq)]
+pub enum DisplayMode {
+    AllEdges,
+    BackFaceCulling,
+    HiddenLineRemoval,
+}
+
+#[derive(Clone, Copy)]
+pub enum Viewer {
+    // Parallel projection: unit vector, which points to the viewer.
+    Direction(Vector3<f32>),
+    // Central projection: position of the eye and unit vector of the view direction.
+    Eye(Vector3<f32>, Vector3<f32>),
+}
+
+impl Viewer {
+    // Unit vector from the point to the viewer and the distance to it.
+    fn ray(&self, point: Vector3<f32>) -> (Vector3<f32>, f32) {
+        match self {
+            Self::Direction(direction) => (*direction, f32::INFINITY),
+            Self::Eye(eye, _) => {
+                let ray = eye - point;
+                (ray.normalize(), ray.norm())
+            },
+        }
+    }
+
+    fn is_front(&self, normal: Vector3<f32>, point: Vector3<f32>) -> bool {
+        normal.dot(&self.ray(point).0) > 0.0
+    }
+
+    // Coordinates on the picture plane, up to an affine map. None, if the point is behind the eye.
+    fn project(&self, point: Vector3<f32>) -> Option<Vector2<f32>> {
+        match self {
+            Self::Direction(direction) => {
+                let (right, up) = basis(*direction);
+                Some(Vector2::new(point.dot(&right), point.dot(&up)))
+            },
+            Self::Eye(eye, forward) => {
+                let (right, up) = basis(*forward);
+                let relative = point - eye;
+                let depth = relative.dot(forward);
+
+                (depth > f32::EPSILON)
+                    .then(|| Vector2::new(relative.dot(&right), relative.dot(&up)) / depth)
+            },
+        }
+    }
+
+    // Normal of the plane, which contains the segment and rays to the viewer through it.
+    fn sweep_normal(&self, start: Vector3<f32>, end: Vector3<f32>) -> Vector3<f32> {
+        match self {
+            Self::Direction(direction) => (end - start).cross(direction),
+            Self::Eye(eye, _) => (start - eye).cross(&(end - eye)),
+        }
+    }
+}
+
+// Appel's idea: visibility of an edge can change only where its projection crosses projection
+// of a contour edge (boundary one or between front and back faces). Edges are split at such
+// crossings, then the middle of every piece is checked by a ray to the viewer.
+struct HiddenLines<'a> {
+    mesh: &'a Mesh,
+    viewer: Viewer,
+
+    points: Vec<Vector3<f32>>,
+    projected: Vec<Option<Vector2<f32>>>,
+
+    normals: Vec<Vector3<f32>>,
+    // Bounding boxes of projected faces: None, if a vertex is behind the eye.
+    bounds: Vec<Option<(Vector2<f32>, Vector2<f32>)>>,
+    is_front: Vec<bool>,
+    contour: Vec<usize>,
+    is_closed: bool,
+}
+
+impl<'a> HiddenLines<'a> {
+    fn new(mesh: &'a Mesh, viewer: Viewer) -> Self {
+        let points: Vec<Vector3<f32>> = mesh
+            .vertices
+            .iter()
+            .map(|point| Vector3::new(point.x, point.y, point.z))
+            .collect();
+        let projected: Vec<Option<Vector2<f32>>> =
+            points.iter().map(|point| viewer.project(*point)).collect();
+
+        let normals: Vec<Vector3<f32>> = (0..mesh.faces.len())
+            .map(|face| mesh.normal(face))
+            .collect();
+        let bounds = mesh
+            .faces
+            .iter()
+            .map(|face| {
+                face.iter().try_fold(
+                    (Vector2::repeat(f32::MAX), Vector2::repeat(f32::MIN)),
+                    |(min, max), vertex| {
+                        let point = projected[*vertex]?;
+                        Some((min.inf(&point), max.sup(&point)))
+                    },
+                )
+            })
+            .collect();
+        let is_front = front_faces(mesh, viewer);
+
+        let contour = (0..mesh.edges.len())
+            .filter(|edge| match mesh.edge_faces[*edge].as_slice() {
+                [] => false,
+                [first, second] => is_front[*first] != is_front[*second],
+                _ => true,
+            })
+            .collect();
+        let is_closed = mesh.edge_faces.iter().all(|faces| faces.len() == 2);
+
+        Self {
+            mesh,
+            viewer,
+            points,
+            projected,
+            normals,
+            bounds,
+            is_front,
+            contour,
+            is_closed,
+        }
+    }
+
+    // Visible and hidden pieces of all edges.
+    fn pieces(&self) -> (Vec<Piece>, Vec<Piece>) {
+        let mut visible: Vec<Piece> = vec![];
+        let mut hidden: Vec<Piece> = vec![];
+
+        for edge in 0..self.mesh.edges.len() {
+            let faces = &self.mesh.edge_faces[edge];
+
+            // Back edges of closed solid are hidden by front faces.
+            if self.is_closed && faces.iter().all(|face| !self.is_front[*face]) {
+                hidden.push((edge, 0.0, 1.0));
+                continue;
+            }
+
+            let parameters = self.splits(edge);
+            let mut start = 0.0;
+            let mut is_start_hidden: Option<bool> = None;
+
+            for pair in parameters.windows(2) {
+                let middle = self.point_on(edge, (pair[0] + pair[1]) / 2.0);
+                let is_hidden = self.is_occluded(middle, faces);
+
+                // Neighbour pieces with the same visibility are merged.
+                if let Some(is_previous_hidden) = is_start_hidden {
+                    if is_previous_hidden != is_hidden {
+                        let pieces = if is_previous_hidden {
+                            &mut hidden
+                        } else {
+                            &mut visible
+                        };
+                        pieces.push((edge, start, pair[0]));
+                        start = pair[0];
+                    }
+                }
+                is_start_hidden = Some(is_hidden);
+            }
+
+            match is_start_hidden {
+                Some(true) => hidden.push((edge, start, 1.0)),
+                _ => visible.push((edge, start, 1.0)),
+            }
+        }
+
+        (visible, hidden)
+    }
+
+    // Sorted parameters of crossings with contour edges, including ends of the edge.
+    fn splits(&self, edge: usize) -> Vec<f32> {
+        let (first, second) = self.mesh.edges[edge];
+        let (start, end) = (self.points[first], self.points[second]);
+
+        let mut parameters = vec![0.0, 1.0];
+
+        for contour in &self.contour {
+            let (contour_first, contour_second) = self.mesh.edges[*contour];
+            if [contour_first, contour_second]
+                .iter()
+                .any(|vertex| *vertex == first || *vertex == second)
+            {
+                continue;
+            }
+
+            let (contour_start, contour_end) =
+                (self.points[contour_first], self.points[contour_second]);
+            let normal = self.viewer.sweep_normal(contour_start, contour_end);
+
+            let denominator = normal.dot(&(end - start));
+            if denominator.abs() < f32::EPSILON {
+                continue;
+            }
+
+            let parameter = normal.dot(&(contour_start - start)) / denominator;
+            if parameter <= SPLIT_EPSILON || parameter >= 1.0 - SPLIT_EPSILON {
+                continue;
+            }
+
+            // Crossing must be within the projection of the contour edge.
+            let crossing = self.viewer.project(start + (end - start) * parameter);
+            let is_within = match (
+                crossing,
+                self.projected[contour_first],
+                self.projected[contour_second],
+            ) {
+                (Some(crossing), Some(contour_start), Some(contour_end)) => {
+                    let direction = contour_end - contour_start;
+                    let length = direction.norm_squared();
+                    let position = (crossing - contour_start).dot(&direction) / length;
+
+                    length < f32::EPSILON || (0.0..=1.0).contains(&position)
+                },
+                _ => true,
+            };
+
+            if is_within {
+                parameters.push(parameter);
+            }
+        }
+
+        parameters.sort_by(f32::total_cmp);
+        parameters.dedup_by(|next, previous| *next - *previous < SPLIT_EPSILON);
+
+        parameters
+    }
+
+    fn point_on(&self, edge: usize, parameter: f32) -> Vector3<f32> {
+        let (first, second) = self.mesh.edges[edge];
+
+        self.points[first] + (self.points[second] - self.points[first]) * parameter
+    }
+
+    // Whether any face, except the given ones, crosses the ray from the point to the viewer.
+    fn is_occluded(&self, point: Vector3<f32>, excluded: &[usize]) -> bool {
+        let projected = self.viewer.project(point);
+        let (ray, distance) = self.viewer.ray(point);
+
+        (0..self.mesh.faces.len()).any(|face| {
+            if excluded.contains(&face) {
+                return false;
+            }
+
+            if let (Some(point), Some((min, max))) = (projected, self.bounds[face]) {
+                if point.x < min.x || point.y < min.y || point.x > max.x || point.y > max.y {
+                    return false;
+                }
+            }
+
+            let normal = self.normals[face];
+            let denominator = normal.dot(&ray);
+            if denominator.abs() < f32::EPSILON {
+                return false;
+            }
+
+            let origin = self.points[self.mesh.faces[face][0]];
+            let parameter = normal.dot(&(origin - point)) / denominator;
+            if parameter <= OCCLUSION_EPSILON || parameter >= distance {
+                return false;
+            }
+
+            self.contains(face, point + ray * parameter)
+        })
+    }
+
+    // Even-odd test in the coordinate plane, where the face has the largest projection.
+    fn contains(&self, face: usize, point: Vector3<f32>) -> bool {
+        let (x, y) = dominant_plane(self.normals[face]);
+
+        let vertices = &self.mesh.faces[face];
+        let mut is_inside = false;
+
+        for i in 0..vertices.len() {
+            let current = self.points[vertices[i]];
+            let previous = self.points[vertices[(i + vertices.len() - 1) % vertices.len()]];
+
+            if (current[y] > point[y]) != (previous[y] > point[y]) {
+                let crossing = current[x]
+                    + (point[y] - current[y]) / (previous[y] - current[y])
+                        * (previous[x] - current[x]);
+                if point[x] < crossing {
+                    is_inside = !is_inside;
+                }
+            }
+        }
+
+        is_inside
+    }
+}
+
+fn front_faces(mesh: &Mesh, viewer: Viewer) -> Vec<bool> {
+    (0..mesh.faces.len())
+        .map(|face| {
+            let point = mesh.vertices[mesh.faces[face][0]];
+            viewer.is_front(mesh.normal(face), Vector3::new(point.x, point.y, point.z))
+        })
+        .collect()
+}
+
+// Two unit vectors, perpendicular to the direction and to each other.
+fn basis(direction: Vector3<f32>) -> (Vector3<f32>, Vector3<f32>) {
+    let right = Vector3::y()
+        .cross(&direction)
+        .try_normalize(f32::EPSILON)
+        .unwrap_or(Vector3::x());
+    let up = direction.cross(&right);
+
+    (right, up)
+}
+
+fn lerp(line: &Line3D, parameter: f32) -> Point3D {
+    Point3D::new(
+        line.start.x + (line.end.x - line.start.x) * parameter,
+        line.start.y + (line.end.y - line.start.y) * parameter,
+        line.start.z + (line.end.z - line.start.z) * parameter,
+    )
+}
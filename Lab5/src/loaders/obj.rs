@@ -0,0 +1,90 @@
+use crate::errors::mesh::MeshError;
+use crate::geometry::point3d::Point3D;
+use crate::graphics::mesh::Mesh;
+
+// Wavefront OBJ: vertices ("v"), faces ("f") and polylines ("l"). Other elements are skipped.
+pub fn parse(name: String, text: &str) -> Result<Mesh, MeshError> {
+    let mut vertices: Vec<Point3D> = vec![];
+    let mut faces: Vec<Vec<usize>> = vec![];
+    let mut polylines: Vec<Vec<usize>> = vec![];
+
+    for (index, line) in text.lines().enumerate() {
+        let error = |message: &str| {
+            MeshError::ParsingFailed(format!(
+                "Line {}: {}\n\"{}\"",
+                index + 1,
+                message,
+                line.trim()
+            ))
+        };
+
+        let mut tokens = line.split_whitespace();
+        match tokens.next() {
+            Some("v") => {
+                let coordinates: Vec<f32> = tokens
+                    .take(3)
+                    .map(|token| token.parse::<f32>())
+                    .collect::<Result<_, _>>()
+                    .map_err(|_| error("Vertex coordinate is not a number."))?;
+
+                match coordinates.as_slice() {
+                    [x, y, z] => vertices.push(Point3D::new(*x, *y, *z)),
+                    _ => return Err(error("Vertex has less than three coordinates.")),
+                }
+            },
+            Some(kind @ ("f" | "l")) => {
+                let indices: Vec<usize> = tokens
+                    .map(|token| vertex_index(token, vertices.len()))
+                    .collect::<Option<_>>()
+                    .ok_or_else(|| error("Vertex index is invalid."))?;
+
+                if kind == "f" {
+                    if indices.len() < 3 {
+                        return Err(error("Face has less than three vertices."));
+                    }
+                    faces.push(indices);
+                } else {
+                    polylines.push(indices);
+                }
+            },
+            _ => {},
+        }
+    }
+
+    Ok(Mesh::new(name, vertices, faces, &polylines))
+}
+
+// Index of "v/vt/vn" element: starts from 1, negative one is relative to the last vertex.
+fn vertex_index(token: &str, count: usize) -> Option<usize> {
+    let index: i64 = token.split('/').next()?.parse().ok()?;
+
+    let index = if index < 0 {
+        count as i64 + index
+    } else {
+        index - 1
+    };
+
+    (0..count as i64).contains(&index).then_some(index as usize)
+}
+
+// Indices start from 1. Edges without faces are written as polylines of two vertices.
+pub fn write(mesh: &Mesh) -> String {
+    let mut text = format!("# {}\n", mesh.name);
+
+    mesh.vertices.iter().for_each(|point| {
+        text.push_str(&format!("v {} {} {}\n", point.x, point.y, point.z));
+    });
+
+    mesh.faces.iter().for_each(|face| {
+        let indices: Vec<String> = face.iter().map(|index| (index + 1).to_string()).collect();
+        text.push_str(&format!("f {}\n", indices.join(" ")));
+    });
+
+    mesh.edges
+        .iter()
+        .zip(&mesh.edge_faces)
+        .filter(|(_, faces)| faces.is_empty())
+        .for_each(|((start, end), _)| text.push_str(&format!("l {} {}\n", start + 1, end + 1)));
+
+    text
+}
@@ -0,0 +1,127 @@
+use crate::errors::mesh::MeshError;
+use crate::geometry::point3d::Point3D;
+use crate::graphics::mesh::Mesh;
+use eframe::epaint::Color32;
+
+// Object File Format: header, counts of vertices and faces, vertices, faces with vertex count
+// first. Optional color follows face indices: integers from 0 to 255 or floats from 0 to 1.
+pub fn parse(name: String, text: &str) -> Result<Mesh, MeshError> {
+    let mut lines = text
+        .lines()
+        .map(|line| line.split('#').next().unwrap_or_default().trim())
+        .enumerate()
+        .filter(|(_, line)| !line.is_empty());
+
+    let error = |index: usize, message: &str| {
+        MeshError::ParsingFailed(format!("Line {}: {}", index + 1, message))
+    };
+
+    let Some((index, header)) = lines.next() else {
+        return Err(MeshError::EmptyMesh);
+    };
+    // Counts may follow the header in the same line.
+    let counts = match header.strip_prefix("OFF") {
+        Some(rest) if !rest.trim().is_empty() => (index, rest.trim()),
+        Some(_) => lines
+            .next()
+            .ok_or_else(|| error(index, "Counts of vertices and faces are missing."))?,
+        None => return Err(error(index, "Header \"OFF\" is missing.")),
+    };
+
+    let numbers = |index: usize, line: &str| -> Result<Vec<f32>, MeshError> {
+        line.split_whitespace()
+            .map(|token| token.parse::<f32>())
+            .collect::<Result<_, _>>()
+            .map_err(|_| error(index, "Value is not a number."))
+    };
+
+    let mut tokens = counts.1.split_whitespace().map(str::parse::<usize>);
+    let (vertex_count, face_count) = match (tokens.next(), tokens.next()) {
+        (Some(Ok(vertices)), Some(Ok(faces))) => (vertices, faces),
+        (Some(_), Some(_)) => return Err(error(counts.0, "Counts are not whole numbers.")),
+        _ => return Err(error(counts.0, "Counts of vertices and faces are missing.")),
+    };
+
+    // Every vertex and face takes a line, so counts are checked before the memory is reserved.
+    let rest: Vec<(usize, &str)> = lines.collect();
+    if vertex_count.saturating_add(face_count) > rest.len() {
+        return Err(error(counts.0, "File has less lines than declared."));
+    }
+    let mut lines = rest.into_iter();
+
+    let mut vertices: Vec<Point3D> = Vec::with_capacity(vertex_count);
+    for _ in 0..vertex_count {
+        let (index, line) = lines
+            .next()
+            .ok_or_else(|| error(counts.0, "File has less vertices than declared."))?;
+
+        match numbers(index, line)?.as_slice() {
+            [x, y, z, ..] => vertices.push(Point3D::new(*x, *y, *z)),
+            _ => return Err(error(index, "Vertex has less than three coordinates.")),
+        }
+    }
+
+    let mut faces: Vec<Vec<usize>> = Vec::with_capacity(face_count);
+    let mut colors: Vec<Option<Color32>> = Vec::with_capacity(face_count);
+    for _ in 0..face_count {
+        let (index, line) = lines
+            .next()
+            .ok_or_else(|| error(counts.0, "File has less faces than declared."))?;
+
+        // Vertex count and indices are whole numbers, optional color follows them.
+        let tokens: Vec<&str> = line.split_whitespace().collect();
+        let whole = |token: &str| {
+            token
+                .parse::<usize>()
+                .map_err(|_| error(index, "Vertex count and indices must be whole numbers."))
+        };
+
+        let count = whole(tokens[0])?;
+        if count < 3 {
+            return Err(error(index, "Face has less than three vertices."));
+        }
+        if tokens.len() - 1 < count {
+            return Err(error(index, "Face has less vertices than declared."));
+        }
+
+        let face: Vec<usize> = tokens[1..=count]
+            .iter()
+            .map(|token| whole(token))
+            .collect::<Result<_, _>>()?;
+        if face.iter().any(|vertex| *vertex >= vertices.len()) {
+            return Err(error(index, "Vertex index is out of range."));
+        }
+        let color_values: Vec<f32> = tokens[count + 1..]
+            .iter()
+            .map(|token| token.parse::<f32>())
+            .collect::<Result<_, _>>()
+            .map_err(|_| error(index, "Value is not a number."))?;
+        faces.push(face);
+        colors.push(color(&color_values));
+    }
+
+    let mut mesh = Mesh::new(name, vertices, faces, &[]);
+    // Colors are used only if every face has one.
+    if let Some(colors) = colors.into_iter().collect::<Option<Vec<Color32>>>() {
+        mesh.face_colors = colors;
+    }
+
+    Ok(mesh)
+}
+
+fn color(values: &[f32]) -> Option<Color32> {
+    let [red, green, blue, ..] = values else {
+        return None;
+    };
+
+    let is_float = [red, green, blue].iter().all(|value| **value <= 1.0)
+        && [red, green, blue].iter().any(|value| value.fract() != 0.0);
+    let scale = if is_float { 255.0 } else { 1.0 };
+    let channel = |value: f32| (value * scale).clamp(0.0, 255.0) as u8;
+
+    Some(Color32::from_rgb(
+        channel(*red),
+        channel(*green),
+        channel(*blue),
+    ))
+}
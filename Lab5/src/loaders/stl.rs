@@ -0,0 +1,127 @@
+use crate::errors::mesh::MeshError;
+use crate::geometry::point3d::Point3D;
+use crate::graphics::mesh::Mesh;
+use std::collections::HashMap;
+
+// Header and count of triangles of binary STL.
+const HEADER_SIZE: usize = 84;
+// Normal, three vertices and attribute of binary STL triangle.
+const TRIANGLE_SIZE: usize = 50;
+
+// Binary or ASCII STL. Equal vertices of triangles are merged.
+pub fn parse(name: String, bytes: &[u8]) -> Result<Mesh, MeshError> {
+    let triangles = if is_binary(bytes) {
+        parse_binary(bytes)?
+    } else {
+        let text = String::from_utf8_lossy(bytes);
+        parse_ascii(&text)?
+    };
+
+    let mut indices: HashMap<[u32; 3], usize> = HashMap::new();
+    let mut vertices: Vec<Point3D> = vec![];
+    let faces: Vec<Vec<usize>> = triangles
+        .iter()
+        .map(|triangle| {
+            triangle
+                .iter()
+                .map(|point| {
+                    let key = [point.x.to_bits(), point.y.to_bits(), point.z.to_bits()];
+                    *indices.entry(key).or_insert_with(|| {
+                        vertices.push(*point);
+                        vertices.len() - 1
+                    })
+                })
+                .collect()
+        })
+        .collect();
+
+    Ok(Mesh::new(name, vertices, faces, &[]))
+}
+
+// ASCII file starts with "solid", but some binary files do too, so the size is checked.
+fn is_binary(bytes: &[u8]) -> bool {
+    if bytes.len() < HEADER_SIZE {
+        return false;
+    }
+
+    let count = u32::from_le_bytes([bytes[80], bytes[81], bytes[82], bytes[83]]) as usize;
+    let has_binary_size = bytes.len() == HEADER_SIZE + count * TRIANGLE_SIZE;
+
+    has_binary_size || !bytes.starts_with(b"solid")
+}
+
+fn parse_binary(bytes: &[u8]) -> Result<Vec<[Point3D; 3]>, MeshError> {
+    let count = u32::from_le_bytes([bytes[80], bytes[81], bytes[82], bytes[83]]) as usize;
+    if bytes.len() < HEADER_SIZE + count * TRIANGLE_SIZE {
+        return Err(MeshError::ParsingFailed(format!(
+            "File is shorter than {} declared triangles.",
+            count
+        )));
+    }
+
+    let float = |offset: usize| {
+        f32::from_le_bytes([
+            bytes[offset],
+            bytes[offset + 1],
+            bytes[offset + 2],
+            bytes[offset + 3],
+        ])
+    };
+    let point = |offset: usize| Point3D::new(float(offset), float(offset + 4), float(offset + 8));
+
+    Ok((0..count)
+        .map(|i| {
+            // Normal is skipped, it is computed from vertices.
+            let offset = HEADER_SIZE + i * TRIANGLE_SIZE + 12;
+            [point(offset), point(offset + 12), point(offset + 24)]
+        })
+        .collect())
+}
+
+fn parse_ascii(text: &str) -> Result<Vec<[Point3D; 3]>, MeshError> {
+    let mut triangles: Vec<[Point3D; 3]> = vec![];
+    let mut facet: Vec<Point3D> = vec![];
+
+    for (index, line) in text.lines().enumerate() {
+        let mut tokens = line.split_whitespace();
+
+        match tokens.next() {
+            Some("vertex") => {
+                let coordinates: Vec<f32> = tokens
+                    .map(|token| token.parse::<f32>())
+                    .collect::<Result<_, _>>()
+                    .map_err(|_| {
+                        MeshError::ParsingFailed(format!(
+                            "Line {}: Vertex coordinate is not a number.",
+                            index + 1
+                        ))
+                    })?;
+
+                match coordinates.as_slice() {
+                    [x, y, z] => facet.push(Point3D::new(*x, *y, *z)),
+                    _ => {
+                        return Err(MeshError::ParsingFailed(format!(
+                            "Line {}: Vertex must have three coordinates.",
+                            index + 1
+                        )))
+                    },
+                }
+            },
+            Some("endfacet") => {
+                match facet.as_slice() {
+                    [first, second, third] => triangles.push([*first, *second, *third]),
+                    _ => {
+                        return Err(MeshError::ParsingFailed(format!(
+                            "Line {}: Facet must have three vertices.",
+                            index + 1
+                        )))
+                    },
+                }
+                facet.clear();
+            },
+            _ => {},
+        }
+    }
+
+    Ok(triangles)
+}
@@ -29,6 +29,7 @@ pub mod context;
 pub mod errors {
     pub mod env;
     pub mod log;
+    pub mod mesh;
 }
 pub mod geometry {
     pub mod line2d;
@@ -38,8 +39,18 @@ pub mod geometry {
 }
 pub mod graphics {
     pub mod axes;
+    pub mod generator;
+    pub mod mesh;
     pub mod model;
+    pub mod multiview;
+    pub mod rasterizer;
     pub mod screen;
+    pub mod visibility;
+}
+pub mod loaders {
+    pub mod obj;
+    pub mod off;
+    pub mod stl;
 }
 pub mod logger;
 pub mod math {
@@ -50,9 +61,11 @@ pub mod operations {
 }
 pub mod projections {
     pub mod orthographic;
+    pub mod perspective;
     pub mod trimetric;
 }
 pub mod transformations {
+    pub mod arcball;
     pub mod offset;
     pub mod rotation;
 }
@@ -61,6 +74,7 @@ pub mod ui {
     pub mod core;
     pub mod components {
         pub mod canvas;
+        pub mod message_box;
         pub mod settings;
     }
     pub mod styles {
@@ -1,5 +1,10 @@
 use crate::graphics::model::Model;
+use crate::math::angle::Angle;
 use crate::transformations::rotation::Rotation;
+use nalgebra::UnitQuaternion;
+
+// Longest time step (in seconds): slow frames do not skip keyframes.
+const MAX_TIME_STEP_S: f32 = 0.1;
 
 pub struct Animation {
     pub is_running: bool,
@@ -14,6 +19,15 @@ pub struct Animation {
     pub step_x: f32,
     pub step_y: f32,
     pub step_z: f32,
+
+    // Orientation is interpolated between keyframes, instead of rotation by steps.
+    pub use_keyframes: bool,
+    pub keyframes: Vec<UnitQuaternion<f32>>,
+    // Time of moving between neighbour keyframes.
+    pub duration_s: f32,
+    pub easing: Easing,
+    pub is_looped: bool,
+    keyframe_time_s: f32,
 }
 
 impl Default for Animation {
@@ -31,6 +45,13 @@ impl Default for Animation {
             step_x: 0.0,
             step_y: 0.0,
             step_z: 0.0,
+
+            use_keyframes: false,
+            keyframes: vec![],
+            duration_s: 2.0,
+            easing: Easing::EaseInOut,
+            is_looped: true,
+            keyframe_time_s: 0.0,
         }
     }
 }
@@ -45,7 +66,10 @@ impl Animation {
                 self.step_radius(model);
             }
 
-            if self.use_rotation {
+            if self.use_keyframes {
+                let time_step = ui.input(|i| i.stable_dt).min(MAX_TIME_STEP_S);
+                self.step_keyframes(rotation, time_step);
+            } else if self.use_rotation {
                 self.step_rotation(rotation);
             }
 
@@ -67,10 +91,47 @@ impl Animation {
     }
 
     pub fn step_rotation(&mut self, rotation: &mut Rotation) {
-        rotation.angle_deg_x = self.step_x % 360.0;
-        rotation.angle_deg_y = self.step_y % 360.0;
-        rotation.angle_deg_z = self.step_z % 360.0;
-        rotation.is_applied = true;
+        rotation.rotate(UnitQuaternion::from_euler_angles(
+            Angle::from_degree(self.step_x % 360.0).radian(),
+            Angle::from_degree(self.step_y % 360.0).radian(),
+            Angle::from_degree(self.step_z % 360.0).radian(),
+        ));
+    }
+
+    // Spherical linear interpolation between neighbour keyframes. Not looped animation stops
+    // at the last keyframe and starts again from the first one.
+    pub fn step_keyframes(&mut self, rotation: &mut Rotation, time_step_s: f32) {
+        let count = self.keyframes.len();
+        if count < 2 {
+            return;
+        }
+
+        let segments = if self.is_looped { count } else { count - 1 };
+        let total_s = self.duration_s * segments as f32;
+
+        self.keyframe_time_s += time_step_s;
+        if self.keyframe_time_s >= total_s {
+            if self.is_looped {
+                self.keyframe_time_s %= total_s;
+            } else {
+                rotation.set_orientation(self.keyframes[count - 1]);
+                self.keyframe_time_s = 0.0;
+                self.is_running = false;
+                return;
+            }
+        }
+
+        let position = self.keyframe_time_s / self.duration_s;
+        let index = (position as usize).min(segments - 1);
+        let from = self.keyframes[index];
+        let to = self.keyframes[(index + 1) % count];
+
+        let fraction = self.easing.apply(position - index as f32);
+        rotation.set_orientation(from.try_slerp(&to, fraction, f32::EPSILON).unwrap_or(to));
+    }
+
+    pub fn restart_keyframes(&mut self) {
+        self.keyframe_time_s = 0.0;
     }
 
     pub fn checkout_status(&mut self) {
@@ -88,8 +149,31 @@ impl Animation {
     }
 
     fn ensure_animation_enabled(&mut self) {
-        if self.is_running && !self.use_rotation && !self.use_radius {
+        let has_keyframes = self.use_keyframes && self.keyframes.len() >= 2;
+        if self.is_running && !self.use_rotation && !self.use_radius && !has_keyframes {
             self.is_running = false;
         }
     }
 }
+
+#[derive(PartialEq)]
+pub enum Easing {
+    Linear,
+    EaseIn,
+    EaseOut,
+    // Smoothstep: starts and stops with zero speed.
+    EaseInOut,
+}
+
+impl Easing {
+    pub fn apply(&self, t: f32) -> f32 {
+        let t = t.clamp(0.0, 1.0);
+
+        match self {
+            Self::Linear => t,
+            Self::EaseIn => t * t,
+            Self::EaseOut => 1.0 - (1.0 - t) * (1.0 - t),
+            Self::EaseInOut => t * t * (3.0 - 2.0 * t),
+        }
+    }
+}
@@ -1,4 +1,3 @@
-use crate::geometry::line3d::Line3D;
 use crate::geometry::point3d::Point3D;
 use nalgebra::Matrix4;
 
@@ -21,17 +20,16 @@ impl Default for OrthographicProjection {
 }
 
 impl OrthographicProjection {
-    pub fn apply_if_set(&self, model: &mut [Line3D]) {
+    pub fn apply_if_set(&self, model: &mut [Point3D]) {
         if self.is_enabled {
             self.process(model);
         }
     }
 
-    fn process(&self, model: &mut [Line3D]) {
-        model.iter_mut().for_each(|line| {
-            self.transform_point(&mut line.start);
-            self.transform_point(&mut line.end);
-        })
+    fn process(&self, model: &mut [Point3D]) {
+        model
+            .iter_mut()
+            .for_each(|point| self.transform_point(point))
     }
 
     fn transform_point(&self, point: &mut Point3D) {
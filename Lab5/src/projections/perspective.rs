@@ -0,0 +1,258 @@
+use crate::geometry::line2d::Line2D;
+use crate::geometry::line3d::Line3D;
+use crate::geometry::point2d::Point2D;
+use crate::geometry::point3d::Point3D;
+use crate::graphics::visibility::Viewer;
+use crate::math::angle::Angle;
+use nalgebra::{Matrix3, Matrix4, SMatrix, Vector3};
+
+// Field of view, for which objects at the target keep their size.
+const NATURAL_FOV_DEG: f32 = 60.0;
+// Distance from the eye to the target, used by presets.
+const PRESET_DISTANCE: f32 = 25.0;
+
+// Central projection from the eye onto the plane, perpendicular to the view direction.
+pub struct PerspectiveProjection {
+    pub is_enabled: bool,
+
+    pub eye: Point3D,
+    pub target: Point3D,
+    pub up: Point3D,
+
+    pub fov_deg: f32,
+    pub near: f32,
+    pub far: f32,
+}
+
+impl Default for PerspectiveProjection {
+    fn default() -> Self {
+        let mut perspective = Self {
+            is_enabled: false,
+
+            eye: Point3D::default(),
+            target: Point3D::default(),
+            up: Point3D::default(),
+
+            fov_deg: NATURAL_FOV_DEG,
+            near: 0.1,
+            far: 100.0,
+        };
+        perspective.set_preset(PerspectivePreset::ThreePoint);
+
+        perspective
+    }
+}
+
+impl PerspectiveProjection {
+    // Camera is placed so that one, two or three axes of the model converge.
+    pub fn set_preset(&mut self, preset: PerspectivePreset) {
+        let distance = PRESET_DISTANCE;
+        let height = 2.0;
+
+        (self.eye, self.target, self.up) = match preset {
+            // View direction is parallel to OZ: only lines along OZ converge.
+            PerspectivePreset::OnePoint => (
+                Point3D::new(3.0, height, distance),
+                Point3D::new(3.0, height, 0.0),
+                Point3D::new(0.0, 1.0, 0.0),
+            ),
+            // View direction is perpendicular to OY: vertical lines stay parallel.
+            PerspectivePreset::TwoPoint => (
+                Point3D::new(
+                    distance * f32::sin(Angle::from_degree(35.0).radian()),
+                    height,
+                    distance * f32::cos(Angle::from_degree(35.0).radian()),
+                ),
+                Point3D::new(0.0, height, 0.0),
+                Point3D::new(0.0, 1.0, 0.0),
+            ),
+            // View direction is oblique to all axes.
+            PerspectivePreset::ThreePoint => {
+                let coordinate = distance / f32::sqrt(3.0);
+                (
+                    Point3D::new(coordinate, coordinate, coordinate),
+                    Point3D::new(0.0, 0.0, 0.0),
+                    Point3D::new(0.0, 1.0, 0.0),
+                )
+            },
+        };
+    }
+
+    pub fn lines2d(&self, lines: &[Line3D]) -> Vec<Line2D> {
+        let view = self.view_matrix();
+        let projection = self.projection_matrix();
+
+        lines
+            .iter()
+            .filter_map(|line| {
+                let start = line.start.to_vector() * view;
+                let end = line.end.to_vector() * view;
+
+                let (start, end) = self.clip(
+                    Vector3::new(start.x, start.y, start.z),
+                    Vector3::new(end.x, end.y, end.z),
+                )?;
+
+                Some(Line2D::new(
+                    Self::project(start, &projection),
+                    Self::project(end, &projection),
+                    line.stroke,
+                ))
+            })
+            .collect()
+    }
+
+    // None, if the point is not between near and far planes.
+    pub fn point2d(&self, point: Point3D) -> Option<Point2D> {
+        let vector = point.to_vector() * self.view_matrix();
+        let depth = -vector.z;
+        if depth < self.near || depth > self.far {
+            return None;
+        }
+
+        Some(Self::project(
+            Vector3::new(vector.x, vector.y, vector.z),
+            &self.projection_matrix(),
+        ))
+    }
+
+    // Columns are directions to the right, up and backward (to the eye).
+    pub fn camera_basis(&self) -> Matrix3<f32> {
+        self.view_matrix().fixed_view::<3, 3>(0, 0).into_owned()
+    }
+
+    // Distance from the eye along the view direction.
+    pub fn depth(&self, point: Point3D) -> f32 {
+        -(point.to_vector() * self.view_matrix()).z
+    }
+
+    // Row vectors of the camera basis: right, up and backward (camera looks along -OZ).
+    pub fn view_matrix(&self) -> Matrix4<f32> {
+        let eye = Vector3::new(self.eye.x, self.eye.y, self.eye.z);
+        let target = Vector3::new(self.target.x, self.target.y, self.target.z);
+
+        let backward = (eye - target)
+            .try_normalize(f32::EPSILON)
+            .unwrap_or(Vector3::z());
+
+        // Up vector, parallel to the view direction, is replaced by any perpendicular one.
+        let up = Vector3::new(self.up.x, self.up.y, self.up.z);
+        let right = up
+            .cross(&backward)
+            .try_normalize(f32::EPSILON)
+            .or_else(|| Vector3::x().cross(&backward).try_normalize(f32::EPSILON))
+            .unwrap_or(Vector3::y().cross(&backward));
+        let up = backward.cross(&right);
+
+        Matrix4::new(
+            right.x,
+            up.x,
+            backward.x,
+            0.0,
+            right.y,
+            up.y,
+            backward.y,
+            0.0,
+            right.z,
+            up.z,
+            backward.z,
+            0.0,
+            -right.dot(&eye),
+            -up.dot(&eye),
+            -backward.dot(&eye),
+            1.0,
+        )
+    }
+
+    // Homogeneous coordinate is depth divided by the distance to the target, so objects at the
+    // target keep their size for the natural field of view.
+    pub fn projection_matrix(&self) -> Matrix4<f32> {
+        let natural_fov = Angle::from_degree(NATURAL_FOV_DEG).radian();
+        let fov = Angle::from_degree(self.fov_deg).radian();
+        let scale = f32::tan(natural_fov / 2.0) / f32::tan(fov / 2.0);
+        let distance = self.distance();
+
+        Matrix4::new(
+            scale,
+            0.0,
+            0.0,
+            0.0,
+            0.0,
+            scale,
+            0.0,
+            0.0,
+            0.0,
+            0.0,
+            1.0,
+            -1.0 / distance,
+            0.0,
+            0.0,
+            0.0,
+            0.0,
+        )
+    }
+
+    pub fn viewer(&self) -> Viewer {
+        let eye = Vector3::new(self.eye.x, self.eye.y, self.eye.z);
+        let target = Vector3::new(self.target.x, self.target.y, self.target.z);
+
+        Viewer::Eye(
+            eye,
+            (target - eye)
+                .try_normalize(f32::EPSILON)
+                .unwrap_or(-Vector3::z()),
+        )
+    }
+
+    pub fn distance(&self) -> f32 {
+        let distance = f32::sqrt(
+            (self.eye.x - self.target.x).powf(2.0)
+                + (self.eye.y - self.target.y).powf(2.0)
+                + (self.eye.z - self.target.z).powf(2.0),
+        );
+
+        f32::max(distance, f32::EPSILON)
+    }
+
+    fn project(point: Vector3<f32>, projection: &Matrix4<f32>) -> Point2D {
+        let vector = SMatrix::<f32, 1, 4>::new(point.x, point.y, point.z, 1.0) * projection;
+
+        Point2D::new(vector.x / vector.w, vector.y / vector.w)
+    }
+
+    // Part of the segment (in camera coordinates) between near and far planes.
+    fn clip(&self, start: Vector3<f32>, end: Vector3<f32>) -> Option<(Vector3<f32>, Vector3<f32>)> {
+        let (depth_start, depth_end) = (-start.z, -end.z);
+        let (mut from, mut to) = (0.0, 1.0);
+
+        for (plane, sign) in [(self.near, 1.0), (self.far, -1.0)] {
+            // Signed distances to the plane, positive on the visible side.
+            let distance_start = sign * (depth_start - plane);
+            let distance_end = sign * (depth_end - plane);
+
+            if distance_start < 0.0 && distance_end < 0.0 {
+                return None;
+            }
+
+            let t = distance_start / (distance_start - distance_end);
+            if distance_start < 0.0 {
+                from = f32::max(from, t);
+            } else if distance_end < 0.0 {
+                to = f32::min(to, t);
+            }
+        }
+
+        if from > to {
+            return None;
+        }
+
+        Some((start.lerp(&end, from), start.lerp(&end, to)))
+    }
+}
+
+#[derive(PartialEq)]
+pub enum PerspectivePreset {
+    OnePoint,
+    TwoPoint,
+    ThreePoint,
+}
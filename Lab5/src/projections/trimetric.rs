@@ -1,22 +1,112 @@
 use crate::math::angle::Angle;
-use nalgebra::Matrix4;
+use nalgebra::{Matrix3, Matrix4, Vector3};
+
+// Angles of the standard dimetric projection (coefficients 0.94, 0.94, 0.47): arcsin(1/3) and arcsin(sqrt(1/8)).
+const DIMETRIC_ANGLE_DEG_X: f32 = 19.471;
+const DIMETRIC_ANGLE_DEG_Y: f32 = 20.705;
+// Angles of the isometric projection: arcsin(tan(30°)) and 45°.
+const ISOMETRIC_ANGLE_DEG_X: f32 = 35.264;
+const ISOMETRIC_ANGLE_DEG_Y: f32 = 45.0;
 
-#[derive(Default)]
 pub struct TrimetricProjection {
-    pub angle_deg_x: i32,
-    pub angle_deg_y: i32,
+    pub mode: ProjectionMode,
+
+    // Axonometric projection: rotation around OY, then around OX.
+    pub angle_deg_x: f32,
+    pub angle_deg_y: f32,
+
+    // Oblique projections: angle between receding OZ axis and OX.
+    pub receding_angle_deg: f32,
+}
+
+impl Default for TrimetricProjection {
+    fn default() -> Self {
+        Self {
+            mode: ProjectionMode::Axonometric,
+
+            angle_deg_x: 0.0,
+            angle_deg_y: 0.0,
+
+            receding_angle_deg: 45.0,
+        }
+    }
 }
 
 impl TrimetricProjection {
     pub fn angle_x(&self) -> Angle {
-        Angle::from_degree(self.angle_deg_x as f32)
+        Angle::from_degree(self.angle_deg_x)
     }
 
     pub fn angle_y(&self) -> Angle {
-        Angle::from_degree(self.angle_deg_y as f32)
+        Angle::from_degree(self.angle_deg_y)
+    }
+
+    pub fn set_preset(&mut self, preset: AxonometricPreset) {
+        self.mode = ProjectionMode::Axonometric;
+
+        (self.angle_deg_x, self.angle_deg_y) = match preset {
+            AxonometricPreset::Isometric => (ISOMETRIC_ANGLE_DEG_X, ISOMETRIC_ANGLE_DEG_Y),
+            AxonometricPreset::Dimetric => (DIMETRIC_ANGLE_DEG_X, DIMETRIC_ANGLE_DEG_Y),
+        };
+    }
+
+    // Name of the axonometric projection for current angles.
+    pub fn kind(&self) -> &'static str {
+        let [x, y, z] = self.scale_factors();
+        let is_equal = |first: f32, second: f32| (first - second).abs() < 0.005;
+
+        match self.mode {
+            ProjectionMode::Cavalier => "Cavalier",
+            ProjectionMode::Cabinet => "Cabinet",
+            ProjectionMode::Axonometric if is_equal(x, y) && is_equal(y, z) => "Isometric",
+            ProjectionMode::Axonometric if is_equal(x, y) || is_equal(y, z) || is_equal(x, z) => {
+                "Dimetric"
+            },
+            ProjectionMode::Axonometric => "Trimetric",
+        }
+    }
+
+    // Lengths of projected unit vectors of OX, OY and OZ.
+    pub fn scale_factors(&self) -> [f32; 3] {
+        let matrix = self.matrix();
+
+        [0, 1, 2].map(|row| f32::sqrt(matrix[(row, 0)].powf(2.0) + matrix[(row, 1)].powf(2.0)))
+    }
+
+    // Unit vector to the viewer: the one, which is projected to a point.
+    pub fn view_direction(&self) -> Vector3<f32> {
+        let matrix = self.matrix();
+        let column =
+            |index: usize| Vector3::new(matrix[(0, index)], matrix[(1, index)], matrix[(2, index)]);
+
+        column(0)
+            .cross(&column(1))
+            .try_normalize(f32::EPSILON)
+            .unwrap_or(Vector3::z())
+    }
+
+    // Columns are directions to the right, up and to the viewer on the picture plane.
+    pub fn camera_basis(&self) -> Matrix3<f32> {
+        let matrix = self.matrix();
+        let toward = self.view_direction();
+
+        let horizontal = Vector3::new(matrix[(0, 0)], matrix[(1, 0)], matrix[(2, 0)]);
+        let right = (horizontal - toward * horizontal.dot(&toward))
+            .try_normalize(f32::EPSILON)
+            .unwrap_or(Vector3::x());
+
+        Matrix3::from_columns(&[right, toward.cross(&right), toward])
     }
 
     pub fn matrix(&self) -> Matrix4<f32> {
+        match self.mode {
+            ProjectionMode::Axonometric => self.matrix_axonometric(),
+            ProjectionMode::Cavalier => self.matrix_oblique(1.0),
+            ProjectionMode::Cabinet => self.matrix_oblique(0.5),
+        }
+    }
+
+    fn matrix_axonometric(&self) -> Matrix4<f32> {
         let alpha = self.angle_x().radian();
         let beta = self.angle_y().radian();
 
@@ -39,4 +129,42 @@ impl TrimetricProjection {
             1.0,
         )
     }
+
+    // OX and OY keep their size, OZ recedes at the angle with the given coefficient.
+    fn matrix_oblique(&self, coefficient: f32) -> Matrix4<f32> {
+        let angle = Angle::from_degree(self.receding_angle_deg).radian();
+
+        Matrix4::new(
+            1.0,
+            0.0,
+            0.0,
+            0.0,
+            0.0,
+            1.0,
+            0.0,
+            0.0,
+            coefficient * f32::cos(angle),
+            coefficient * f32::sin(angle),
+            0.0,
+            0.0,
+            0.0,
+            0.0,
+            0.0,
+            1.0,
+        )
+    }
+}
+
+#[derive(PartialEq)]
+pub enum ProjectionMode {
+    Axonometric,
+    // Oblique projection with full length of OZ.
+    Cavalier,
+    // Oblique projection with half length of OZ.
+    Cabinet,
+}
+
+pub enum AxonometricPreset {
+    Isometric,
+    Dimetric,
 }
@@ -0,0 +1,47 @@
+use egui::Vec2;
+use nalgebra::{Matrix3, Unit, UnitQuaternion, Vector3};
+
+// Mouse control: left drag rotates the model, right drag pans, the wheel zooms.
+pub struct Arcball {
+    pub is_enabled: bool,
+
+    pub sensitivity: f32,
+}
+
+impl Default for Arcball {
+    fn default() -> Self {
+        Self {
+            is_enabled: false,
+
+            sensitivity: 1.0,
+        }
+    }
+}
+
+impl Arcball {
+    // Shoemake's arcball: positions (relative to the center, OY goes up) are lifted to the sphere
+    // of the given radius, rotation moves the first one to the second one. Axis is converted
+    // from camera coordinates by the basis of the camera.
+    pub fn rotation(
+        &self, from: Vec2, to: Vec2, radius: f32, camera_basis: Matrix3<f32>,
+    ) -> Option<UnitQuaternion<f32>> {
+        let from = Self::sphere_point(from / radius);
+        let to = Self::sphere_point(to / radius);
+
+        let axis = Unit::try_new(camera_basis * from.cross(&to), f32::EPSILON)?;
+        let angle = from.dot(&to).clamp(-1.0, 1.0).acos() * self.sensitivity;
+
+        Some(UnitQuaternion::from_axis_angle(&axis, angle))
+    }
+
+    // Points outside the sphere are moved to its silhouette.
+    fn sphere_point(position: Vec2) -> Vector3<f32> {
+        let length_squared = position.length_sq();
+
+        if length_squared <= 1.0 {
+            Vector3::new(position.x, position.y, f32::sqrt(1.0 - length_squared))
+        } else {
+            Vector3::new(position.x, position.y, 0.0) / length_squared.sqrt()
+        }
+    }
+}
@@ -1,4 +1,3 @@
-use crate::geometry::line3d::Line3D;
 use crate::geometry::point3d::Point3D;
 
 #[derive(Default)]
@@ -15,7 +14,7 @@ pub struct Offset {
 }
 
 impl Offset {
-    pub fn apply(&mut self, model: &mut [Line3D]) {
+    pub fn apply(&mut self, model: &mut [Point3D]) {
         if self.is_applied {
             self.static_x += self.x;
             self.static_y += self.y;
@@ -27,11 +26,8 @@ impl Offset {
         }
     }
 
-    pub fn process(&self, model: &mut [Line3D]) {
-        model.iter_mut().for_each(|line| {
-            self.update_point(&mut line.start);
-            self.update_point(&mut line.end);
-        });
+    pub fn process(&self, model: &mut [Point3D]) {
+        model.iter_mut().for_each(|point| self.update_point(point));
     }
 
     fn update_point(&self, point: &mut Point3D) {
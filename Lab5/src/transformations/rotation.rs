@@ -1,9 +1,8 @@
-use crate::geometry::line3d::Line3D;
 use crate::geometry::point3d::Point3D;
 use crate::math::angle::Angle;
-use nalgebra::Matrix4;
+use nalgebra::{Matrix4, Unit, UnitQuaternion, Vector3};
 
-#[derive(Default)]
+// Orientation is accumulated in the quaternion, so it has no gimbal lock.
 pub struct Rotation {
     pub is_applied: bool,
 
@@ -11,117 +10,123 @@ pub struct Rotation {
     pub angle_deg_y: f32,
     pub angle_deg_z: f32,
 
-    static_angle_deg_x: f32,
-    static_angle_deg_y: f32,
-    static_angle_deg_z: f32,
+    // Rotation around the axis, which goes through the point.
+    pub is_applied_around_axis: bool,
+    pub axis: Point3D,
+    pub axis_point: Point3D,
+    pub axis_angle_deg: f32,
+
+    orientation: UnitQuaternion<f32>,
+    // Offset of the pivot after rotations around other points.
+    translation: Vector3<f32>,
+}
+
+impl Default for Rotation {
+    fn default() -> Self {
+        Self {
+            is_applied: false,
+
+            angle_deg_x: 0.0,
+            angle_deg_y: 0.0,
+            angle_deg_z: 0.0,
+
+            is_applied_around_axis: false,
+            axis: Point3D::new(0.0, 0.0, 1.0),
+            axis_point: Point3D::new(0.0, 0.0, 0.0),
+            axis_angle_deg: 0.0,
+
+            orientation: UnitQuaternion::identity(),
+            translation: Vector3::zeros(),
+        }
+    }
 }
 
 impl Rotation {
-    pub fn apply(&mut self, model: &mut [Line3D], pivot: Point3D) {
+    pub fn apply(&mut self, model: &mut [Point3D], pivot: Point3D) {
         if self.is_applied {
-            self.static_angle_deg_x += self.angle_deg_x;
-            self.static_angle_deg_y += self.angle_deg_y;
-            self.static_angle_deg_z += self.angle_deg_z;
+            // Around OX, then around OY, then around OZ.
+            self.rotate(UnitQuaternion::from_euler_angles(
+                Angle::from_degree(self.angle_deg_x).radian(),
+                Angle::from_degree(self.angle_deg_y).radian(),
+                Angle::from_degree(self.angle_deg_z).radian(),
+            ));
             self.is_applied = false;
         }
-        if self.are_statics_not_default() {
+        if self.is_applied_around_axis {
+            self.rotate_around_axis(pivot);
+            self.is_applied_around_axis = false;
+        }
+        if self.orientation != UnitQuaternion::identity() || self.translation != Vector3::zeros() {
             self.process(model, pivot);
         }
     }
 
-    pub fn process(&self, model: &mut [Line3D], pivot: Point3D) {
+    pub fn process(&self, model: &mut [Point3D], pivot: Point3D) {
         let matrix_to_origin = self.matrix_offset_to_origin(&pivot);
         let matrix_from_origin = self.matrix_offset_to_point(&pivot);
-        let matrix_ox = self.matrix_around_ox();
-        let matrix_oy = self.matrix_around_oy();
-        let matrix_oz = self.matrix_around_oz();
+        // Points are row vectors, so the matrix is transposed.
+        let matrix_rotation = self.orientation.to_homogeneous().transpose();
+        let matrix_translation = self.matrix_offset_to_point(&Point3D::new(
+            self.translation.x,
+            self.translation.y,
+            self.translation.z,
+        ));
 
         let result_matrix =
-            matrix_to_origin * matrix_ox * matrix_oy * matrix_oz * matrix_from_origin;
+            matrix_to_origin * matrix_rotation * matrix_from_origin * matrix_translation;
 
-        model.iter_mut().for_each(|line| {
-            self.update_point(&mut line.start, &result_matrix);
-            self.update_point(&mut line.end, &result_matrix);
-        });
+        model
+            .iter_mut()
+            .for_each(|point| self.update_point(point, &result_matrix));
     }
 
-    fn update_point(&self, point: &mut Point3D, result_matrix: &Matrix4<f32>) {
-        let vector = point.to_vector();
-
-        let result = vector * result_matrix;
+    // Rotation after the current orientation. Accumulated error of the norm is removed.
+    pub fn rotate(&mut self, rotation: UnitQuaternion<f32>) {
+        self.orientation = rotation * self.orientation;
+        self.orientation.renormalize_fast();
+    }
 
-        point.x = result.x;
-        point.y = result.y;
-        point.z = result.z;
+    pub fn orientation(&self) -> UnitQuaternion<f32> {
+        self.orientation
     }
 
-    fn matrix_around_ox(&self) -> Matrix4<f32> {
-        let angle = Angle::from_degree(self.static_angle_deg_x).radian();
+    pub fn set_orientation(&mut self, orientation: UnitQuaternion<f32>) {
+        self.orientation = orientation;
+    }
 
-        Matrix4::new(
-            1.0,
-            0.0,
-            0.0,
-            0.0,
-            0.0,
-            f32::cos(angle),
-            f32::sin(angle),
-            0.0,
-            0.0,
-            -f32::sin(angle),
-            f32::cos(angle),
-            0.0,
-            0.0,
-            0.0,
-            0.0,
-            1.0,
-        )
+    // Point C goes to C + r(P - C): orientation is rotated, pivot is moved around the axis.
+    fn rotate_around_axis(&mut self, pivot: Point3D) {
+        let axis = Vector3::new(self.axis.x, self.axis.y, self.axis.z);
+        let Some(axis) = Unit::try_new(axis, f32::EPSILON) else {
+            return;
+        };
+
+        let rotation = UnitQuaternion::from_axis_angle(
+            &axis,
+            Angle::from_degree(self.axis_angle_deg).radian(),
+        );
+        let pivot = Vector3::new(pivot.x, pivot.y, pivot.z);
+        let center = Vector3::new(self.axis_point.x, self.axis_point.y, self.axis_point.z);
+
+        self.translation = rotation * (pivot + self.translation - center) + center - pivot;
+        self.rotate(rotation);
     }
 
-    fn matrix_around_oy(&self) -> Matrix4<f32> {
-        let angle = Angle::from_degree(self.static_angle_deg_y).radian();
+    // Angles around OX, OY and OZ, which give the current orientation in this order.
+    pub fn euler_angles_deg(&self) -> (f32, f32, f32) {
+        let (x, y, z) = self.orientation.euler_angles();
 
-        Matrix4::new(
-            f32::cos(angle),
-            0.0,
-            -f32::sin(angle),
-            0.0,
-            0.0,
-            1.0,
-            0.0,
-            0.0,
-            f32::sin(angle),
-            0.0,
-            f32::cos(angle),
-            0.0,
-            0.0,
-            0.0,
-            0.0,
-            1.0,
-        )
+        (x.to_degrees(), y.to_degrees(), z.to_degrees())
     }
 
-    fn matrix_around_oz(&self) -> Matrix4<f32> {
-        let angle = Angle::from_degree(self.static_angle_deg_z).radian();
+    fn update_point(&self, point: &mut Point3D, result_matrix: &Matrix4<f32>) {
+        let vector = point.to_vector();
 
-        Matrix4::new(
-            f32::cos(angle),
-            f32::sin(angle),
-            0.0,
-            0.0,
-            -f32::sin(angle),
-            f32::cos(angle),
-            0.0,
-            0.0,
-            0.0,
-            0.0,
-            1.0,
-            0.0,
-            0.0,
-            0.0,
-            0.0,
-            1.0,
-        )
+        let result = vector * result_matrix;
+
+        point.x = result.x;
+        point.y = result.y;
+        point.z = result.z;
     }
 
     fn matrix_offset_to_origin(&self, pivot: &Point3D) -> Matrix4<f32> {
@@ -138,15 +143,8 @@ impl Rotation {
         )
     }
 
-    fn are_statics_not_default(&self) -> bool {
-        self.static_angle_deg_x != 0.0
-            || self.static_angle_deg_y != 0.0
-            || self.static_angle_deg_z != 0.0
-    }
-
     pub fn reset_position(&mut self) {
-        self.static_angle_deg_x = 0.0;
-        self.static_angle_deg_y = 0.0;
-        self.static_angle_deg_z = 0.0;
+        self.orientation = UnitQuaternion::identity();
+        self.translation = Vector3::zeros();
     }
 }
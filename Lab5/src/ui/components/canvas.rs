@@ -1,30 +1,51 @@
 use crate::context::Context;
 use crate::geometry::line2d::Line2D;
+use crate::geometry::line3d::Line3D;
 use crate::geometry::point2d::Point2D;
+use crate::geometry::point3d::Point3D;
+use crate::graphics::mesh::Mesh;
+use crate::graphics::multiview::{View, Viewport};
+use crate::graphics::rasterizer::{Rasterizer, ShadedVertex};
 use crate::graphics::screen::ScreenParams;
+use crate::graphics::visibility::Viewer;
 use crate::ui::styles::colors;
-use eframe::epaint::Shape;
-use egui::{Frame, Response, Sense};
+use eframe::epaint::{Shape, Stroke};
+use egui::{
+    pos2, Align2, Color32, CursorIcon, FontId, Frame, Painter, PointerButton, Pos2, Rect, Response,
+    Sense, TextureHandle, TextureOptions, Vec2,
+};
 
 #[derive(Default)]
 pub struct Canvas {
     pub screen_params: ScreenParams,
 
     pub lines: Vec<Line2D>,
+    pub labels: Vec<(Point2D, String, Color32)>,
+
+    // Shaded triangles of the model, rendered to the texture.
+    pub surface: Vec<[ShadedVertex; 3]>,
+    texture: Option<TextureHandle>,
+
+    // Front, top and side views of the multi-view layout.
+    pub viewports: [Viewport; 3],
+    // Positions of vertices on front, top and side views.
+    projection_points: Vec<[Point2D; 3]>,
 }
 
 impl Canvas {
     pub fn process(&mut self, context: &mut Context, ui: &mut egui::Ui) {
         let mut converted_lines: Vec<Line2D> = vec![];
+        self.labels.clear();
 
         // Axes processing
         if context.axes.are_enabled {
             let axes = context.axes.lines();
 
-            axes.iter().for_each(|line3d| {
-                let axis2d = line3d.to_line2d(&context.trimetric);
-                converted_lines.push(axis2d);
-            });
+            if context.axes.are_labels_enabled {
+                self.labels = Self::axis_labels(context);
+            }
+
+            converted_lines.extend(self.lines2d(context, &axes));
         }
 
         // Animation Step
@@ -33,56 +54,361 @@ impl Canvas {
             .process(ui, &mut context.model, &mut context.rotation);
 
         // Model processing
-        let mut model = context.model.lines(self.screen_params);
+        let mut model = context.model.mesh().clone();
 
         // Model Offset
-        context.offset.apply(&mut model);
+        context.offset.apply(&mut model.vertices);
 
         // Model Rotation
         let pivot_point = context.model.pivot_point(context.offset.statics());
-        context.rotation.apply(&mut model, pivot_point);
+        context.rotation.apply(&mut model.vertices, pivot_point);
+
+        // Orthographic views of the multi-view layout
+        if context.multi_view.is_enabled {
+            self.process_views(context, &model);
+        }
 
         // Model on Orthographic
-        context.orthographic.apply_if_set(&mut model);
+        context.orthographic.apply_if_set(&mut model.vertices);
+
+        // Solid model (faces are flat on orthographic projection, so it is not shaded)
+        self.surface = if context.rasterizer.is_enabled && !context.orthographic.is_enabled {
+            context
+                .rasterizer
+                .triangles(&model, |point| Self::project(context, point))
+        } else {
+            vec![]
+        };
+
+        // Hidden edges (faces are flat on orthographic projection, so all edges are shown)
+        let stroke = context.model.stroke(self.screen_params);
+        let mut hidden_stroke = context.visibility.hidden_stroke;
+        hidden_stroke.width = self.screen_params.value_cm_to_px(hidden_stroke.width);
+
+        let (visible, hidden) = if context.rasterizer.is_enabled && !context.rasterizer.show_edges {
+            (vec![], vec![])
+        } else if context.orthographic.is_enabled {
+            (model.lines(stroke), vec![])
+        } else {
+            let viewer = if context.perspective.is_enabled {
+                context.perspective.viewer()
+            } else {
+                Viewer::Direction(context.trimetric.view_direction())
+            };
+
+            context
+                .visibility
+                .lines(&model, viewer, stroke, hidden_stroke)
+        };
 
         // Model -> 2D
-        model.iter().for_each(|line3d| {
-            let line = line3d.to_line2d(&context.trimetric);
-            converted_lines.push(line);
-        });
+        let mut hidden_lines = self.lines2d(context, &hidden);
+        hidden_lines
+            .iter_mut()
+            .for_each(|line| line.is_dashed = true);
+
+        converted_lines.extend(hidden_lines);
+        converted_lines.extend(self.lines2d(context, &visible));
 
         // Passing all lines to draw() method
         self.lines = converted_lines;
     }
 
-    pub fn draw(&mut self, ui: &mut egui::Ui) -> Response {
+    fn process_views(&mut self, context: &mut Context, model: &Mesh) {
+        let axes = if context.axes.are_enabled {
+            context.axes.lines()
+        } else {
+            vec![]
+        };
+
+        for (viewport, view) in self.viewports.iter_mut().zip(View::ORTHOGRAPHIC) {
+            let Some(viewer) = view.viewer() else {
+                continue;
+            };
+
+            let stroke = context.model.stroke(viewport.screen_params);
+            let mut hidden_stroke = context.visibility.hidden_stroke;
+            hidden_stroke.width = viewport.screen_params.value_cm_to_px(hidden_stroke.width);
+
+            let (visible, hidden) = context
+                .visibility
+                .lines(model, viewer, stroke, hidden_stroke);
+
+            let line2d = |line: &Line3D| {
+                Line2D::new(
+                    view.point2d(line.start),
+                    view.point2d(line.end),
+                    line.stroke,
+                )
+            };
+            let hidden_lines = hidden.iter().map(|line| Line2D {
+                is_dashed: true,
+                ..line2d(line)
+            });
+
+            viewport.lines = axes
+                .iter()
+                .map(line2d)
+                .chain(hidden_lines)
+                .chain(visible.iter().map(line2d))
+                .collect();
+        }
+
+        self.projection_points = if context.multi_view.show_projection_lines {
+            model
+                .vertices
+                .iter()
+                .map(|point| View::ORTHOGRAPHIC.map(|view| view.point2d(*point)))
+                .collect()
+        } else {
+            vec![]
+        };
+    }
+
+    fn lines2d(&self, context: &Context, lines: &[Line3D]) -> Vec<Line2D> {
+        if context.perspective.is_enabled {
+            context.perspective.lines2d(lines)
+        } else {
+            lines
+                .iter()
+                .map(|line3d| line3d.to_line2d(&context.trimetric))
+                .collect()
+        }
+    }
+
+    // Position on the picture plane and depth, linear on it: inverse distance for perspective.
+    fn project(context: &Context, point: Point3D) -> Option<(Point2D, f32)> {
+        if context.perspective.is_enabled {
+            let position = context.perspective.point2d(point)?;
+            Some((position, 1.0 / context.perspective.depth(point)))
+        } else {
+            let direction = context.trimetric.view_direction();
+            let depth = point.x * direction.x + point.y * direction.y + point.z * direction.z;
+            Some((point.to_point2d(&context.trimetric), depth))
+        }
+    }
+
+    // Names of axes with scale factors of parallel projection.
+    fn axis_labels(context: &Context) -> Vec<(Point2D, String, Color32)> {
+        let scale_factors = context.trimetric.scale_factors();
+
+        context
+            .axes
+            .labels()
+            .iter()
+            .zip(scale_factors)
+            .filter_map(|((point, name, color), scale_factor)| {
+                if context.perspective.is_enabled {
+                    let position = context.perspective.point2d(*point)?;
+                    Some((position, name.to_string(), *color))
+                } else {
+                    let position = point.to_point2d(&context.trimetric);
+                    Some((position, format!("{name} ({scale_factor:.2})"), *color))
+                }
+            })
+            .collect()
+    }
+
+    pub fn draw(&mut self, context: &mut Context, ui: &mut egui::Ui) -> Response {
         let painter_size = ui.available_size_before_wrap();
         let (response, painter) = ui.allocate_painter(painter_size, Sense::click_and_drag());
-        self.screen_params.canvas_center = Point2D::from_pos2(response.rect.center());
+
+        let main_rect = if context.multi_view.is_enabled {
+            View::Main.rect(response.rect)
+        } else {
+            response.rect
+        };
+        self.screen_params.canvas_center = Point2D::from_pos2(main_rect.center());
+        let main_painter = painter.with_clip_rect(main_rect);
+
+        if !self.surface.is_empty() {
+            self.draw_surface(ui, &main_painter, main_rect);
+        }
 
         let line_shapes: Vec<Shape> = self
             .lines
             .iter()
             .map(|line| line.to_screen(self.screen_params).to_shape())
             .collect();
-        painter.extend(line_shapes);
+        main_painter.extend(line_shapes);
+
+        for (position, text, color) in &self.labels {
+            main_painter.text(
+                position.to_screen(self.screen_params).to_pos2(),
+                Align2::LEFT_BOTTOM,
+                text,
+                FontId::proportional(14.0),
+                *color,
+            );
+        }
+
+        if context.multi_view.is_enabled {
+            self.draw_views(context, &painter, response.rect);
+        }
+
+        // Zoom of the view under the pointer
+        if let Some(position) = response.hover_pos() {
+            let delta = ui.input(|i| i.smooth_scroll_delta.y);
+            if delta != 0.0 {
+                let viewport = self.viewport_at(context, response.rect, position);
+                let screen_params = match viewport {
+                    Some(index) => &mut self.viewports[index].screen_params,
+                    None => &mut self.screen_params,
+                };
+                screen_params.px_per_cm += delta * 0.1;
+            }
+        }
 
         // Check for dragging
-        self.screen_params.update_offset_on_drag(ui, &response);
+        let press_origin = ui.input(|i| i.pointer.press_origin());
+        let viewport =
+            press_origin.and_then(|position| self.viewport_at(context, response.rect, position));
+
+        if let Some(index) = viewport {
+            self.viewports[index]
+                .screen_params
+                .update_offset_on_drag(ui, &response);
+        } else if context.arcball.is_enabled {
+            self.rotate_on_drag(context, ui, &response, main_rect);
+        } else {
+            self.screen_params.update_offset_on_drag(ui, &response);
+        }
 
         response
     }
 
+    // Front, top and side views with projection lines between them, borders and names of views.
+    fn draw_views(&mut self, context: &Context, painter: &Painter, rect: Rect) {
+        for (viewport, view) in self.viewports.iter_mut().zip(View::ORTHOGRAPHIC) {
+            viewport.screen_params.canvas_center = Point2D::from_pos2(view.rect(rect).center());
+        }
+
+        let stroke = context.multi_view.projection_stroke;
+        for points in &self.projection_points {
+            let [front, top, side] = [0, 1, 2].map(|i| {
+                points[i]
+                    .to_screen(self.viewports[i].screen_params)
+                    .to_pos2()
+            });
+
+            painter.line_segment([front, top], stroke);
+            painter.line_segment([front, side], stroke);
+        }
+
+        for (viewport, view) in self.viewports.iter().zip(View::ORTHOGRAPHIC) {
+            let line_shapes: Vec<Shape> = viewport
+                .lines
+                .iter()
+                .map(|line| line.to_screen(viewport.screen_params).to_shape())
+                .collect();
+            painter.with_clip_rect(view.rect(rect)).extend(line_shapes);
+        }
+
+        let border = Stroke::new(1.0, colors::DARK_GRAY);
+        painter.line_segment([rect.center_top(), rect.center_bottom()], border);
+        painter.line_segment([rect.left_center(), rect.right_center()], border);
+
+        for view in [View::Top, View::Main, View::Front, View::Side] {
+            painter.text(
+                view.rect(rect).left_top() + Vec2::new(5.0, 5.0),
+                Align2::LEFT_TOP,
+                view.name(),
+                FontId::proportional(14.0),
+                colors::DARK_GRAY,
+            );
+        }
+    }
+
+    // Index of the orthographic viewport, which contains the position.
+    fn viewport_at(&self, context: &Context, rect: Rect, position: Pos2) -> Option<usize> {
+        if !context.multi_view.is_enabled {
+            return None;
+        }
+
+        View::ORTHOGRAPHIC
+            .iter()
+            .position(|view| view.rect(rect).contains(position))
+    }
+
+    // Left drag rotates the model by the arcball, right drag pans.
+    fn rotate_on_drag(
+        &mut self, context: &mut Context, ui: &egui::Ui, response: &Response, rect: Rect,
+    ) {
+        if response.dragged_by(PointerButton::Secondary) {
+            self.screen_params.update_offset_on_drag(ui, response);
+        }
+
+        if !response.dragged_by(PointerButton::Primary) {
+            return;
+        }
+        let Some(position) = response.interact_pointer_pos() else {
+            return;
+        };
+        ui.ctx().set_cursor_icon(CursorIcon::Grabbing);
+
+        // Positions relative to the center of the view, OY goes up.
+        let center = rect.center();
+        let to = Vec2::new(position.x - center.x, center.y - position.y);
+        let delta = response.drag_delta();
+        let from = to - Vec2::new(delta.x, -delta.y);
+        let radius = rect.width().min(rect.height()) / 2.0;
+
+        let camera_basis = if context.perspective.is_enabled {
+            context.perspective.camera_basis()
+        } else {
+            context.trimetric.camera_basis()
+        };
+
+        if let Some(rotation) = context.arcball.rotation(from, to, radius, camera_basis) {
+            context.rotation.rotate(rotation);
+            ui.ctx().request_repaint();
+        }
+    }
+
+    fn draw_surface(&mut self, ui: &egui::Ui, painter: &Painter, rect: Rect) {
+        let pixels_per_point = ui.ctx().pixels_per_point();
+        let size = [
+            (rect.width() * pixels_per_point).round() as usize,
+            (rect.height() * pixels_per_point).round() as usize,
+        ];
+        if size.contains(&0) {
+            return;
+        }
+
+        let image = Rasterizer::render(&self.surface, size, |point| {
+            let point = point.to_screen(self.screen_params);
+            Point2D::new(
+                (point.x - rect.min.x) * pixels_per_point,
+                (point.y - rect.min.y) * pixels_per_point,
+            )
+        });
+
+        let texture = match &mut self.texture {
+            Some(texture) => {
+                texture.set(image, TextureOptions::NEAREST);
+                texture
+            },
+            None => self.texture.insert(ui.ctx().load_texture(
+                "Surface",
+                image,
+                TextureOptions::NEAREST,
+            )),
+        };
+
+        painter.image(
+            texture.id(),
+            rect,
+            Rect::from_min_max(pos2(0.0, 0.0), pos2(1.0, 1.0)),
+            Color32::WHITE,
+        );
+    }
+
     pub fn show_content(&mut self, context: &mut Context, ui: &mut egui::Ui) {
         Frame::canvas(ui.style())
             .fill(colors::WHITE)
             .show(ui, |ui| {
-                ui.input(|i| {
-                    let delta = i.smooth_scroll_delta.y;
-                    self.screen_params.px_per_cm += delta * 0.1;
-                });
                 self.process(context, ui);
-                self.draw(ui);
+                self.draw(context, ui);
             });
     }
 }
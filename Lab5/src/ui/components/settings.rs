@@ -1,471 +1,1171 @@
 use crate::context::Context;
+use crate::graphics::generator::{Profile, Solid};
+use crate::graphics::rasterizer::{Coloring, Shading};
 use crate::graphics::screen::{MAX_PX_PER_CM, MIN_PX_PER_CM};
+use crate::graphics::visibility::DisplayMode;
+use crate::operations::animation::Easing;
 use crate::projections::orthographic::OrthographicMode;
+use crate::projections::perspective::PerspectivePreset;
+use crate::projections::trimetric::{AxonometricPreset, ProjectionMode};
 use crate::ui::components::canvas::Canvas;
+use crate::ui::components::message_box::MessageBox;
 use crate::ui::styles::colors;
 use egui::{DragValue, Grid, RichText};
 
 pub const SETTINGS_PANEL_WIDTH: f32 = 250.0;
 
-pub fn show_panel(context: &mut Context, canvas: &mut Canvas, ui: &mut egui::Ui) {
-    egui::ScrollArea::vertical().show(ui, |ui| {
-        ui.vertical_centered(|ui| {
-            ui.heading("Settings");
-        });
+#[derive(Default)]
+pub struct Settings {
+    error_box: Option<MessageBox>,
+}
 
-        ui.add_space(10.0);
+impl Settings {
+    pub fn show_panel(&mut self, context: &mut Context, canvas: &mut Canvas, ui: &mut egui::Ui) {
+        egui::ScrollArea::vertical().show(ui, |ui| {
+            ui.vertical_centered(|ui| {
+                ui.heading("Settings");
+            });
 
-        ui.horizontal(|ui| {
-            ui.label("Pixels per Centimeter:");
-            ui.add(
-                DragValue::new(&mut canvas.screen_params.px_per_cm)
-                    .speed(1)
-                    .range(MIN_PX_PER_CM..=MAX_PX_PER_CM)
-                    .suffix(" px"),
-            );
-        });
+            ui.add_space(10.0);
 
-        ui.add_space(10.0);
+            ui.horizontal(|ui| {
+                ui.label("Pixels per Centimeter:");
+                ui.add(
+                    DragValue::new(&mut canvas.screen_params.px_per_cm)
+                        .speed(1)
+                        .range(MIN_PX_PER_CM..=MAX_PX_PER_CM)
+                        .suffix(" px"),
+                );
+            });
 
-        ui.vertical_centered(|ui| {
-            if ui.button("Reset to Default Settings").clicked() {
-                reset_to_defaults(context, canvas);
-            }
-        });
+            ui.add_space(10.0);
 
-        ui.add_space(10.0);
+            ui.vertical_centered(|ui| {
+                if ui.button("Reset to Default Settings").clicked() {
+                    self.reset_to_defaults(context, canvas);
+                }
+            });
 
-        ui.collapsing("Animation", |ui| {
-            ui.horizontal_wrapped(|ui| {
-                ui.label("Status: ");
-                if context.animation.is_running {
-                    ui.label(RichText::new("Running!").color(colors::LIME));
-                } else {
-                    ui.label(RichText::new("Stopped.").color(colors::RED));
+            ui.add_space(10.0);
+
+            ui.collapsing("Animation", |ui| {
+                ui.horizontal_wrapped(|ui| {
+                    ui.label("Status: ");
+                    if context.animation.is_running {
+                        ui.label(RichText::new("Running!").color(colors::LIME));
+                    } else {
+                        ui.label(RichText::new("Stopped.").color(colors::RED));
+                    }
+
+                    ui.vertical_centered(|ui| {
+                        if ui.button("Start / Stop").clicked() {
+                            context.animation.checkout_status();
+                        }
+                    });
+                });
+
+                ui.add_space(10.0);
+
+                ui.vertical_centered(|ui| {
+                    ui.label(RichText::new("Animation Parameters").color(colors::WHITE));
+                });
+
+                ui.add_space(5.0);
+
+                ui.checkbox(&mut context.animation.use_radius, "Use Radius");
+                if context.animation.use_radius {
+                    Grid::new("AnimationRadiusParameters")
+                        .num_columns(2)
+                        .show(ui, |ui| {
+                            ui.label("Minimum: ");
+                            ui.add(
+                                DragValue::new(&mut context.animation.min_radius)
+                                    .speed(0.1)
+                                    .range(0.1..=f32::MAX)
+                                    .suffix(" cm"),
+                            );
+                            ui.end_row();
+
+                            ui.label("Maximum: ");
+                            ui.add(
+                                DragValue::new(&mut context.animation.max_radius)
+                                    .speed(0.1)
+                                    .range(0.1..=f32::MAX)
+                                    .suffix(" cm"),
+                            );
+                            ui.end_row();
+
+                            ui.label("Step: ");
+                            ui.add(
+                                DragValue::new(&mut context.animation.step_radius)
+                                    .speed(0.01)
+                                    .range(0.01..=f32::MAX)
+                                    .suffix(" cm"),
+                            );
+                            ui.end_row();
+                        });
+                }
+
+                ui.add_space(5.0);
+
+                ui.checkbox(&mut context.animation.use_rotation, "Use Rotation");
+                if context.animation.use_rotation {
+                    Grid::new("AnimationRotationParameters")
+                        .num_columns(2)
+                        .show(ui, |ui| {
+                            ui.label("Step, OX: ");
+                            ui.add(
+                                DragValue::new(&mut context.animation.step_x)
+                                    .speed(1)
+                                    .range(-360..=360)
+                                    .suffix("°"),
+                            );
+                            ui.end_row();
+
+                            ui.label("Step, OY: ");
+                            ui.add(
+                                DragValue::new(&mut context.animation.step_y)
+                                    .speed(1)
+                                    .range(-360..=360)
+                                    .suffix("°"),
+                            );
+                            ui.end_row();
+
+                            ui.label("Step, OZ: ");
+                            ui.add(
+                                DragValue::new(&mut context.animation.step_z)
+                                    .speed(1)
+                                    .range(-360..=360)
+                                    .suffix("°"),
+                            );
+                            ui.end_row();
+                        });
+                }
+
+                ui.add_space(5.0);
+
+                ui.checkbox(&mut context.animation.use_keyframes, "Use Keyframes")
+                    .on_hover_text("Orientation is interpolated between saved ones.");
+                if context.animation.use_keyframes {
+                    Grid::new("AnimationKeyframeParameters")
+                        .num_columns(2)
+                        .show(ui, |ui| {
+                            ui.label("Keyframes: ");
+                            ui.label(context.animation.keyframes.len().to_string());
+                            ui.end_row();
+
+                            ui.label("Duration: ");
+                            ui.add(
+                                DragValue::new(&mut context.animation.duration_s)
+                                    .speed(0.1)
+                                    .range(0.1..=60.0)
+                                    .suffix(" s"),
+                            )
+                            .on_hover_text("Time of moving between neighbour keyframes.");
+                            ui.end_row();
+
+                            ui.label("Easing: ");
+                            egui::ComboBox::from_id_salt("AnimationEasing")
+                                .selected_text(match context.animation.easing {
+                                    Easing::Linear => "Linear",
+                                    Easing::EaseIn => "Ease In",
+                                    Easing::EaseOut => "Ease Out",
+                                    Easing::EaseInOut => "Ease In-Out",
+                                })
+                                .show_ui(ui, |ui| {
+                                    let easing = &mut context.animation.easing;
+                                    ui.selectable_value(easing, Easing::Linear, "Linear");
+                                    ui.selectable_value(easing, Easing::EaseIn, "Ease In");
+                                    ui.selectable_value(easing, Easing::EaseOut, "Ease Out");
+                                    ui.selectable_value(easing, Easing::EaseInOut, "Ease In-Out");
+                                });
+                            ui.end_row();
+                        });
+
+                    ui.checkbox(&mut context.animation.is_looped, "Loop")
+                        .on_hover_text("Last keyframe goes back to the first one.");
+
+                    ui.vertical_centered_justified(|ui| {
+                        if ui.button("Save Current Orientation").clicked() {
+                            let orientation = context.rotation.orientation();
+                            context.animation.keyframes.push(orientation);
+                        }
+
+                        if ui
+                            .add_enabled(
+                                !context.animation.keyframes.is_empty(),
+                                egui::Button::new("Clear Keyframes"),
+                            )
+                            .clicked()
+                        {
+                            context.animation.keyframes.clear();
+                            context.animation.restart_keyframes();
+                        }
+                    });
                 }
+            });
+
+            ui.add_space(10.0);
+
+            ui.collapsing("Axis Settings", |ui| {
+                Grid::new("AxisSettings").num_columns(2).show(ui, |ui| {
+                    ui.checkbox(&mut context.axes.are_enabled, "Enable Axes");
+                    ui.checkbox(&mut context.axes.are_labels_enabled, "Labels")
+                        .on_hover_text("Names of axes with their scale factors.");
+                    ui.end_row();
+
+                    ui.label("Unit Length:");
+                    ui.add(
+                        DragValue::new(&mut canvas.screen_params.unit_length)
+                            .speed(1)
+                            .range(1.0..=10.0)
+                            .suffix(" cm"),
+                    );
+                    ui.end_row();
+
+                    ui.label("Axes Length:");
+                    ui.add(
+                        DragValue::new(&mut context.axes.axis_length)
+                            .speed(1)
+                            .range(1..=u32::MAX)
+                            .suffix(" cm"),
+                    );
+                    ui.end_row();
+                });
+
+                ui.add_space(10.0);
+
+                ui.label(RichText::new("Rotation").color(colors::WHITE));
+                ui.add_space(5.0);
+                Grid::new("AxisRotation").num_columns(2).show(ui, |ui| {
+                    ui.label("OX:");
+                    ui.add(
+                        DragValue::new(&mut context.trimetric.angle_deg_x)
+                            .speed(1)
+                            .range(-360..=360)
+                            .suffix("°"),
+                    );
+                    ui.end_row();
+
+                    ui.label("OY:");
+                    ui.add(
+                        DragValue::new(&mut context.trimetric.angle_deg_y)
+                            .speed(1)
+                            .range(-360..=360)
+                            .suffix("°"),
+                    );
+                    ui.end_row();
+                });
+
+                ui.add_space(10.0);
+
+                ui.label(RichText::new("Stroke").color(colors::WHITE));
+                ui.add_space(5.0);
+                Grid::new("AxisStrokes").num_columns(2).show(ui, |ui| {
+                    ui.label("Axis X:");
+                    egui::color_picker::color_edit_button_srgba(
+                        ui,
+                        &mut context.axes.color_x,
+                        egui::color_picker::Alpha::Opaque,
+                    );
+                    ui.end_row();
+
+                    ui.label("Axis Y:");
+                    egui::color_picker::color_edit_button_srgba(
+                        ui,
+                        &mut context.axes.color_y,
+                        egui::color_picker::Alpha::Opaque,
+                    );
+                    ui.end_row();
+
+                    ui.label("Axis Z:");
+                    egui::color_picker::color_edit_button_srgba(
+                        ui,
+                        &mut context.axes.color_z,
+                        egui::color_picker::Alpha::Opaque,
+                    );
+                    ui.end_row();
+                });
+
+                ui.add_space(10.0);
 
                 ui.vertical_centered(|ui| {
-                    if ui.button("Start / Stop").clicked() {
-                        context.animation.checkout_status();
+                    if ui.button("Reset Settings").clicked() {
+                        context.axes = Default::default();
+                        canvas.screen_params.unit_length = 1.0;
+                    }
+                });
+                ui.vertical_centered(|ui| {
+                    if ui.button("Reset Rotation").clicked() {
+                        context.trimetric = Default::default();
                     }
                 });
             });
 
             ui.add_space(10.0);
 
-            ui.vertical_centered(|ui| {
-                ui.label(RichText::new("Animation Parameters").color(colors::WHITE));
-            });
+            ui.collapsing("Parallel Projection", |ui| {
+                ui.horizontal_wrapped(|ui| {
+                    ui.radio_value(
+                        &mut context.trimetric.mode,
+                        ProjectionMode::Axonometric,
+                        "Axonometric",
+                    );
+                    ui.radio_value(
+                        &mut context.trimetric.mode,
+                        ProjectionMode::Cavalier,
+                        "Cavalier",
+                    );
+                    ui.radio_value(
+                        &mut context.trimetric.mode,
+                        ProjectionMode::Cabinet,
+                        "Cabinet",
+                    );
+                });
 
-            ui.add_space(5.0);
+                ui.add_space(5.0);
+
+                match context.trimetric.mode {
+                    ProjectionMode::Axonometric => {
+                        ui.horizontal(|ui| {
+                            if ui.button("Isometric").clicked() {
+                                context.trimetric.set_preset(AxonometricPreset::Isometric);
+                            }
+                            if ui
+                                .button("Dimetric")
+                                .on_hover_text("Standard dimetric projection: 0.94, 0.94, 0.47.")
+                                .clicked()
+                            {
+                                context.trimetric.set_preset(AxonometricPreset::Dimetric);
+                            }
+                        });
+
+                        ui.add_space(5.0);
+                        ui.label("Angles are set in Axis Settings.");
+                    },
+                    ProjectionMode::Cavalier | ProjectionMode::Cabinet => {
+                        ui.horizontal_wrapped(|ui| {
+                            ui.label("Receding Angle: ");
+                            ui.add(
+                                DragValue::new(&mut context.trimetric.receding_angle_deg)
+                                    .speed(1)
+                                    .range(0..=360)
+                                    .suffix("°"),
+                            );
+                        });
+                    },
+                }
+
+                ui.add_space(10.0);
 
-            ui.checkbox(&mut context.animation.use_radius, "Use Radius");
-            if context.animation.use_radius {
-                Grid::new("AnimationRadiusParameters")
+                let [x, y, z] = context.trimetric.scale_factors();
+                Grid::new("ProjectionCoefficients")
                     .num_columns(2)
                     .show(ui, |ui| {
-                        ui.label("Minimum: ");
-                        ui.add(
-                            DragValue::new(&mut context.animation.min_radius)
-                                .speed(0.1)
-                                .range(0.1..=f32::MAX)
-                                .suffix(" cm"),
-                        );
+                        ui.label("Kind:");
+                        ui.label(context.trimetric.kind());
                         ui.end_row();
 
-                        ui.label("Maximum: ");
-                        ui.add(
-                            DragValue::new(&mut context.animation.max_radius)
-                                .speed(0.1)
-                                .range(0.1..=f32::MAX)
-                                .suffix(" cm"),
-                        );
+                        ui.label("Coefficient, OX:");
+                        ui.label(format!("{x:.3}"));
                         ui.end_row();
 
-                        ui.label("Step: ");
-                        ui.add(
-                            DragValue::new(&mut context.animation.step_radius)
-                                .speed(0.01)
-                                .range(0.01..=f32::MAX)
-                                .suffix(" cm"),
-                        );
+                        ui.label("Coefficient, OY:");
+                        ui.label(format!("{y:.3}"));
+                        ui.end_row();
+
+                        ui.label("Coefficient, OZ:");
+                        ui.label(format!("{z:.3}"));
                         ui.end_row();
                     });
-            }
 
-            ui.add_space(5.0);
+                ui.add_space(10.0);
+
+                ui.vertical_centered(|ui| {
+                    if ui.button("Reset Settings").clicked() {
+                        context.trimetric = Default::default();
+                    }
+                });
+            });
 
-            ui.checkbox(&mut context.animation.use_rotation, "Use Rotation");
-            if context.animation.use_rotation {
-                Grid::new("AnimationRotationParameters")
+            ui.add_space(10.0);
+
+            ui.collapsing("Drag & Offset", |ui| {
+                Grid::new("DragAndOffsetSettings")
                     .num_columns(2)
                     .show(ui, |ui| {
-                        ui.label("Step, OX: ");
-                        ui.add(
-                            DragValue::new(&mut context.animation.step_x)
-                                .speed(1)
-                                .range(-360..=360)
-                                .suffix("°"),
+                        ui.checkbox(
+                            &mut canvas.screen_params.is_dragging_offset_enabled,
+                            "Enable",
                         );
+                        if ui.button("Reset Settings").clicked() {
+                            canvas.screen_params.offset = Default::default();
+                            canvas.screen_params.is_dragging_offset_enabled = true;
+                        }
                         ui.end_row();
 
-                        ui.label("Step, OY: ");
-                        ui.add(
-                            DragValue::new(&mut context.animation.step_y)
-                                .speed(1)
-                                .range(-360..=360)
-                                .suffix("°"),
-                        );
+                        ui.label("X:");
+                        ui.label(format!("{:.2}", canvas.screen_params.offset.0));
                         ui.end_row();
+                        ui.label("Y:");
+                        ui.label(format!("{:.2}", -canvas.screen_params.offset.1));
+                        ui.end_row();
+                    });
+            });
+
+            ui.add_space(10.0);
+
+            ui.collapsing("Euclidean Offset", |ui| {
+                Grid::new("OffsetSettings").num_columns(2).show(ui, |ui| {
+                    ui.label("Offset X: ");
+                    ui.add(
+                        DragValue::new(&mut context.offset.x)
+                            .speed(0.1)
+                            .suffix(" cm"),
+                    );
+                    ui.end_row();
+
+                    ui.label("Offset Y: ");
+                    ui.add(
+                        DragValue::new(&mut context.offset.y)
+                            .speed(0.1)
+                            .suffix(" cm"),
+                    );
+                    ui.end_row();
+
+                    ui.label("Offset Z: ");
+                    ui.add(
+                        DragValue::new(&mut context.offset.z)
+                            .speed(0.1)
+                            .suffix(" cm"),
+                    );
+                    ui.end_row();
+                });
+
+                ui.add_space(10.0);
 
-                        ui.label("Step, OZ: ");
+                ui.vertical_centered_justified(|ui| {
+                    if ui.button("Apply").clicked() {
+                        context.offset.is_applied = true;
+                    }
+                });
+
+                ui.vertical_centered_justified(|ui| {
+                    if ui.button("Clear Fields").clicked() {
+                        context.offset.x = 0.0;
+                        context.offset.y = 0.0;
+                        context.offset.z = 0.0;
+                    }
+                });
+
+                ui.vertical_centered_justified(|ui| {
+                    if ui.button("Reset Position").clicked() {
+                        context.offset.reset_position();
+                    }
+                });
+            });
+
+            ui.add_space(10.0);
+
+            ui.collapsing("Euclidean Rotation", |ui| {
+                Grid::new("RotationSettings").num_columns(2).show(ui, |ui| {
+                    ui.label("OX Angle: ");
+                    ui.add(
+                        DragValue::new(&mut context.rotation.angle_deg_x)
+                            .speed(1)
+                            .range(-360..=360)
+                            .suffix("°"),
+                    );
+                    ui.end_row();
+
+                    ui.label("OY Angle: ");
+                    ui.add(
+                        DragValue::new(&mut context.rotation.angle_deg_y)
+                            .speed(1)
+                            .range(-360..=360)
+                            .suffix("°"),
+                    );
+                    ui.end_row();
+
+                    ui.label("OZ Angle: ");
+                    ui.add(
+                        DragValue::new(&mut context.rotation.angle_deg_z)
+                            .speed(1)
+                            .range(-360..=360)
+                            .suffix("°"),
+                    );
+                    ui.end_row();
+                });
+
+                ui.add_space(10.0);
+
+                ui.vertical_centered_justified(|ui| {
+                    if ui.button("Apply").clicked() {
+                        context.rotation.is_applied = true;
+                    }
+                });
+
+                ui.vertical_centered_justified(|ui| {
+                    if ui.button("Clear Fields").clicked() {
+                        context.rotation.angle_deg_x = 0.0;
+                        context.rotation.angle_deg_y = 0.0;
+                        context.rotation.angle_deg_z = 0.0;
+                    }
+                });
+
+                ui.vertical_centered_justified(|ui| {
+                    if ui.button("Reset Position").clicked() {
+                        context.rotation.reset_position();
+                    }
+                });
+
+                ui.add_space(10.0);
+
+                ui.vertical_centered(|ui| {
+                    ui.label(RichText::new("Rotation Around Axis").color(colors::WHITE));
+                });
+
+                ui.add_space(5.0);
+
+                Grid::new("AxisRotationSettings")
+                    .num_columns(2)
+                    .show(ui, |ui| {
+                        ui.label("Direction: ");
+                        ui.horizontal(|ui| {
+                            ui.add(DragValue::new(&mut context.rotation.axis.x).speed(0.1));
+                            ui.add(DragValue::new(&mut context.rotation.axis.y).speed(0.1));
+                            ui.add(DragValue::new(&mut context.rotation.axis.z).speed(0.1));
+                        });
+                        ui.end_row();
+
+                        ui.label("Point: ");
+                        ui.horizontal(|ui| {
+                            ui.add(DragValue::new(&mut context.rotation.axis_point.x).speed(0.1));
+                            ui.add(DragValue::new(&mut context.rotation.axis_point.y).speed(0.1));
+                            ui.add(DragValue::new(&mut context.rotation.axis_point.z).speed(0.1));
+                        });
+                        ui.end_row();
+
+                        ui.label("Angle: ");
                         ui.add(
-                            DragValue::new(&mut context.animation.step_z)
+                            DragValue::new(&mut context.rotation.axis_angle_deg)
                                 .speed(1)
                                 .range(-360..=360)
                                 .suffix("°"),
                         );
                         ui.end_row();
                     });
-            }
-        });
 
-        ui.add_space(10.0);
+                ui.add_space(5.0);
 
-        ui.collapsing("Axis Settings", |ui| {
-            Grid::new("AxisSettings").num_columns(2).show(ui, |ui| {
-                ui.checkbox(&mut context.axes.are_enabled, "Enable Axes");
-                ui.end_row();
+                ui.vertical_centered_justified(|ui| {
+                    if ui.button("Rotate Around Axis").clicked() {
+                        context.rotation.is_applied_around_axis = true;
+                    }
+                });
 
-                ui.label("Unit Length:");
-                ui.add(
-                    DragValue::new(&mut canvas.screen_params.unit_length)
-                        .speed(1)
-                        .range(1.0..=10.0)
-                        .suffix(" cm"),
-                );
-                ui.end_row();
+                ui.add_space(10.0);
 
-                ui.label("Axes Length:");
-                ui.add(
-                    DragValue::new(&mut context.axes.axis_length)
-                        .speed(1)
-                        .range(1..=u32::MAX)
-                        .suffix(" cm"),
-                );
-                ui.end_row();
-            });
+                ui.vertical_centered(|ui| {
+                    ui.label(RichText::new("Current Orientation").color(colors::WHITE));
+                });
 
-            ui.add_space(10.0);
+                ui.add_space(5.0);
 
-            ui.label(RichText::new("Rotation").color(colors::WHITE));
-            ui.add_space(5.0);
-            Grid::new("AxisRotation").num_columns(2).show(ui, |ui| {
-                ui.label("OX:");
-                ui.add(
-                    DragValue::new(&mut context.trimetric.angle_deg_x)
-                        .speed(1)
-                        .range(-360..=360)
-                        .suffix("°"),
-                );
-                ui.end_row();
+                let (angle_x, angle_y, angle_z) = context.rotation.euler_angles_deg();
+                Grid::new("RotationOrientation")
+                    .num_columns(2)
+                    .show(ui, |ui| {
+                        ui.label("OX Angle: ");
+                        ui.label(format!("{angle_x:.1}°"));
+                        ui.end_row();
 
-                ui.label("OY:");
-                ui.add(
-                    DragValue::new(&mut context.trimetric.angle_deg_y)
-                        .speed(1)
-                        .range(-360..=360)
-                        .suffix("°"),
-                );
-                ui.end_row();
+                        ui.label("OY Angle: ");
+                        ui.label(format!("{angle_y:.1}°"));
+                        ui.end_row();
+
+                        ui.label("OZ Angle: ");
+                        ui.label(format!("{angle_z:.1}°"));
+                        ui.end_row();
+                    });
+
+                ui.add_space(10.0);
+
+                ui.checkbox(&mut context.arcball.is_enabled, "Arcball Control")
+                    .on_hover_text(
+                        "Left drag rotates the model, right drag pans, the wheel zooms.",
+                    );
+                if context.arcball.is_enabled {
+                    Grid::new("ArcballSettings").num_columns(2).show(ui, |ui| {
+                        ui.label("Sensitivity: ");
+                        ui.add(
+                            DragValue::new(&mut context.arcball.sensitivity)
+                                .speed(0.05)
+                                .range(0.1..=5.0),
+                        );
+                        ui.end_row();
+                    });
+                }
             });
 
             ui.add_space(10.0);
 
-            ui.label(RichText::new("Stroke").color(colors::WHITE));
-            ui.add_space(5.0);
-            Grid::new("AxisStrokes").num_columns(2).show(ui, |ui| {
-                ui.label("Axis X:");
-                egui::color_picker::color_edit_button_srgba(
-                    ui,
-                    &mut context.axes.color_x,
-                    egui::color_picker::Alpha::Opaque,
-                );
-                ui.end_row();
+            ui.collapsing("Model Settings", |ui| {
+                Grid::new("ModelSettings").num_columns(2).show(ui, |ui| {
+                    ui.label("Radius:");
+                    ui.add(
+                        DragValue::new(&mut context.model.radius)
+                            .speed(0.1)
+                            .range(0.1..=f32::MAX)
+                            .suffix(" cm"),
+                    );
+                    ui.end_row();
 
-                ui.label("Axis Y:");
-                egui::color_picker::color_edit_button_srgba(
-                    ui,
-                    &mut context.axes.color_y,
-                    egui::color_picker::Alpha::Opaque,
-                );
-                ui.end_row();
+                    if context.model.mesh.is_none() {
+                        ui.label("Thickness:");
+                        ui.add(
+                            DragValue::new(&mut context.model.thickness)
+                                .speed(0.1)
+                                .range(0.1..=f32::MAX)
+                                .suffix(" cm"),
+                        );
+                        ui.end_row();
+                    }
+                });
 
-                ui.label("Axis Z:");
-                egui::color_picker::color_edit_button_srgba(
-                    ui,
-                    &mut context.axes.color_z,
-                    egui::color_picker::Alpha::Opaque,
-                );
-                ui.end_row();
-            });
+                ui.add_space(5.0);
 
-            ui.add_space(10.0);
+                if let Some(mesh) = &context.model.mesh {
+                    ui.label(format!("File: {}", mesh.name));
+                    ui.label(format!(
+                        "Vertices: {}, Edges: {}, Faces: {}",
+                        mesh.vertices.len(),
+                        mesh.edges.len(),
+                        mesh.faces.len()
+                    ));
+                } else {
+                    let generator = &mut context.model.generator;
+
+                    Grid::new("GeneratorSettings")
+                        .num_columns(2)
+                        .show(ui, |ui| {
+                            ui.label("Profile:");
+                            ui.horizontal(|ui| {
+                                ui.radio_value(&mut generator.profile, Profile::Star, "Star");
+                                ui.radio_value(&mut generator.profile, Profile::Polygon, "Polygon");
+                            });
+                            ui.end_row();
+
+                            ui.label("Solid:");
+                            ui.horizontal(|ui| {
+                                ui.radio_value(&mut generator.solid, Solid::Prism, "Prism");
+                                ui.radio_value(&mut generator.solid, Solid::Antiprism, "Antiprism");
+                            });
+                            ui.end_row();
+
+                            if generator.profile == Profile::Star {
+                                ui.label("Rays:");
+                            } else {
+                                ui.label("Sides:");
+                            }
+                            ui.add(DragValue::new(&mut generator.rays).speed(0.1).range(3..=50));
+                            ui.end_row();
+
+                            if generator.profile == Profile::Star {
+                                ui.label("Inner Radius:");
+                                ui.add(
+                                    DragValue::new(&mut generator.inner_radius)
+                                        .speed(0.1)
+                                        .range(0.1..=f32::MAX)
+                                        .suffix(" cm"),
+                                );
+                                ui.end_row();
+                            }
+
+                            ui.label("Twist:");
+                            ui.add(
+                                DragValue::new(&mut generator.twist_deg)
+                                    .speed(1)
+                                    .range(-360..=360)
+                                    .suffix("°"),
+                            );
+                            ui.end_row();
+
+                            ui.label("Taper:");
+                            ui.add(
+                                DragValue::new(&mut generator.taper)
+                                    .speed(0.01)
+                                    .range(0.0..=2.0),
+                            )
+                            .on_hover_text("Scale of the top: 1 for prism, 0 for pyramid.");
+                            ui.end_row();
+
+                            ui.label("Bevel:");
+                            ui.add(
+                                DragValue::new(&mut generator.bevel)
+                                    .speed(0.01)
+                                    .range(0.0..=f32::MAX)
+                                    .suffix(" cm"),
+                            )
+                            .on_hover_text("Chamfers on edges of the bottom and the top.");
+                            ui.end_row();
+                        });
 
-            ui.vertical_centered(|ui| {
-                if ui.button("Reset Settings").clicked() {
-                    context.axes = Default::default();
-                    canvas.screen_params.unit_length = 1.0;
-                }
-            });
-            ui.vertical_centered(|ui| {
-                if ui.button("Reset Rotation").clicked() {
-                    context.trimetric = Default::default();
+                    ui.add_space(5.0);
+
+                    let mesh = context.model.mesh();
+                    ui.label(format!("Figure: {}", mesh.name));
+                    ui.label(format!(
+                        "Vertices: {}, Edges: {}, Faces: {}",
+                        mesh.vertices.len(),
+                        mesh.edges.len(),
+                        mesh.faces.len()
+                    ));
                 }
+
+                ui.add_space(5.0);
+
+                ui.vertical_centered_justified(|ui| {
+                    if ui
+                        .button("Load Model")
+                        .on_hover_text(
+                            "Wireframe from OBJ, OFF or STL file, fitted into the radius.",
+                        )
+                        .clicked()
+                    {
+                        if let Err(e) = context.model.load_dialog() {
+                            let mut error_message =
+                                format!("Error occurred while loading model:\n{}\n", e);
+                            if let Some(additional_info) = e.get_additional_info() {
+                                error_message.push_str(
+                                    format!("\nAdditional Info:\n{}", additional_info).as_str(),
+                                );
+                            }
+
+                            self.error_box =
+                                Some(MessageBox::new("Error!".to_string(), error_message));
+                        }
+                    }
+
+                    if ui
+                        .add_enabled(
+                            context.model.mesh.is_some(),
+                            egui::Button::new("Use Generated Figure"),
+                        )
+                        .clicked()
+                    {
+                        context.model.mesh = None;
+                    }
+
+                    if ui
+                        .button("Export OBJ")
+                        .on_hover_text("Faces of the figure, without transformations.")
+                        .clicked()
+                    {
+                        if let Err(e) = context.model.save_dialog() {
+                            let mut error_message =
+                                format!("Error occurred while exporting model:\n{}\n", e);
+                            if let Some(additional_info) = e.get_additional_info() {
+                                error_message.push_str(
+                                    format!("\nAdditional Info:\n{}", additional_info).as_str(),
+                                );
+                            }
+
+                            self.error_box =
+                                Some(MessageBox::new("Error!".to_string(), error_message));
+                        }
+                    }
+                });
+
+                ui.add_space(10.0);
+
+                ui.vertical_centered(|ui| {
+                    if ui.button("Reset Settings").clicked() {
+                        context.model = Default::default();
+                    }
+                });
             });
-        });
 
-        ui.add_space(10.0);
+            ui.add_space(10.0);
+
+            ui.collapsing("Visibility Settings", |ui| {
+                ui.vertical(|ui| {
+                    ui.radio_value(
+                        &mut context.visibility.mode,
+                        DisplayMode::AllEdges,
+                        "All Edges",
+                    );
+                    ui.radio_value(
+                        &mut context.visibility.mode,
+                        DisplayMode::BackFaceCulling,
+                        "Back-Face Culling",
+                    )
+                    .on_hover_text("Edges of faces, turned away from the viewer, are hidden.");
+                    ui.radio_value(
+                        &mut context.visibility.mode,
+                        DisplayMode::HiddenLineRemoval,
+                        "Hidden-Line Removal",
+                    )
+                    .on_hover_text("Edges are split, where they go behind other faces.");
+                });
+
+                ui.add_space(5.0);
 
-        ui.collapsing("Drag & Offset", |ui| {
-            Grid::new("DragAndOffsetSettings")
-                .num_columns(2)
-                .show(ui, |ui| {
+                ui.add_enabled_ui(context.visibility.mode != DisplayMode::AllEdges, |ui| {
                     ui.checkbox(
-                        &mut canvas.screen_params.is_dragging_offset_enabled,
-                        "Enable",
+                        &mut context.visibility.show_hidden,
+                        "Show Hidden Edges Dashed",
+                    );
+
+                    Grid::new("VisibilitySettings")
+                        .num_columns(2)
+                        .show(ui, |ui| {
+                            ui.label("Hidden Stroke:");
+                            ui.add(&mut context.visibility.hidden_stroke);
+                            ui.end_row();
+                        });
+                });
+
+                if context.orthographic.is_enabled {
+                    ui.label(
+                        RichText::new("All edges are shown on orthographic projections.")
+                            .color(colors::DARK_GRAY),
                     );
+                }
+
+                ui.add_space(10.0);
+
+                ui.vertical_centered(|ui| {
                     if ui.button("Reset Settings").clicked() {
-                        canvas.screen_params.offset = Default::default();
-                        canvas.screen_params.is_dragging_offset_enabled = true;
+                        context.visibility = Default::default();
                     }
+                });
+            });
+
+            ui.add_space(10.0);
+
+            ui.collapsing("Surface Shading", |ui| {
+                ui.horizontal(|ui| {
+                    ui.checkbox(&mut context.rasterizer.is_enabled, "Enable");
+                    ui.checkbox(&mut context.rasterizer.show_edges, "Show Edges");
+                });
+
+                ui.add_space(5.0);
+
+                Grid::new("ShadingSettings").num_columns(2).show(ui, |ui| {
+                    ui.label("Shading:");
+                    ui.horizontal(|ui| {
+                        ui.radio_value(&mut context.rasterizer.shading, Shading::Flat, "Flat");
+                        ui.radio_value(
+                            &mut context.rasterizer.shading,
+                            Shading::Gouraud,
+                            "Gouraud",
+                        );
+                    });
+                    ui.end_row();
+
+                    ui.label("Colors:");
+                    ui.horizontal(|ui| {
+                        ui.radio_value(
+                            &mut context.rasterizer.coloring,
+                            Coloring::Single,
+                            "Single",
+                        );
+                        ui.radio_value(
+                            &mut context.rasterizer.coloring,
+                            Coloring::PerFace,
+                            "Per Face",
+                        )
+                        .on_hover_text("Colors of faces from OFF file or generated ones.");
+                    });
                     ui.end_row();
 
-                    ui.label("X:");
-                    ui.label(format!("{:.2}", canvas.screen_params.offset.0));
+                    if context.rasterizer.coloring == Coloring::Single {
+                        ui.label("Color:");
+                        ui.color_edit_button_srgba(&mut context.rasterizer.color);
+                        ui.end_row();
+                    }
+
+                    ui.label("Ambient:");
+                    ui.add(
+                        DragValue::new(&mut context.rasterizer.ambient)
+                            .speed(0.01)
+                            .range(0.0..=1.0),
+                    );
                     ui.end_row();
-                    ui.label("Y:");
-                    ui.label(format!("{:.2}", -canvas.screen_params.offset.1));
+
+                    ui.label("Diffuse:");
+                    ui.add(
+                        DragValue::new(&mut context.rasterizer.diffuse)
+                            .speed(0.01)
+                            .range(0.0..=1.0),
+                    );
                     ui.end_row();
                 });
-        });
 
-        ui.add_space(10.0);
+                ui.add_space(5.0);
 
-        ui.collapsing("Euclidean Offset", |ui| {
-            Grid::new("OffsetSettings").num_columns(2).show(ui, |ui| {
-                ui.label("Offset X: ");
-                ui.add(
-                    DragValue::new(&mut context.offset.x)
-                        .speed(0.1)
-                        .suffix(" cm"),
-                );
-                ui.end_row();
+                ui.label("Direction to the Light:");
+                ui.horizontal(|ui| {
+                    ui.add(
+                        DragValue::new(&mut context.rasterizer.light.x)
+                            .speed(0.1)
+                            .prefix("x: "),
+                    );
+                    ui.add(
+                        DragValue::new(&mut context.rasterizer.light.y)
+                            .speed(0.1)
+                            .prefix("y: "),
+                    );
+                    ui.add(
+                        DragValue::new(&mut context.rasterizer.light.z)
+                            .speed(0.1)
+                            .prefix("z: "),
+                    );
+                });
 
-                ui.label("Offset Y: ");
-                ui.add(
-                    DragValue::new(&mut context.offset.y)
-                        .speed(0.1)
-                        .suffix(" cm"),
-                );
-                ui.end_row();
+                if context.orthographic.is_enabled {
+                    ui.label(
+                        RichText::new("Surface is not shown on orthographic projections.")
+                            .color(colors::DARK_GRAY),
+                    );
+                }
 
-                ui.label("Offset Z: ");
-                ui.add(
-                    DragValue::new(&mut context.offset.z)
-                        .speed(0.1)
-                        .suffix(" cm"),
-                );
-                ui.end_row();
+                ui.add_space(10.0);
+
+                ui.vertical_centered(|ui| {
+                    if ui.button("Reset Settings").clicked() {
+                        context.rasterizer = Default::default();
+                    }
+                });
             });
 
             ui.add_space(10.0);
 
-            ui.vertical_centered_justified(|ui| {
-                if ui.button("Apply").clicked() {
-                    context.offset.is_applied = true;
-                }
-            });
+            ui.collapsing("Multi-View Layout", |ui| {
+                ui.checkbox(&mut context.multi_view.is_enabled, "Enable")
+                    .on_hover_text("Front, top and right side views with the main one.");
 
-            ui.vertical_centered_justified(|ui| {
-                if ui.button("Clear Fields").clicked() {
-                    context.offset.x = 0.0;
-                    context.offset.y = 0.0;
-                    context.offset.z = 0.0;
-                }
-            });
+                ui.add_space(5.0);
 
-            ui.vertical_centered_justified(|ui| {
-                if ui.button("Reset Position").clicked() {
-                    context.offset.reset_position();
-                }
-            });
-        });
+                ui.checkbox(
+                    &mut context.multi_view.show_projection_lines,
+                    "Projection Lines",
+                )
+                .on_hover_text("Lines between the same points of the front view and other ones.");
+
+                Grid::new("MultiViewSettings")
+                    .num_columns(2)
+                    .show(ui, |ui| {
+                        ui.label("Stroke:");
+                        ui.add(&mut context.multi_view.projection_stroke);
+                        ui.end_row();
+                    });
 
-        ui.add_space(10.0);
+                ui.add_space(5.0);
 
-        ui.collapsing("Euclidean Rotation", |ui| {
-            Grid::new("RotationSettings").num_columns(2).show(ui, |ui| {
-                ui.label("OX Angle: ");
-                ui.add(
-                    DragValue::new(&mut context.rotation.angle_deg_x)
-                        .speed(1)
-                        .range(-360..=360)
-                        .suffix("°"),
+                ui.label(
+                    RichText::new("Every view is panned and zoomed separately.")
+                        .color(colors::DARK_GRAY),
                 );
-                ui.end_row();
 
-                ui.label("OY Angle: ");
-                ui.add(
-                    DragValue::new(&mut context.rotation.angle_deg_y)
-                        .speed(1)
-                        .range(-360..=360)
-                        .suffix("°"),
-                );
-                ui.end_row();
+                ui.add_space(10.0);
 
-                ui.label("OZ Angle: ");
-                ui.add(
-                    DragValue::new(&mut context.rotation.angle_deg_z)
-                        .speed(1)
-                        .range(-360..=360)
-                        .suffix("°"),
-                );
-                ui.end_row();
+                ui.vertical_centered(|ui| {
+                    if ui.button("Reset Views").clicked() {
+                        canvas.viewports = Default::default();
+                    }
+
+                    if ui.button("Reset Settings").clicked() {
+                        context.multi_view = Default::default();
+                    }
+                });
             });
 
             ui.add_space(10.0);
 
-            ui.vertical_centered_justified(|ui| {
-                if ui.button("Apply").clicked() {
-                    context.rotation.is_applied = true;
-                }
-            });
+            ui.collapsing("Orthographic Projections", |ui| {
+                ui.checkbox(&mut context.orthographic.is_enabled, "Enable");
 
-            ui.vertical_centered_justified(|ui| {
-                if ui.button("Clear Fields").clicked() {
-                    context.rotation.angle_deg_x = 0.0;
-                    context.rotation.angle_deg_y = 0.0;
-                    context.rotation.angle_deg_z = 0.0;
-                }
-            });
+                ui.add_space(5.0);
 
-            ui.vertical_centered_justified(|ui| {
-                if ui.button("Reset Position").clicked() {
-                    context.rotation.reset_position();
-                }
-            });
-        });
+                Grid::new("SelectOrthographicProjection")
+                    .num_columns(2)
+                    .show(ui, |ui| {
+                        ui.radio_value(
+                            &mut context.orthographic.mode,
+                            OrthographicMode::XOnZero,
+                            "X = 0",
+                        );
+                        ui.radio_value(
+                            &mut context.orthographic.mode,
+                            OrthographicMode::XOnP,
+                            "X = P",
+                        );
+                        ui.end_row();
 
-        ui.add_space(10.0);
+                        ui.radio_value(
+                            &mut context.orthographic.mode,
+                            OrthographicMode::YOnZero,
+                            "Y = 0",
+                        );
+                        ui.radio_value(
+                            &mut context.orthographic.mode,
+                            OrthographicMode::YOnP,
+                            "Y = P",
+                        );
+                        ui.end_row();
 
-        ui.collapsing("Model Settings", |ui| {
-            Grid::new("ModelSettings").num_columns(2).show(ui, |ui| {
-                ui.label("Radius:");
-                ui.add(
-                    DragValue::new(&mut context.model.radius)
-                        .speed(0.1)
-                        .range(0.1..=f32::MAX)
-                        .suffix(" cm"),
-                );
-                ui.end_row();
+                        ui.radio_value(
+                            &mut context.orthographic.mode,
+                            OrthographicMode::ZOnZero,
+                            "Z = 0",
+                        );
+                        ui.radio_value(
+                            &mut context.orthographic.mode,
+                            OrthographicMode::ZOnP,
+                            "Z = P",
+                        );
+                        ui.end_row();
+                    });
 
-                ui.label("Thickness:");
-                ui.add(
-                    DragValue::new(&mut context.model.thickness)
-                        .speed(0.1)
-                        .range(0.1..=f32::MAX)
-                        .suffix(" cm"),
-                );
-                ui.end_row();
+                match &context.orthographic.mode {
+                    OrthographicMode::XOnP | OrthographicMode::YOnP | OrthographicMode::ZOnP => {
+                        ui.add_space(5.0);
+
+                        ui.horizontal_wrapped(|ui| {
+                            ui.label("P: ");
+                            ui.add(
+                                DragValue::new(&mut context.orthographic.p)
+                                    .speed(0.1)
+                                    .suffix(" cm"),
+                            );
+                        });
+                    },
+                    _ => {},
+                }
             });
 
             ui.add_space(10.0);
 
-            ui.vertical_centered(|ui| {
-                if ui.button("Reset Settings").clicked() {
-                    context.model = Default::default();
-                }
-            });
-        });
+            ui.collapsing("Perspective Projection", |ui| {
+                ui.checkbox(&mut context.perspective.is_enabled, "Enable")
+                    .on_hover_text("Replaces the trimetric projection of the model and axes.");
 
-        ui.add_space(10.0);
+                ui.add_space(5.0);
 
-        ui.collapsing("Orthographic Projections", |ui| {
-            ui.checkbox(&mut context.orthographic.is_enabled, "Enable");
+                ui.label(RichText::new("Presets").color(colors::WHITE));
+                ui.add_space(5.0);
+                ui.horizontal(|ui| {
+                    if ui.button("1-Point").clicked() {
+                        context.perspective.set_preset(PerspectivePreset::OnePoint);
+                    }
+                    if ui.button("2-Point").clicked() {
+                        context.perspective.set_preset(PerspectivePreset::TwoPoint);
+                    }
+                    if ui.button("3-Point").clicked() {
+                        context
+                            .perspective
+                            .set_preset(PerspectivePreset::ThreePoint);
+                    }
+                });
 
-            ui.add_space(5.0);
+                ui.add_space(10.0);
 
-            Grid::new("SelectOrthographicProjection")
-                .num_columns(2)
-                .show(ui, |ui| {
-                    ui.radio_value(
-                        &mut context.orthographic.mode,
-                        OrthographicMode::XOnZero,
-                        "X = 0",
-                    );
-                    ui.radio_value(
-                        &mut context.orthographic.mode,
-                        OrthographicMode::XOnP,
-                        "X = P",
-                    );
-                    ui.end_row();
-
-                    ui.radio_value(
-                        &mut context.orthographic.mode,
-                        OrthographicMode::YOnZero,
-                        "Y = 0",
-                    );
-                    ui.radio_value(
-                        &mut context.orthographic.mode,
-                        OrthographicMode::YOnP,
-                        "Y = P",
-                    );
-                    ui.end_row();
+                let vectors = [
+                    ("Eye", &mut context.perspective.eye),
+                    ("Target", &mut context.perspective.target),
+                    ("Up", &mut context.perspective.up),
+                ];
+                for (name, point) in vectors {
+                    ui.label(RichText::new(name).color(colors::WHITE));
+                    ui.add_space(5.0);
+                    ui.horizontal(|ui| {
+                        ui.add(DragValue::new(&mut point.x).speed(0.1).prefix("X: "));
+                        ui.add(DragValue::new(&mut point.y).speed(0.1).prefix("Y: "));
+                        ui.add(DragValue::new(&mut point.z).speed(0.1).prefix("Z: "));
+                    });
+                    ui.add_space(5.0);
+                }
 
-                    ui.radio_value(
-                        &mut context.orthographic.mode,
-                        OrthographicMode::ZOnZero,
-                        "Z = 0",
-                    );
-                    ui.radio_value(
-                        &mut context.orthographic.mode,
-                        OrthographicMode::ZOnP,
-                        "Z = P",
-                    );
-                    ui.end_row();
-                });
+                ui.add_space(5.0);
 
-            match &context.orthographic.mode {
-                OrthographicMode::XOnP | OrthographicMode::YOnP | OrthographicMode::ZOnP => {
-                    ui.add_space(5.0);
+                Grid::new("PerspectiveSettings")
+                    .num_columns(2)
+                    .show(ui, |ui| {
+                        ui.label("Field of View:");
+                        ui.add(
+                            DragValue::new(&mut context.perspective.fov_deg)
+                                .speed(1)
+                                .range(1.0..=170.0)
+                                .suffix("°"),
+                        );
+                        ui.end_row();
 
-                    ui.horizontal_wrapped(|ui| {
-                        ui.label("P: ");
+                        ui.label("Near Plane:");
                         ui.add(
-                            DragValue::new(&mut context.orthographic.p)
+                            DragValue::new(&mut context.perspective.near)
                                 .speed(0.1)
+                                .range(0.01..=context.perspective.far)
                                 .suffix(" cm"),
                         );
+                        ui.end_row();
+
+                        ui.label("Far Plane:");
+                        ui.add(
+                            DragValue::new(&mut context.perspective.far)
+                                .speed(1)
+                                .range(context.perspective.near..=f32::MAX)
+                                .suffix(" cm"),
+                        );
+                        ui.end_row();
+
+                        ui.label("Distance:");
+                        ui.label(format!("{:.2} cm", context.perspective.distance()));
+                        ui.end_row();
                     });
-                },
-                _ => {},
-            }
+
+                ui.add_space(10.0);
+
+                ui.vertical_centered(|ui| {
+                    if ui.button("Reset Settings").clicked() {
+                        context.perspective = Default::default();
+                    }
+                });
+            });
         });
-    });
-}
 
-fn reset_to_defaults(context: &mut Context, canvas: &mut Canvas) {
-    canvas.screen_params = Default::default();
+        self.show_errors_if_some(ui);
+    }
+
+    fn show_errors_if_some(&mut self, ui: &egui::Ui) {
+        if let Some(error) = &mut self.error_box {
+            error.show(ui);
+
+            if error.is_closed() {
+                self.error_box = None;
+            }
+        }
+    }
+
+    fn reset_to_defaults(&self, context: &mut Context, canvas: &mut Canvas) {
+        canvas.screen_params = Default::default();
 
-    context.axes = Default::default();
-    context.model = Default::default();
+        context.axes = Default::default();
+        context.model = Default::default();
+        context.visibility = Default::default();
+        context.rasterizer = Default::default();
 
-    context.trimetric = Default::default();
-    context.orthographic = Default::default();
+        context.trimetric = Default::default();
+        context.orthographic = Default::default();
+        context.perspective = Default::default();
+        context.multi_view = Default::default();
+        canvas.viewports = Default::default();
 
-    context.animation = Default::default();
+        context.animation = Default::default();
 
-    context.offset = Default::default();
-    context.rotation = Default::default();
+        context.offset = Default::default();
+        context.rotation = Default::default();
+        context.arcball = Default::default();
+    }
 }
@@ -17,3 +17,11 @@ pub fn axis_red() -> Stroke {
 pub fn model_black(width: f32) -> Stroke {
     Stroke::new(width, colors::BLACK)
 }
+
+pub fn hidden_gray(width: f32) -> Stroke {
+    Stroke::new(width, colors::DARK_GRAY)
+}
+
+pub fn projection_gray() -> Stroke {
+    Stroke::new(0.5, colors::GRAY)
+}
@@ -8,7 +8,8 @@ pub fn show(app: &mut App, ui: &mut egui::Ui, _ctx: &egui::Context) {
         .default_width(components::settings::SETTINGS_PANEL_WIDTH)
         .show_separator_line(true)
         .show_inside(ui, |ui| {
-            components::settings::show_panel(&mut app.context, &mut app.canvas, ui);
+            app.settings
+                .show_panel(&mut app.context, &mut app.canvas, ui);
         });
 
     CentralPanel::default().show_inside(ui, |ui| {